@@ -0,0 +1,71 @@
+use bevy::prelude::*;
+use smooth_bevy_cameras::{
+    controllers::chase::{ChaseCameraBundle, ChaseCameraController, ChaseCameraPlugin},
+    LookTransformBundle, LookTransformPlugin,
+};
+
+fn main() {
+    App::new()
+        .insert_resource(Msaa { samples: 4 })
+        .add_plugins(DefaultPlugins)
+        .add_plugin(LookTransformPlugin)
+        .add_plugin(ChaseCameraPlugin::default())
+        .add_startup_system(setup.system())
+        .add_system(drive.system())
+        .run();
+}
+
+struct Car;
+
+/// set up a simple 3D scene
+fn setup(
+    mut commands: Commands,
+    mut meshes: ResMut<Assets<Mesh>>,
+    mut materials: ResMut<Assets<StandardMaterial>>,
+) {
+    // plane
+    commands.spawn_bundle(PbrBundle {
+        mesh: meshes.add(Mesh::from(shape::Plane { size: 50.0 })),
+        material: materials.add(Color::rgb(0.3, 0.5, 0.3).into()),
+        ..Default::default()
+    });
+
+    // car
+    let car = commands
+        .spawn_bundle(PbrBundle {
+            mesh: meshes.add(Mesh::from(shape::Box::new(1.0, 0.5, 2.0))),
+            material: materials.add(Color::rgb(0.8, 0.2, 0.2).into()),
+            transform: Transform::from_xyz(0.0, 0.25, 0.0),
+            ..Default::default()
+        })
+        .insert(Car)
+        .id();
+
+    // light
+    commands.spawn_bundle(PointLightBundle {
+        transform: Transform::from_xyz(4.0, 8.0, 4.0),
+        ..Default::default()
+    });
+
+    let (look_transform_bundle, transform) =
+        LookTransformBundle::new(Vec3::new(0.0, 3.0, 6.0), Vec3::new(0., 0., 0.), 0.5);
+
+    commands
+        .spawn_bundle(look_transform_bundle)
+        .insert_bundle(PerspectiveCameraBundle {
+            transform,
+            ..Default::default()
+        })
+        .insert_bundle(ChaseCameraBundle::new(ChaseCameraController::new(car)));
+}
+
+/// Drives the car around a figure eight, so the camera has corners to swing through.
+fn drive(time: Res<Time>, mut cars: Query<&mut Transform, With<Car>>) {
+    let t = 0.5 * time.seconds_since_startup() as f32;
+    let position = Vec3::new(10.0 * t.sin(), 0.25, 5.0 * (2.0 * t).sin());
+    let velocity = Vec3::new(10.0 * t.cos(), 0.0, 10.0 * (2.0 * t).cos());
+    for mut transform in cars.iter_mut() {
+        transform.translation = position;
+        transform.look_at(position + velocity, Vec3::Y);
+    }
+}
@@ -0,0 +1,61 @@
+use bevy::prelude::*;
+use smooth_bevy_cameras::{
+    controllers::isometric::{
+        IsometricCameraBundle, IsometricCameraController, IsometricCameraPlugin,
+    },
+    LookTransformBundle, LookTransformPlugin,
+};
+
+fn main() {
+    App::new()
+        .insert_resource(Msaa { samples: 4 })
+        .add_plugins(DefaultPlugins)
+        .add_plugin(LookTransformPlugin)
+        .add_plugin(IsometricCameraPlugin::default())
+        .add_startup_system(setup.system())
+        .run();
+}
+
+/// set up a simple 3D scene
+fn setup(
+    mut commands: Commands,
+    mut meshes: ResMut<Assets<Mesh>>,
+    mut materials: ResMut<Assets<StandardMaterial>>,
+) {
+    // plane
+    commands.spawn_bundle(PbrBundle {
+        mesh: meshes.add(Mesh::from(shape::Plane { size: 5.0 })),
+        material: materials.add(Color::rgb(0.3, 0.5, 0.3).into()),
+        ..Default::default()
+    });
+
+    // cube
+    commands.spawn_bundle(PbrBundle {
+        mesh: meshes.add(Mesh::from(shape::Cube { size: 1.0 })),
+        material: materials.add(Color::rgb(0.8, 0.7, 0.6).into()),
+        transform: Transform::from_xyz(0.0, 0.5, 0.0),
+        ..Default::default()
+    });
+
+    // light
+    commands.spawn_bundle(PointLightBundle {
+        transform: Transform::from_xyz(4.0, 8.0, 4.0),
+        ..Default::default()
+    });
+
+    // The controller moves the eye to its fixed angle on the first frame.
+    let (look_transform_bundle, transform) =
+        LookTransformBundle::new(Vec3::new(10.0, 10.0, 10.0), Vec3::new(0., 0., 0.), 0.9);
+
+    let mut camera = OrthographicCameraBundle::new_3d();
+    camera.orthographic_projection.scale = 3.0;
+    camera.transform = transform;
+
+    commands
+        .spawn_bundle(look_transform_bundle)
+        .insert_bundle(camera)
+        .insert_bundle(IsometricCameraBundle::new(IsometricCameraController {
+            grid_size: Some(0.25),
+            ..Default::default()
+        }));
+}
@@ -0,0 +1,44 @@
+use bevy::prelude::*;
+use smooth_bevy_cameras::{
+    controllers::pancam::{PanCamBundle, PanCamController, PanCamPlugin},
+    LookTransformBundle, LookTransformPlugin,
+};
+
+fn main() {
+    App::new()
+        .add_plugins(DefaultPlugins)
+        .add_plugin(LookTransformPlugin)
+        .add_plugin(PanCamPlugin::default())
+        .add_startup_system(setup.system())
+        .run();
+}
+
+/// set up a simple 2D scene
+fn setup(mut commands: Commands, mut materials: ResMut<Assets<ColorMaterial>>) {
+    // a grid of squares
+    for x in -5..=5 {
+        for y in -5..=5 {
+            let shade = if (x + y) % 2 == 0 { 0.3 } else { 0.6 };
+            commands.spawn_bundle(SpriteBundle {
+                material: materials.add(Color::rgb(shade, 0.5, 0.3).into()),
+                sprite: Sprite::new(Vec2::new(90.0, 90.0)),
+                transform: Transform::from_xyz(x as f32 * 100.0, y as f32 * 100.0, 0.0),
+                ..Default::default()
+            });
+        }
+    }
+
+    let (look_transform_bundle, transform) =
+        LookTransformBundle::new(Vec3::new(0.0, 0.0, 999.9), Vec3::ZERO, 0.8);
+
+    let mut camera = OrthographicCameraBundle::new_2d();
+    camera.transform = transform;
+
+    commands
+        .spawn_bundle(look_transform_bundle)
+        .insert_bundle(camera)
+        .insert_bundle(PanCamBundle::new(PanCamController {
+            bounds: Some((Vec2::splat(-550.0), Vec2::splat(550.0))),
+            ..Default::default()
+        }));
+}
@@ -0,0 +1,82 @@
+use bevy::prelude::*;
+use smooth_bevy_cameras::{
+    controllers::rail::{CameraRail, RailCameraBundle, RailCameraController, RailCameraPlugin},
+    LookTransformBundle, LookTransformPlugin, PathInterpolation,
+};
+
+fn main() {
+    App::new()
+        .insert_resource(Msaa { samples: 4 })
+        .add_plugins(DefaultPlugins)
+        .add_plugin(LookTransformPlugin)
+        .add_plugin(RailCameraPlugin::default())
+        .add_startup_system(setup.system())
+        .add_system(walk.system())
+        .run();
+}
+
+struct Player;
+
+/// set up a simple 3D scene
+fn setup(
+    mut commands: Commands,
+    mut meshes: ResMut<Assets<Mesh>>,
+    mut materials: ResMut<Assets<StandardMaterial>>,
+) {
+    // plane
+    commands.spawn_bundle(PbrBundle {
+        mesh: meshes.add(Mesh::from(shape::Plane { size: 30.0 })),
+        material: materials.add(Color::rgb(0.3, 0.5, 0.3).into()),
+        ..Default::default()
+    });
+
+    // player
+    let player = commands
+        .spawn_bundle(PbrBundle {
+            mesh: meshes.add(Mesh::from(shape::Cube { size: 1.0 })),
+            material: materials.add(Color::rgb(0.8, 0.7, 0.6).into()),
+            transform: Transform::from_xyz(0.0, 0.5, 0.0),
+            ..Default::default()
+        })
+        .insert(Player)
+        .id();
+
+    // light
+    commands.spawn_bundle(PointLightBundle {
+        transform: Transform::from_xyz(4.0, 8.0, 4.0),
+        ..Default::default()
+    });
+
+    // The rail swoops up and in over the middle of the level.
+    let rail = CameraRail::new(
+        vec![
+            Vec3::new(-12.0, 3.0, 10.0),
+            Vec3::new(-4.0, 4.0, 8.0),
+            Vec3::new(0.0, 7.0, 5.0),
+            Vec3::new(4.0, 4.0, 8.0),
+            Vec3::new(12.0, 3.0, 10.0),
+        ],
+        PathInterpolation::CatmullRom,
+    );
+
+    let (look_transform_bundle, transform) =
+        LookTransformBundle::new(Vec3::new(0.0, 7.0, 5.0), Vec3::new(0., 0., 0.), 0.8);
+
+    commands
+        .spawn_bundle(look_transform_bundle)
+        .insert_bundle(PerspectiveCameraBundle {
+            transform,
+            ..Default::default()
+        })
+        .insert_bundle(RailCameraBundle::new(RailCameraController::new(
+            player, rail,
+        )));
+}
+
+/// Walks the player back and forth across the level, so the camera rides along its rail.
+fn walk(time: Res<Time>, mut players: Query<&mut Transform, With<Player>>) {
+    let t = 0.3 * time.seconds_since_startup() as f32;
+    for mut transform in players.iter_mut() {
+        transform.translation = Vec3::new(12.0 * t.sin(), 0.5, 0.0);
+    }
+}
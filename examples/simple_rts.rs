@@ -0,0 +1,54 @@
+use bevy::prelude::*;
+use smooth_bevy_cameras::{
+    controllers::rts::{RtsCameraBundle, RtsCameraController, RtsCameraPlugin},
+    LookTransformBundle, LookTransformPlugin,
+};
+
+fn main() {
+    App::new()
+        .insert_resource(Msaa { samples: 4 })
+        .add_plugins(DefaultPlugins)
+        .add_plugin(LookTransformPlugin)
+        .add_plugin(RtsCameraPlugin::default())
+        .add_startup_system(setup.system())
+        .run();
+}
+
+/// set up a simple 3D scene
+fn setup(
+    mut commands: Commands,
+    mut meshes: ResMut<Assets<Mesh>>,
+    mut materials: ResMut<Assets<StandardMaterial>>,
+) {
+    // plane
+    commands.spawn_bundle(PbrBundle {
+        mesh: meshes.add(Mesh::from(shape::Plane { size: 5.0 })),
+        material: materials.add(Color::rgb(0.3, 0.5, 0.3).into()),
+        ..Default::default()
+    });
+
+    // cube
+    commands.spawn_bundle(PbrBundle {
+        mesh: meshes.add(Mesh::from(shape::Cube { size: 1.0 })),
+        material: materials.add(Color::rgb(0.8, 0.7, 0.6).into()),
+        transform: Transform::from_xyz(0.0, 0.5, 0.0),
+        ..Default::default()
+    });
+
+    // light
+    commands.spawn_bundle(PointLightBundle {
+        transform: Transform::from_xyz(4.0, 8.0, 4.0),
+        ..Default::default()
+    });
+
+    let (look_transform_bundle, transform) =
+        LookTransformBundle::new(Vec3::new(-2.0, 5.0, 5.0), Vec3::new(0., 0., 0.), 0.9);
+
+    commands
+        .spawn_bundle(look_transform_bundle)
+        .insert_bundle(PerspectiveCameraBundle {
+            transform,
+            ..Default::default()
+        })
+        .insert_bundle(RtsCameraBundle::new(RtsCameraController::default()));
+}
@@ -0,0 +1,193 @@
+use bevy::{
+    ecs::prelude::*,
+    input::{
+        mouse::{MouseMotion, MouseScrollUnit, MouseWheel},
+        prelude::*,
+    },
+    math::prelude::*,
+};
+use std::{
+    any::TypeId,
+    collections::{HashMap, HashSet},
+};
+
+/// Mouse input that has built up since an input-mapping system last read it.
+#[derive(Clone, Copy, Debug, Default, PartialEq)]
+pub struct PointerDelta {
+    /// Mouse motion in physical pixels.
+    pub motion: Vec2,
+    /// Vertical scrolling from wheels that scroll in lines.
+    pub wheel_lines: f32,
+    /// Vertical scrolling from touchpads and other devices that scroll in pixels.
+    pub wheel_pixels: f32,
+    /// Horizontal scrolling from tilt wheels that scroll in lines.
+    pub horizontal_wheel_lines: f32,
+    /// Horizontal scrolling from touchpads and other devices that scroll in pixels.
+    pub horizontal_wheel_pixels: f32,
+}
+
+impl PointerDelta {
+    /// All vertical scrolling, regardless of its unit.
+    pub fn wheel(&self) -> f32 {
+        self.wheel_lines + self.wheel_pixels
+    }
+
+    /// All horizontal scrolling, regardless of its unit.
+    pub fn horizontal_wheel(&self) -> f32 {
+        self.horizontal_wheel_lines + self.horizontal_wheel_pixels
+    }
+}
+
+/// The keys and mouse buttons that were pressed since an input-mapping system last read them.
+#[derive(Clone, Debug, Default, PartialEq)]
+pub struct Presses {
+    keys: HashSet<KeyCode>,
+    mouse_buttons: HashSet<MouseButton>,
+}
+
+impl Presses {
+    /// Like `Input::just_pressed`, but for every press since the last read rather than only this frame's.
+    pub fn just_pressed(&self, key: KeyCode) -> bool {
+        self.keys.contains(&key)
+    }
+
+    /// Like `Input::just_pressed`, but for every press since the last read rather than only this frame's.
+    pub fn just_pressed_mouse(&self, button: MouseButton) -> bool {
+        self.mouse_buttons.contains(&button)
+    }
+}
+
+/// A resource that collects mouse motion, scrolling, and presses every frame, until each input-mapping system takes them.
+///
+/// Bevy only keeps input events for two frames, and `Input::just_pressed` only for one, so a system that doesn't run every
+/// frame, e.g. one in a fixed timestep, would drop some of them or see them twice. Reading from here instead, a system gets
+/// all of the input since it last ran, however often that is. Every reader is keyed by a type, usually its control event,
+/// and is independent of the others.
+///
+/// ```rust
+/// fn map_my_input(mut input: ResMut<AccumulatedInput>) {
+///     let delta = input.take::<MyControlEvent>();
+///     let jumped = input.take_presses::<MyControlEvent>().just_pressed(KeyCode::Space);
+/// }
+/// ```
+#[derive(Debug, Default)]
+pub struct AccumulatedInput {
+    readers: HashMap<TypeId, PointerDelta>,
+    press_readers: HashMap<TypeId, Presses>,
+}
+
+impl AccumulatedInput {
+    /// The input since the last call with the same `R`. The first call starts collecting for `R` and returns nothing.
+    pub fn take<R: 'static>(&mut self) -> PointerDelta {
+        std::mem::take(self.readers.entry(TypeId::of::<R>()).or_default())
+    }
+
+    /// The presses since the last call with the same `R`. The first call starts collecting for `R` and returns nothing.
+    pub fn take_presses<R: 'static>(&mut self) -> Presses {
+        std::mem::take(self.press_readers.entry(TypeId::of::<R>()).or_default())
+    }
+
+    fn add(&mut self, delta: PointerDelta) {
+        for pending in self.readers.values_mut() {
+            pending.motion += delta.motion;
+            pending.wheel_lines += delta.wheel_lines;
+            pending.wheel_pixels += delta.wheel_pixels;
+            pending.horizontal_wheel_lines += delta.horizontal_wheel_lines;
+            pending.horizontal_wheel_pixels += delta.horizontal_wheel_pixels;
+        }
+    }
+
+    fn press(&mut self, keys: &[KeyCode], mouse_buttons: &[MouseButton]) {
+        for pending in self.press_readers.values_mut() {
+            pending.keys.extend(keys);
+            pending.mouse_buttons.extend(mouse_buttons);
+        }
+    }
+}
+
+pub(crate) fn accumulate_input_system(
+    mut input: ResMut<AccumulatedInput>,
+    mut mouse_motion_events: EventReader<MouseMotion>,
+    mut mouse_wheel_reader: EventReader<MouseWheel>,
+    keyboard: Res<Input<KeyCode>>,
+    mouse_buttons: Res<Input<MouseButton>>,
+) {
+    let mut delta = PointerDelta::default();
+    for event in mouse_motion_events.iter() {
+        delta.motion += event.delta;
+    }
+    for event in mouse_wheel_reader.iter() {
+        match event.unit {
+            MouseScrollUnit::Line => {
+                delta.wheel_lines += event.y;
+                delta.horizontal_wheel_lines += event.x;
+            }
+            MouseScrollUnit::Pixel => {
+                delta.wheel_pixels += event.y;
+                delta.horizontal_wheel_pixels += event.x;
+            }
+        }
+    }
+
+    if delta != PointerDelta::default() {
+        input.add(delta);
+    }
+
+    let keys: Vec<KeyCode> = keyboard.get_just_pressed().copied().collect();
+    let buttons: Vec<MouseButton> = mouse_buttons.get_just_pressed().copied().collect();
+    if !keys.is_empty() || !buttons.is_empty() {
+        input.press(&keys, &buttons);
+    }
+}
+
+// ████████╗███████╗███████╗████████╗
+// ╚══██╔══╝██╔════╝██╔════╝╚══██╔══╝
+//    ██║   █████╗  ███████╗   ██║
+//    ██║   ██╔══╝  ╚════██║   ██║
+//    ██║   ███████╗███████║   ██║
+//    ╚═╝   ╚══════╝╚══════╝   ╚═╝
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    struct A;
+    struct B;
+
+    #[test]
+    fn test_readers_take_input_independently() {
+        let mut input = AccumulatedInput::default();
+        assert_eq!(input.take::<A>(), PointerDelta::default());
+
+        let delta = PointerDelta {
+            motion: Vec2::new(1.0, 2.0),
+            wheel_lines: 1.0,
+            horizontal_wheel_pixels: -3.0,
+            ..Default::default()
+        };
+        input.add(delta);
+        assert_eq!(input.take::<B>(), PointerDelta::default());
+        input.add(delta);
+
+        // A missed a frame, so it gets both frames of input at once.
+        assert_eq!(input.take::<A>().motion, Vec2::new(2.0, 4.0));
+        assert_eq!(input.take::<A>(), PointerDelta::default());
+        assert_eq!(input.take::<B>(), delta);
+    }
+
+    #[test]
+    fn test_presses_are_latched_until_taken() {
+        let mut input = AccumulatedInput::default();
+        input.take_presses::<A>();
+
+        input.press(&[KeyCode::F], &[]);
+        input.press(&[], &[MouseButton::Left]);
+
+        // Both frames' presses are seen once, on the next read.
+        let presses = input.take_presses::<A>();
+        assert!(presses.just_pressed(KeyCode::F));
+        assert!(presses.just_pressed_mouse(MouseButton::Left));
+        assert!(!presses.just_pressed(KeyCode::G));
+        assert_eq!(input.take_presses::<A>(), Presses::default());
+    }
+}
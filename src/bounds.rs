@@ -0,0 +1,78 @@
+use crate::LookTransform;
+
+use bevy::math::prelude::*;
+use serde::{Deserialize, Serialize};
+
+/// Which point of a `LookTransform` is kept inside a `LookTransformBounds`.
+#[derive(Clone, Copy, Debug, Deserialize, PartialEq, Serialize)]
+pub enum BoundedPoint {
+    Eye,
+    Target,
+}
+
+/// Keeps the eye or target of a camera's `LookTransform` inside an axis-aligned box, e.g. to stop an RTS camera from leaving
+/// the map. Set `min.y == max.y` to restrict the point to a rectangle on a plane.
+///
+/// The whole `LookTransform` is translated to satisfy the bounds, so the view direction is preserved. Bounds are applied
+/// after the controllers and before smoothing.
+#[derive(Clone, Copy, Debug, Deserialize, Serialize)]
+pub struct LookTransformBounds {
+    pub min: Vec3,
+    pub max: Vec3,
+    pub point: BoundedPoint,
+}
+
+impl LookTransformBounds {
+    pub fn new(min: Vec3, max: Vec3, point: BoundedPoint) -> Self {
+        Self { min, max, point }
+    }
+
+    pub fn clamp(&self, transform: LookTransform) -> LookTransform {
+        let p = match self.point {
+            BoundedPoint::Eye => transform.eye,
+            BoundedPoint::Target => transform.target,
+        };
+        let offset = p.max(self.min).min(self.max) - p;
+
+        LookTransform {
+            eye: transform.eye + offset,
+            target: transform.target + offset,
+            ..transform
+        }
+    }
+}
+
+// ████████╗███████╗███████╗████████╗
+// ╚══██╔══╝██╔════╝██╔════╝╚══██╔══╝
+//    ██║   █████╗  ███████╗   ██║
+//    ██║   ██╔══╝  ╚════██║   ██║
+//    ██║   ███████╗███████║   ██║
+//    ╚═╝   ╚══════╝╚══════╝   ╚═╝
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_clamp_target_preserves_view() {
+        let bounds = LookTransformBounds::new(
+            Vec3::new(-10.0, 0.0, -10.0),
+            Vec3::new(10.0, 0.0, 10.0),
+            BoundedPoint::Target,
+        );
+        let t = LookTransform::new(Vec3::new(15.0, 5.0, 5.0), Vec3::new(15.0, 0.0, 0.0));
+
+        let clamped = bounds.clamp(t);
+
+        assert_eq!(clamped.target, Vec3::new(10.0, 0.0, 0.0));
+        assert_eq!(clamped.eye, Vec3::new(10.0, 5.0, 5.0));
+    }
+
+    #[test]
+    fn test_inside_bounds_is_unchanged() {
+        let bounds = LookTransformBounds::new(Vec3::splat(-1.0), Vec3::splat(1.0), BoundedPoint::Eye);
+        let t = LookTransform::new(Vec3::ZERO, Vec3::new(0.0, 0.0, 5.0));
+
+        assert_eq!(bounds.clamp(t), t);
+    }
+}
@@ -0,0 +1,74 @@
+use crate::{
+    controllers::{chase::ChaseCameraState, fps::FpsCameraState, orbit::OrbitCameraState},
+    CameraCollision, LookTransform, LookTransformOffset, LookTransformTween, Smoother,
+};
+
+use bevy::{ecs::prelude::*, math::prelude::*};
+
+/// Teleports `camera` to `transform`, e.g. when respawning a player or cutting to another part of the level. Unlike writing
+/// the `LookTransform` directly, this also resets the camera's `Smoother` and `CameraCollision`, stops any zoom, momentum,
+/// head bob, or swing in its controller's state, and cancels any `LookTransformTween`, so the next frame shows the new
+/// pose instead of easing over from the old one.
+///
+/// ```rust
+/// events.send(CameraJumpEvent::new(camera, spawn_point + Vec3::new(0.0, 2.0, 5.0), spawn_point));
+/// ```
+#[derive(Clone, Copy, Debug)]
+pub struct CameraJumpEvent {
+    pub camera: Entity,
+    pub transform: LookTransform,
+}
+
+impl CameraJumpEvent {
+    /// Jumps to look from `eye` to `target` with `Vec3::Y` as the up direction.
+    pub fn new(camera: Entity, eye: Vec3, target: Vec3) -> Self {
+        Self {
+            camera,
+            transform: LookTransform::new(eye, target),
+        }
+    }
+}
+
+pub(crate) fn camera_jump_system(
+    mut commands: Commands,
+    mut events: EventReader<CameraJumpEvent>,
+    mut cameras: Query<(
+        &mut LookTransform,
+        Option<&mut Smoother>,
+        Option<&mut CameraCollision>,
+        Option<&LookTransformTween>,
+        Option<&mut OrbitCameraState>,
+        Option<&mut FpsCameraState>,
+        Option<&mut LookTransformOffset>,
+        Option<&mut ChaseCameraState>,
+    )>,
+) {
+    for event in events.iter() {
+        let (mut transform, smoother, collision, tween, orbit, fps, offset, chase) =
+            if let Ok(camera) = cameras.get_mut(event.camera) {
+                camera
+            } else {
+                continue;
+            };
+
+        *transform = event.transform;
+        if let Some(mut smoother) = smoother {
+            smoother.reset();
+        }
+        if let Some(mut collision) = collision {
+            collision.reset();
+        }
+        if let Some(mut orbit) = orbit {
+            orbit.reset_motion();
+        }
+        if let Some(mut fps) = fps {
+            fps.reset_motion(offset);
+        }
+        if let Some(mut chase) = chase {
+            chase.reset_motion();
+        }
+        if tween.is_some() {
+            commands.entity(event.camera).remove::<LookTransformTween>();
+        }
+    }
+}
@@ -0,0 +1,306 @@
+use crate::{look_angles::normalize_or, ControlTimestep, LookTransform, ReducedMotion};
+
+use bevy::{core::Time, ecs::prelude::*, math::prelude::*};
+use serde::{Deserialize, Serialize};
+
+/// How a `CameraPathPlayer` moves between keyframes.
+#[derive(Clone, Copy, Debug, Deserialize, PartialEq, Serialize)]
+pub enum PathInterpolation {
+    /// Straight lines between keyframes, with sharp turns at each one.
+    Linear,
+    /// A Catmull-Rom spline through the keyframes, which turns smoothly at each one. The spline is parametrized by the
+    /// keyframe times, so the speed stays smooth through keyframes that are unevenly spaced in time.
+    CatmullRom,
+}
+
+impl Default for PathInterpolation {
+    fn default() -> Self {
+        PathInterpolation::CatmullRom
+    }
+}
+
+/// A `LookTransform` at a point in time, in seconds from the start of the path.
+#[derive(Clone, Copy, Debug, Deserialize, PartialEq, Serialize)]
+pub struct CameraKeyframe {
+    pub time: f32,
+    pub transform: LookTransform,
+}
+
+/// A sequence of keyframes in order of time. Paths can be saved and loaded with any serde format, like RON or JSON:
+///
+/// ```rust
+/// std::fs::write("tour.ron", ron::to_string(&recorder.path)?)?;
+/// let path: CameraPath = ron::from_str(&std::fs::read_to_string("tour.ron")?)?;
+/// ```
+#[derive(Clone, Debug, Default, Deserialize, PartialEq, Serialize)]
+pub struct CameraPath {
+    pub keyframes: Vec<CameraKeyframe>,
+}
+
+impl CameraPath {
+    /// Appends a keyframe. It should be later than the last keyframe.
+    pub fn push(&mut self, time: f32, transform: LookTransform) {
+        self.keyframes.push(CameraKeyframe { time, transform });
+    }
+
+    /// The time of the last keyframe.
+    pub fn duration(&self) -> f32 {
+        self.keyframes.last().map_or(0.0, |k| k.time)
+    }
+
+    /// The transform at `time`, which is clamped to the span of the keyframes. Returns `None` if the path is empty.
+    pub fn sample(&self, time: f32, interpolation: PathInterpolation) -> Option<LookTransform> {
+        let keyframes = &self.keyframes;
+        let last = keyframes.len().checked_sub(1)?;
+
+        // The segment that `time` falls in runs from keyframe `i` to `i + 1`.
+        let i = match keyframes.iter().rposition(|k| k.time <= time) {
+            None => return Some(keyframes[0].transform),
+            Some(i) if i == last => return Some(keyframes[last].transform),
+            Some(i) => i,
+        };
+        let (k1, k2) = (&keyframes[i], &keyframes[i + 1]);
+        let span = k2.time - k1.time;
+        let s = if span > 0.0 {
+            (time - k1.time) / span
+        } else {
+            1.0
+        };
+
+        let interpolate = |point: fn(&LookTransform) -> Vec3| match interpolation {
+            PathInterpolation::Linear => point(&k1.transform).lerp(point(&k2.transform), s),
+            PathInterpolation::CatmullRom => {
+                // The ends of the path are repeated so the spline still reaches them.
+                let key = |k: &CameraKeyframe| (k.time, point(&k.transform));
+                let k0 = key(&keyframes[i.saturating_sub(1)]);
+                let k3 = key(&keyframes[(i + 2).min(last)]);
+                let (k1, k2) = (key(k1), key(k2));
+                hermite(k1.1, k2.1, tangent(k0, k1, k2), tangent(k1, k2, k3), span, s)
+            }
+        };
+
+        Some(LookTransform {
+            eye: interpolate(|t| t.eye),
+            target: interpolate(|t| t.target),
+            // Opposite up vectors cancel out between their keyframes, so keep the earlier one there.
+            up: normalize_or(interpolate(|t| t.up), k1.transform.up),
+        })
+    }
+}
+
+/// The point at `s` in `[0.0, 1.0]` along the uniform Catmull-Rom segment from `p1` to `p2`.
+pub(crate) fn catmull_rom(p0: Vec3, p1: Vec3, p2: Vec3, p3: Vec3, s: f32) -> Vec3 {
+    let s2 = s * s;
+    let s3 = s2 * s;
+
+    0.5 * (2.0 * p1
+        + s * (p2 - p0)
+        + s2 * (2.0 * p0 - 5.0 * p1 + 4.0 * p2 - p3)
+        + s3 * (3.0 * p1 - p0 - 3.0 * p2 + p3))
+}
+
+/// The velocity of a Catmull-Rom spline at the keyframe `at`, given as a time and point, from the slopes to its neighbors
+/// weighted by their times. At the ends of a path, where a neighbor is repeated, it's the slope to the other neighbor.
+fn tangent(prev: (f32, Vec3), at: (f32, Vec3), next: (f32, Vec3)) -> Vec3 {
+    let (dt0, dt1) = (at.0 - prev.0, next.0 - at.0);
+    let slope = |from: (f32, Vec3), to: (f32, Vec3), dt: f32| (to.1 - from.1) / dt;
+
+    match (dt0 > 0.0, dt1 > 0.0) {
+        (true, true) => (dt1 * slope(prev, at, dt0) + dt0 * slope(at, next, dt1)) / (dt0 + dt1),
+        (true, false) => slope(prev, at, dt0),
+        (false, true) => slope(at, next, dt1),
+        (false, false) => Vec3::ZERO,
+    }
+}
+
+/// The point at `s` in `[0.0, 1.0]` along the cubic Hermite segment that takes `span` seconds from `p1` to `p2`, with
+/// velocities `m1` and `m2` at its ends.
+fn hermite(p1: Vec3, p2: Vec3, m1: Vec3, m2: Vec3, span: f32, s: f32) -> Vec3 {
+    let s2 = s * s;
+    let s3 = s2 * s;
+
+    (2.0 * s3 - 3.0 * s2 + 1.0) * p1
+        + (s3 - 2.0 * s2 + s) * span * m1
+        + (-2.0 * s3 + 3.0 * s2) * p2
+        + (s3 - s2) * span * m2
+}
+
+/// Records a camera's `LookTransform` into a `CameraPath` while `recording` is set, every frame or every `interval` seconds.
+/// Take the `path` when you're done, e.g. to save it or hand it to a `CameraPathPlayer`.
+#[derive(Clone, Debug, Default)]
+pub struct CameraPathRecorder {
+    pub path: CameraPath,
+    pub recording: bool,
+    /// Seconds between keyframes, or `None` for a keyframe every frame.
+    pub interval: Option<f32>,
+    elapsed: f32,
+}
+
+impl CameraPathRecorder {
+    pub fn new(interval: Option<f32>) -> Self {
+        Self {
+            recording: true,
+            interval,
+            ..Default::default()
+        }
+    }
+}
+
+/// Plays a `CameraPath` back on a camera's `LookTransform`, e.g. for trailers or automated screenshot tours. It overrides
+/// the camera's controller while present, and removes itself once the path is finished unless it loops. While
+/// `ReducedMotion` is enabled, the camera cuts from keyframe to keyframe at their times instead of moving between them.
+#[derive(Clone, Debug)]
+pub struct CameraPathPlayer {
+    pub path: CameraPath,
+    pub interpolation: PathInterpolation,
+    /// Starts over from the beginning after the last keyframe instead of finishing.
+    pub looping: bool,
+    elapsed: f32,
+}
+
+impl CameraPathPlayer {
+    pub fn new(path: CameraPath, interpolation: PathInterpolation) -> Self {
+        Self {
+            path,
+            interpolation,
+            looping: false,
+            elapsed: 0.0,
+        }
+    }
+
+    /// Seconds since playback started, wrapped around the path's duration if it loops.
+    pub fn elapsed(&self) -> f32 {
+        self.elapsed
+    }
+}
+
+pub(crate) fn camera_path_recorder_system(
+    time: Res<Time>,
+    timestep: Res<ControlTimestep>,
+    mut cameras: Query<(&mut CameraPathRecorder, &LookTransform)>,
+) {
+    for (mut recorder, transform) in cameras.iter_mut() {
+        if !recorder.recording {
+            continue;
+        }
+
+        let due = match (recorder.path.keyframes.last(), recorder.interval) {
+            (Some(last), Some(interval)) => recorder.elapsed - last.time >= interval,
+            _ => true,
+        };
+        if due {
+            let time = recorder.elapsed;
+            recorder.path.push(time, *transform);
+        }
+
+        recorder.elapsed += timestep.frame_delta_seconds(&time);
+    }
+}
+
+pub(crate) fn camera_path_player_system(
+    mut commands: Commands,
+    time: Res<Time>,
+    timestep: Res<ControlTimestep>,
+    reduced_motion: Res<ReducedMotion>,
+    mut cameras: Query<(Entity, &mut CameraPathPlayer, &mut LookTransform)>,
+) {
+    for (entity, mut player, mut transform) in cameras.iter_mut() {
+        let duration = player.path.duration();
+
+        let sampled = if reduced_motion.allows_inertia() {
+            player.path.sample(player.elapsed, player.interpolation)
+        } else {
+            let keyframes = &player.path.keyframes;
+            keyframes
+                .iter()
+                .rev()
+                .find(|keyframe| keyframe.time <= player.elapsed)
+                .or_else(|| keyframes.first())
+                .map(|keyframe| keyframe.transform)
+        };
+        if let Some(sampled) = sampled {
+            *transform = sampled;
+        }
+
+        if player.elapsed >= duration {
+            if player.looping && duration > 0.0 {
+                player.elapsed %= duration;
+            } else {
+                commands.entity(entity).remove::<CameraPathPlayer>();
+                continue;
+            }
+        }
+        player.elapsed += timestep.frame_delta_seconds(&time);
+    }
+}
+
+// ████████╗███████╗███████╗████████╗
+// ╚══██╔══╝██╔════╝██╔════╝╚══██╔══╝
+//    ██║   █████╗  ███████╗   ██║
+//    ██║   ██╔══╝  ╚════██║   ██║
+//    ██║   ███████╗███████║   ██║
+//    ╚═╝   ╚══════╝╚══════╝   ╚═╝
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn path() -> CameraPath {
+        let mut path = CameraPath::default();
+        path.push(0.0, LookTransform::new(Vec3::new(0.0, 0.0, 5.0), Vec3::ZERO));
+        path.push(1.0, LookTransform::new(Vec3::new(5.0, 0.0, 0.0), Vec3::ZERO));
+        path.push(3.0, LookTransform::new(Vec3::new(0.0, 0.0, -5.0), Vec3::ZERO));
+
+        path
+    }
+
+    #[test]
+    fn test_sample_passes_through_keyframes() {
+        let path = path();
+
+        for interpolation in [PathInterpolation::Linear, PathInterpolation::CatmullRom].iter() {
+            for keyframe in path.keyframes.iter() {
+                let sampled = path.sample(keyframe.time, *interpolation).unwrap();
+                assert!(sampled.eye.abs_diff_eq(keyframe.transform.eye, 1e-5));
+            }
+
+            // Times outside the path clamp to its ends.
+            assert_eq!(path.sample(-1.0, *interpolation), Some(path.keyframes[0].transform));
+            assert_eq!(path.sample(4.0, *interpolation), Some(path.keyframes[2].transform));
+        }
+
+        assert_eq!(CameraPath::default().sample(0.0, PathInterpolation::Linear), None);
+    }
+
+    #[test]
+    fn test_catmull_rom_speed_is_smooth_through_uneven_keyframes() {
+        let path = path();
+        let velocity = |time: f32| {
+            let eye = |time| path.sample(time, PathInterpolation::CatmullRom).unwrap().eye;
+            (eye(time + 1e-3) - eye(time)) / 1e-3
+        };
+
+        // The keyframe at 1s is between segments of 1s and 2s.
+        assert!(velocity(1.0 - 1e-3).abs_diff_eq(velocity(1.0), 1e-1));
+    }
+
+    #[test]
+    fn test_linear_sample_between_keyframes() {
+        let sampled = path().sample(2.0, PathInterpolation::Linear).unwrap();
+
+        assert!(sampled.eye.abs_diff_eq(Vec3::new(2.5, 0.0, -2.5), 1e-5));
+        assert_eq!(sampled.target, Vec3::ZERO);
+    }
+
+    #[test]
+    fn test_opposite_up_vectors_fall_back_to_the_earlier_keyframe() {
+        let mut path = CameraPath::default();
+        path.push(0.0, LookTransform::look_at(Vec3::new(0.0, 0.0, 5.0), Vec3::ZERO, Vec3::Y));
+        path.push(1.0, LookTransform::look_at(Vec3::new(0.0, 0.0, 5.0), Vec3::ZERO, -Vec3::Y));
+
+        for interpolation in [PathInterpolation::Linear, PathInterpolation::CatmullRom].iter() {
+            let sampled = path.sample(0.5, *interpolation).unwrap();
+            assert_eq!(sampled.up, Vec3::Y);
+        }
+    }
+}
@@ -0,0 +1,274 @@
+use crate::{ControlTimestep, Easing, LookTransform};
+
+use bevy::{core::Time, ecs::prelude::*};
+use serde::{Deserialize, Serialize};
+
+/// A `LookTransform` at a point in time on a `CameraTrack`. The `easing` shapes the move from the previous keyframe to this
+/// one.
+#[derive(Clone, Copy, Debug, Deserialize, PartialEq, Serialize)]
+pub struct TrackKeyframe {
+    pub time: f32,
+    pub transform: LookTransform,
+    pub easing: Easing,
+}
+
+/// What a `CameraTrack` does when it reaches its last keyframe.
+#[derive(Clone, Copy, Debug, Deserialize, Eq, PartialEq, Serialize)]
+pub enum TrackPlayback {
+    /// Stops, then blends back into the camera's controller.
+    Once,
+    /// Jumps back to the first keyframe and plays again.
+    Loop,
+    /// Plays backward to the first keyframe, then forward again.
+    PingPong,
+}
+
+impl Default for TrackPlayback {
+    fn default() -> Self {
+        TrackPlayback::Once
+    }
+}
+
+/// A cinematic camera move through a sequence of keyframes, in order of time. The track overrides the camera's controller
+/// while present. A `TrackPlayback::Once` track then blends back into the controller over `blend_out` seconds and removes
+/// itself.
+///
+/// A `CameraTrackEvent` is sent as each keyframe is reached, e.g. to trigger dialogue or effects in a cut-scene.
+#[derive(Clone, Debug, Default, Deserialize, Serialize)]
+pub struct CameraTrack {
+    pub keyframes: Vec<TrackKeyframe>,
+    pub playback: TrackPlayback,
+    /// Seconds to hand control back to the camera's controller after a `TrackPlayback::Once` track ends.
+    pub blend_out: f32,
+    #[serde(skip)]
+    elapsed: Option<f32>,
+}
+
+impl CameraTrack {
+    pub fn new(keyframes: Vec<TrackKeyframe>, playback: TrackPlayback) -> Self {
+        Self {
+            keyframes,
+            playback,
+            ..Default::default()
+        }
+    }
+
+    pub fn with_blend_out(mut self, blend_out: f32) -> Self {
+        self.blend_out = blend_out;
+        self
+    }
+
+    /// The time of the last keyframe.
+    pub fn duration(&self) -> f32 {
+        self.keyframes.last().map_or(0.0, |k| k.time)
+    }
+
+    /// Seconds since playback started.
+    pub fn elapsed(&self) -> f32 {
+        self.elapsed.unwrap_or(0.0)
+    }
+
+    /// The time on the track after playing for `elapsed` seconds, accounting for looping.
+    fn track_time(&self, elapsed: f32) -> f32 {
+        let duration = self.duration();
+        if duration <= 0.0 {
+            return 0.0;
+        }
+
+        match self.playback {
+            TrackPlayback::Once => elapsed.min(duration),
+            TrackPlayback::Loop => elapsed % duration,
+            TrackPlayback::PingPong => {
+                let t = elapsed % (2.0 * duration);
+                if t > duration {
+                    2.0 * duration - t
+                } else {
+                    t
+                }
+            }
+        }
+    }
+
+    /// The transform at `time` on the track, which is clamped to the span of the keyframes. Returns `None` if there are no
+    /// keyframes.
+    pub fn sample(&self, time: f32) -> Option<LookTransform> {
+        let keyframes = &self.keyframes;
+        let next = keyframes.iter().position(|k| k.time > time);
+
+        match next {
+            Some(0) => keyframes.first().map(|k| k.transform),
+            Some(i) => {
+                let (k1, k2) = (&keyframes[i - 1], &keyframes[i]);
+                let s = (time - k1.time) / (k2.time - k1.time);
+
+                Some(k1.transform.lerp(&k2.transform, k2.easing.apply(s)))
+            }
+            None => keyframes.last().map(|k| k.transform),
+        }
+    }
+
+    /// The indices of the keyframes that are reached after `previous` and up to `elapsed` seconds of playback, in order. With
+    /// no `previous`, playback has just started and the first keyframe is included.
+    fn reached_keyframes(&self, previous: Option<f32>, elapsed: f32) -> Vec<usize> {
+        let len = self.keyframes.len();
+        let duration = self.duration();
+
+        // The times within one period of playback when each keyframe is reached.
+        let mut reach_times: Vec<(f32, usize)> = self
+            .keyframes
+            .iter()
+            .enumerate()
+            .map(|(i, k)| (k.time, i))
+            .collect();
+        let period = match self.playback {
+            TrackPlayback::Once => f32::INFINITY,
+            TrackPlayback::Loop => duration,
+            TrackPlayback::PingPong => {
+                // On the way back, skip the ends, which are shared with the way forward.
+                for i in (1..len.saturating_sub(1)).rev() {
+                    reach_times.push((2.0 * duration - self.keyframes[i].time, i));
+                }
+                2.0 * duration
+            }
+        };
+
+        let reached = |t: f32| previous.map_or(t <= elapsed, |p| p < t && t <= elapsed);
+
+        if !(period > 0.0 && period.is_finite()) {
+            return reach_times
+                .into_iter()
+                .filter(|&(t, _)| reached(t))
+                .map(|(_, i)| i)
+                .collect();
+        }
+
+        let first_cycle = previous.map_or(0, |p| (p / period).floor() as u32);
+        let last_cycle = (elapsed / period).floor() as u32;
+        let mut indices = Vec::new();
+        for cycle in first_cycle..=last_cycle {
+            let offset = cycle as f32 * period;
+            for &(t, i) in reach_times.iter() {
+                if reached(offset + t) {
+                    indices.push(i);
+                }
+            }
+        }
+
+        indices
+    }
+}
+
+/// Sent by the track playback system for cameras with a `CameraTrack`.
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub enum CameraTrackEvent {
+    /// The camera reached the keyframe at this index.
+    KeyframeReached(Entity, usize),
+    /// A `TrackPlayback::Once` track finished blending out and was removed.
+    Finished(Entity),
+}
+
+impl CameraTrackEvent {
+    /// The camera entity that this event is about.
+    pub fn camera(&self) -> Entity {
+        match *self {
+            Self::KeyframeReached(camera, _) | Self::Finished(camera) => camera,
+        }
+    }
+}
+
+pub(crate) fn camera_track_system(
+    mut commands: Commands,
+    time: Res<Time>,
+    timestep: Res<ControlTimestep>,
+    mut events: EventWriter<CameraTrackEvent>,
+    mut cameras: Query<(Entity, &mut CameraTrack, &mut LookTransform)>,
+) {
+    for (entity, mut track, mut transform) in cameras.iter_mut() {
+        let previous = track.elapsed;
+        let elapsed = previous.map_or(0.0, |p| p + timestep.frame_delta_seconds(&time));
+        track.elapsed = Some(elapsed);
+
+        for index in track.reached_keyframes(previous, elapsed) {
+            events.send(CameraTrackEvent::KeyframeReached(entity, index));
+        }
+
+        let sampled = if let Some(sampled) = track.sample(track.track_time(elapsed)) {
+            sampled
+        } else {
+            continue;
+        };
+
+        let blend_time = elapsed - track.duration();
+        if track.playback != TrackPlayback::Once || blend_time <= 0.0 {
+            *transform = sampled;
+        } else if blend_time < track.blend_out {
+            // The controller moves the camera from where the track left it last frame, so this hands over gradually.
+            *transform = sampled.lerp(&transform, blend_time / track.blend_out);
+        } else {
+            if track.blend_out <= 0.0 {
+                *transform = sampled;
+            }
+            commands.entity(entity).remove::<CameraTrack>();
+            events.send(CameraTrackEvent::Finished(entity));
+        }
+    }
+}
+
+// ████████╗███████╗███████╗████████╗
+// ╚══██╔══╝██╔════╝██╔════╝╚══██╔══╝
+//    ██║   █████╗  ███████╗   ██║
+//    ██║   ██╔══╝  ╚════██║   ██║
+//    ██║   ███████╗███████║   ██║
+//    ╚═╝   ╚══════╝╚══════╝   ╚═╝
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    use bevy::math::prelude::*;
+
+    fn track(playback: TrackPlayback) -> CameraTrack {
+        let keyframe = |time: f32, x: f32| TrackKeyframe {
+            time,
+            transform: LookTransform::new(Vec3::new(x, 0.0, 5.0), Vec3::ZERO),
+            easing: Easing::Linear,
+        };
+
+        CameraTrack::new(
+            vec![keyframe(0.0, 0.0), keyframe(1.0, 1.0), keyframe(2.0, 3.0)],
+            playback,
+        )
+    }
+
+    #[test]
+    fn test_sample_eases_between_keyframes() {
+        let track = track(TrackPlayback::Once);
+
+        assert_eq!(track.sample(1.5).unwrap().eye, Vec3::new(2.0, 0.0, 5.0));
+        assert_eq!(track.sample(5.0).unwrap().eye, Vec3::new(3.0, 0.0, 5.0));
+    }
+
+    #[test]
+    fn test_ping_pong_plays_backward() {
+        let track = track(TrackPlayback::PingPong);
+
+        assert_eq!(track.track_time(1.5), 1.5);
+        assert_eq!(track.track_time(2.5), 1.5);
+        assert_eq!(track.track_time(4.5), 0.5);
+    }
+
+    #[test]
+    fn test_reached_keyframes() {
+        let once = track(TrackPlayback::Once);
+        assert_eq!(once.reached_keyframes(None, 0.0), vec![0]);
+        assert_eq!(once.reached_keyframes(Some(0.5), 2.5), vec![1, 2]);
+        assert!(once.reached_keyframes(Some(2.5), 3.0).is_empty());
+
+        let looping = track(TrackPlayback::Loop);
+        // The end of one loop is reached at the same time as the start of the next.
+        assert_eq!(looping.reached_keyframes(Some(1.5), 2.5), vec![2, 0]);
+
+        let ping_pong = track(TrackPlayback::PingPong);
+        assert_eq!(ping_pong.reached_keyframes(Some(1.5), 4.5), vec![2, 1, 0]);
+    }
+}
@@ -0,0 +1,141 @@
+use crate::{smooth, LookTransform};
+
+use bevy::math::prelude::*;
+
+// Keeps the eye from landing exactly on the target, where the look direction is undefined.
+const MIN_RADIUS: f32 = 0.01;
+
+/// Casts a ray from `origin` along the unit vector `direction`, returning the distance to the first hit within `max_distance`.
+///
+/// This is how the crate asks your physics engine (or any other scene query) about level geometry, so it doesn't need to
+/// depend on one:
+///
+/// ```rust
+/// app.insert_resource(CameraRaycaster::new(|origin, direction, max_distance| {
+///     my_physics_world.cast_ray(origin, direction, max_distance).map(|hit| hit.distance)
+/// }));
+/// ```
+pub struct CameraRaycaster(Box<dyn Fn(Vec3, Vec3, f32) -> Option<f32> + Send + Sync>);
+
+impl CameraRaycaster {
+    pub fn new(cast_ray: impl Fn(Vec3, Vec3, f32) -> Option<f32> + Send + Sync + 'static) -> Self {
+        Self(Box::new(cast_ray))
+    }
+
+    pub fn cast_ray(&self, origin: Vec3, direction: Vec3, max_distance: f32) -> Option<f32> {
+        (self.0)(origin, direction, max_distance)
+    }
+}
+
+/// Keeps the eye from passing through geometry between it and the target. When the `CameraRaycaster` resource finds a hit,
+/// the eye is pulled in front of it; once the way is clear, the eye eases back out to where the `LookTransform` puts it.
+///
+/// Only the scene `Transform` is affected, so controllers still see the unobstructed `LookTransform`.
+#[derive(Clone, Copy, Debug)]
+pub struct CameraCollision {
+    /// How far in front of a hit to place the eye.
+    pub margin: f32,
+    /// Between `0.0` and `1.0`, where higher restores the radius more slowly after an obstruction clears. Like the other lag
+    /// weights, it's the fraction of the way left to go after 1/60 s.
+    pub restore_lag_weight: f32,
+    radius: Option<f32>,
+}
+
+impl Default for CameraCollision {
+    fn default() -> Self {
+        Self {
+            margin: 0.2,
+            restore_lag_weight: 0.9,
+            radius: None,
+        }
+    }
+}
+
+impl CameraCollision {
+    /// Forgets the eased radius, so the eye goes straight to where the `LookTransform` puts it.
+    pub(crate) fn reset(&mut self) {
+        self.radius = None;
+    }
+
+    pub(crate) fn resolve(
+        &mut self,
+        look_transform: LookTransform,
+        raycaster: &CameraRaycaster,
+        dt: f32,
+    ) -> LookTransform {
+        let desired_radius = look_transform.radius();
+        let back = -look_transform.look_direction();
+
+        let allowed_radius = raycaster
+            .cast_ray(look_transform.target, back, desired_radius + self.margin)
+            .map(|hit| (hit - self.margin).max(MIN_RADIUS).min(desired_radius))
+            .unwrap_or(desired_radius);
+
+        // Snap in immediately so we never clip, but ease back out.
+        let radius = match self.radius {
+            Some(previous) if previous < allowed_radius => {
+                smooth(previous, allowed_radius, self.restore_lag_weight, dt)
+            }
+            _ => allowed_radius,
+        };
+        self.radius = Some(radius);
+
+        LookTransform {
+            eye: look_transform.target + radius * back,
+            ..look_transform
+        }
+    }
+}
+
+// ████████╗███████╗███████╗████████╗
+// ╚══██╔══╝██╔════╝██╔════╝╚══██╔══╝
+//    ██║   █████╗  ███████╗   ██║
+//    ██║   ██╔══╝  ╚════██║   ██║
+//    ██║   ███████╗███████║   ██║
+//    ╚═╝   ╚══════╝╚══════╝   ╚═╝
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    use approx::assert_relative_eq;
+
+    #[test]
+    fn test_blocked_eye_snaps_in_and_eases_out() {
+        let look_transform = LookTransform::new(Vec3::new(0.0, 0.0, -5.0), Vec3::ZERO);
+        let mut collision = CameraCollision {
+            margin: 0.5,
+            restore_lag_weight: 0.5,
+            ..Default::default()
+        };
+
+        let wall = CameraRaycaster::new(|_, _, max_distance| Some(2.5f32).filter(|d| *d <= max_distance));
+        let resolved = collision.resolve(look_transform, &wall, 1.0 / 60.0);
+        assert_relative_eq!(resolved.radius(), 2.0);
+        assert_eq!(resolved.target, look_transform.target);
+
+        let clear = CameraRaycaster::new(|_, _, _| None);
+        let resolved = collision.resolve(look_transform, &clear, 1.0 / 60.0);
+        assert_relative_eq!(resolved.radius(), 3.5);
+    }
+
+    #[test]
+    fn test_restoring_the_radius_is_independent_of_the_frame_rate() {
+        let look_transform = LookTransform::new(Vec3::new(0.0, 0.0, -5.0), Vec3::ZERO);
+        let wall = CameraRaycaster::new(|_, _, max_distance| Some(2.5f32).filter(|d| *d <= max_distance));
+        let clear = CameraRaycaster::new(|_, _, _| None);
+
+        let restore = |steps: usize| {
+            let mut collision = CameraCollision {
+                margin: 0.5,
+                restore_lag_weight: 0.5,
+                ..Default::default()
+            };
+            collision.resolve(look_transform, &wall, 1.0 / 60.0);
+            let dt = 1.0 / 60.0 / steps as f32;
+            (0..steps).fold(look_transform, |_, _| collision.resolve(look_transform, &clear, dt))
+        };
+
+        assert_relative_eq!(restore(1).radius(), restore(4).radius(), epsilon = 1e-4);
+    }
+}
@@ -0,0 +1,210 @@
+use crate::{ControlTimestep, LookTransform};
+
+use bevy::{
+    core::Time,
+    ecs::{component::Component, prelude::*},
+};
+use serde::{Deserialize, Serialize};
+
+/// A control event with the step of the control systems that it was sent on, and how long that step was, so the event
+/// stream can be saved or sent over the network and played back the same way elsewhere.
+///
+/// Steps are counted from `0` like those of `ScriptedControlEvents`, so a recording can be replayed through a controller
+/// with:
+///
+/// ```rust
+/// ScriptedControlEvents::new(recording.into_iter().map(|stamped| (stamped.step, stamped.event)))
+/// ```
+#[derive(Clone, Copy, Debug, Deserialize, PartialEq, Serialize)]
+pub struct StampedControlEvent<E> {
+    pub step: u64,
+    pub delta_seconds: f32,
+    pub event: E,
+}
+
+/// A resource that stamps and keeps every control event of type `E` that the control systems see, whether it came from
+/// input mapping, a script, or gameplay code. Add it with `AddCameraController::add_control_event_recorder`.
+pub struct ControlEventRecorder<E> {
+    /// Events are only kept while this is set.
+    pub recording: bool,
+    events: Vec<StampedControlEvent<E>>,
+    step: u64,
+}
+
+impl<E> Default for ControlEventRecorder<E> {
+    fn default() -> Self {
+        Self {
+            recording: true,
+            events: Vec::new(),
+            step: 0,
+        }
+    }
+}
+
+impl<E> ControlEventRecorder<E> {
+    /// The events recorded so far, in the order they were sent.
+    pub fn events(&self) -> &[StampedControlEvent<E>] {
+        &self.events
+    }
+
+    /// Removes and returns the events recorded so far, e.g. to send the latest ones to spectators every frame. Steps keep
+    /// counting from where they were.
+    pub fn take(&mut self) -> Vec<StampedControlEvent<E>> {
+        std::mem::take(&mut self.events)
+    }
+
+    /// Stamps `events` with the current step and moves on to the next one.
+    fn record(&mut self, events: impl IntoIterator<Item = E>, delta_seconds: f32) {
+        if self.recording {
+            let step = self.step;
+            self.events
+                .extend(events.into_iter().map(|event| StampedControlEvent {
+                    step,
+                    delta_seconds,
+                    event,
+                }));
+        }
+        self.step += 1;
+    }
+}
+
+pub(crate) fn record_control_events_system<E: Component + Clone>(
+    time: Res<Time>,
+    timestep: Res<ControlTimestep>,
+    mut recorder: ResMut<ControlEventRecorder<E>>,
+    mut events: EventReader<E>,
+) {
+    let dt = timestep.delta_seconds(&time);
+    recorder.record(events.iter().cloned(), dt);
+}
+
+/// Control events whose effect only depends on the `LookTransform` they're applied to and a `Context` of controller
+/// settings and state, so they can be played back with `apply_events` outside of the ECS schedule.
+///
+/// The free-fly, RTS, isometric, and PanCam events implement this. The other controllers also depend on the rest of the
+/// world, like followed entities, the ground, or the camera's projection matrix, so replay their events through the
+/// controller with `ScriptedControlEvents` instead.
+pub trait ReplayableControlEvent: Sized {
+    /// What the events act on besides the `LookTransform`, e.g. the controller's settings and the scale of its
+    /// `OrthographicProjection`. Stateful parts are updated as the events are applied.
+    type Context;
+
+    /// The camera entity that this event controls.
+    fn camera(&self) -> Entity;
+
+    /// Applies the events of one control step that lasted `delta_seconds` to `transform` and `context`, the same way the
+    /// controller's control system does.
+    fn apply_step(
+        transform: &mut LookTransform,
+        context: &mut Self::Context,
+        events: &[&Self],
+        delta_seconds: f32,
+    );
+}
+
+/// Plays back the recorded `events` of `camera`, which must be in step order, on `transform` and `context`, e.g. to move a
+/// spectator's camera along with a player's camera on another machine. The events of other cameras are skipped, so one
+/// recording can drive several spectators.
+pub fn apply_events<E: ReplayableControlEvent>(
+    camera: Entity,
+    transform: &mut LookTransform,
+    context: &mut E::Context,
+    events: &[StampedControlEvent<E>],
+) {
+    let mut rest = events;
+    while let Some(first) = rest.first() {
+        let len = rest.iter().take_while(|e| e.step == first.step).count();
+        let (step, next) = rest.split_at(len);
+        let step_events: Vec<&E> = step
+            .iter()
+            .map(|stamped| &stamped.event)
+            .filter(|event| event.camera() == camera)
+            .collect();
+        if !step_events.is_empty() {
+            E::apply_step(transform, context, &step_events, first.delta_seconds);
+        }
+        rest = next;
+    }
+}
+
+// ████████╗███████╗███████╗████████╗
+// ╚══██╔══╝██╔════╝██╔════╝╚══██╔══╝
+//    ██║   █████╗  ███████╗   ██║
+//    ██║   ██╔══╝  ╚════██║   ██║
+//    ██║   ███████╗███████║   ██║
+//    ╚═╝   ╚══════╝╚══════╝   ╚═╝
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    use bevy::math::prelude::*;
+
+    /// Moves the eye along X by the number of events in each step times its length, and counts the steps.
+    struct Nudge(Entity);
+
+    impl ReplayableControlEvent for Nudge {
+        type Context = usize;
+
+        fn camera(&self) -> Entity {
+            self.0
+        }
+
+        fn apply_step(
+            transform: &mut LookTransform,
+            steps: &mut usize,
+            events: &[&Self],
+            delta_seconds: f32,
+        ) {
+            transform.eye.x += events.len() as f32 * delta_seconds;
+            *steps += 1;
+        }
+    }
+
+    #[test]
+    fn test_recorder_stamps_steps_while_recording() {
+        let mut recorder = ControlEventRecorder::default();
+        recorder.record(vec!['a', 'b'], 0.5);
+        recorder.record(vec![], 0.5);
+        recorder.recording = false;
+        recorder.record(vec!['c'], 0.5);
+        recorder.recording = true;
+        recorder.record(vec!['d'], 0.25);
+
+        let steps: Vec<(u64, char)> = recorder
+            .events()
+            .iter()
+            .map(|e| (e.step, e.event))
+            .collect();
+        assert_eq!(steps, vec![(0, 'a'), (0, 'b'), (3, 'd')]);
+        assert_eq!(recorder.take().len(), 3);
+        assert!(recorder.events().is_empty());
+    }
+
+    #[test]
+    fn test_apply_events_steps_by_each_stamp() {
+        let (camera, other_camera) = (Entity::new(0), Entity::new(1));
+        let stamped = |step, delta_seconds, camera| StampedControlEvent {
+            step,
+            delta_seconds,
+            event: Nudge(camera),
+        };
+        let mut transform = LookTransform::new(Vec3::ZERO, Vec3::Z);
+        let mut steps = 0;
+        apply_events(
+            camera,
+            &mut transform,
+            &mut steps,
+            &[
+                stamped(0, 0.5, camera),
+                stamped(0, 0.5, other_camera),
+                stamped(0, 0.5, camera),
+                stamped(1, 0.5, other_camera),
+                stamped(2, 0.25, camera),
+            ],
+        );
+        // The other camera's events, and the step that only had those, are skipped.
+        assert_eq!(transform.eye, Vec3::new(1.25, 0.0, 0.0));
+        assert_eq!(steps, 2);
+    }
+}
@@ -0,0 +1,64 @@
+use bevy::{
+    app::prelude::*,
+    core::{FixedTimestep, Time},
+    ecs::prelude::*,
+};
+
+/// A resource that sets how often the built-in controllers' input-mapping and control systems run.
+///
+/// With `ControlTimestep::Fixed`, they run at a fixed rate, zero or more times per frame, and step the `LookTransform` by the
+/// fixed step each time. The `Smoother` still runs every frame and eases toward the latest step, which softens the steps
+/// but lags behind them rather than interpolating. Insert it before adding the controller plugins, which read it when
+/// they're built:
+///
+/// ```rust
+/// App::new()
+///     .insert_resource(ControlTimestep::Fixed(1.0 / 60.0))
+///     .add_plugin(LookTransformPlugin)
+///     .add_plugin(OrbitCameraPlugin::default())
+/// ```
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub enum ControlTimestep {
+    /// Once per frame, stepping by the frame time.
+    Variable,
+    /// Every this many seconds.
+    Fixed(f64),
+    /// Once per frame, stepping by this many seconds whatever the frame time, and the `Smoother` steps by the same amount.
+    /// The cameras then only depend on the control events and the number of frames, so headless tests and replays are
+    /// reproducible.
+    Stepped(f64),
+}
+
+impl Default for ControlTimestep {
+    fn default() -> Self {
+        Self::Variable
+    }
+}
+
+impl ControlTimestep {
+    /// The seconds that a control system should step by each time it runs.
+    pub fn delta_seconds(&self, time: &Time) -> f32 {
+        match *self {
+            Self::Variable => time.delta_seconds(),
+            Self::Fixed(step) | Self::Stepped(step) => step as f32,
+        }
+    }
+
+    /// The seconds that a system that runs every frame, like the `Smoother` or a tween, should step by. That's the frame
+    /// time, except with `ControlTimestep::Stepped`, so those systems are reproducible too.
+    pub fn frame_delta_seconds(&self, time: &Time) -> f32 {
+        match *self {
+            Self::Stepped(step) => step as f32,
+            Self::Variable | Self::Fixed(_) => time.delta_seconds(),
+        }
+    }
+}
+
+/// A `SystemSet` that runs at the app's `ControlTimestep`.
+pub(crate) fn control_system_set(app: &App) -> SystemSet {
+    let set = SystemSet::new();
+    match app.world.get_resource::<ControlTimestep>() {
+        Some(&ControlTimestep::Fixed(step)) => set.with_run_criteria(FixedTimestep::step(step)),
+        _ => set,
+    }
+}
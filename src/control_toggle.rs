@@ -0,0 +1,80 @@
+use crate::{ActiveCameraEntity, ControllerEnabled, ControllerViewport, InputCaptureState};
+
+use bevy::{
+    ecs::{component::Component, prelude::*},
+    input::{keyboard::KeyCode, Input},
+    window::{Window, Windows},
+};
+
+/// A controller with an `enabled` field that a `ControlToggle` can flip. Every built-in controller implements this, and
+/// controllers written outside this crate can too, then call `add_control_toggle` from their plugin.
+pub trait ToggleableController: Component {
+    fn enabled(&self) -> bool;
+
+    fn enabled_mut(&mut self) -> &mut bool;
+}
+
+/// Put this on a camera to turn its controller on and off with a key, e.g. to switch between UI mode and camera mode.
+///
+/// Like other input, the key only reaches the `ActiveCameraEntity`, and nothing is toggled while `ControllerEnabled` is
+/// off. On an FPS camera, it takes over from the `CameraAction::ToggleCursorGrab` bindings.
+///
+/// Don't combine it with a `CameraRig`, which decides on its own which of its controllers are enabled.
+#[derive(Clone, Copy, Debug)]
+pub struct ControlToggle {
+    pub key: KeyCode,
+    /// Also grabs and hides the cursor while the controller is enabled, and releases it while the controller is disabled.
+    pub grab_cursor: bool,
+}
+
+impl ControlToggle {
+    pub fn new(key: KeyCode) -> Self {
+        Self {
+            key,
+            grab_cursor: false,
+        }
+    }
+
+    pub fn with_cursor_grab(mut self) -> Self {
+        self.grab_cursor = true;
+        self
+    }
+}
+
+pub(crate) fn control_toggle_system<C: ToggleableController>(
+    controller_enabled: Res<ControllerEnabled>,
+    active_camera: Res<ActiveCameraEntity>,
+    keyboard: Res<Input<KeyCode>>,
+    input_capture: Res<InputCaptureState>,
+    mut windows: ResMut<Windows>,
+    mut controllers: Query<(Entity, &ControlToggle, &mut C, Option<&ControllerViewport>)>,
+) {
+    if !controller_enabled.0 || input_capture.keyboard_captured {
+        return;
+    }
+
+    for (entity, toggle, mut controller, viewport) in controllers.iter_mut() {
+        if !active_camera.allows(entity) || !keyboard.just_pressed(toggle.key) {
+            continue;
+        }
+
+        let enabled = !*controller.enabled_mut();
+        *controller.enabled_mut() = enabled;
+        if !toggle.grab_cursor {
+            continue;
+        }
+
+        let window = match viewport {
+            Some(viewport) => windows.get_mut(viewport.window),
+            None => windows.get_primary_mut(),
+        };
+        if let Some(window) = window {
+            set_cursor_grab(window, enabled);
+        }
+    }
+}
+
+pub(crate) fn set_cursor_grab(window: &mut Window, grab: bool) {
+    window.set_cursor_lock_mode(grab);
+    window.set_cursor_visibility(!grab);
+}
@@ -1,2 +1,13 @@
+pub mod chase;
+pub mod follow;
 pub mod fps;
-pub mod orbit;
\ No newline at end of file
+pub mod free_fly;
+pub mod isometric;
+pub mod orbit;
+pub mod pancam;
+pub mod rail;
+pub mod rig;
+pub mod rts;
+
+#[cfg(test)]
+mod harness;
@@ -0,0 +1,402 @@
+use crate::{
+    input_filter::InputFilterState, look_angles::y_up_rotation, set_look_transform_if_changed,
+    smoothing::damped_spring, viewport::{pointer_in_viewport, viewport_window}, AccumulatedInput,
+    ActiveCameraEntity, AddCameraController, BindingInput, CameraAction, CameraInputMap,
+    ControlTimestep, ControllerEnabled, ControllerViewport, InputAxisConfig, InputCaptureState,
+    InputFilter, InputSource, LookTransform, LookTransformTween, PointerNormalization,
+    ReducedMotion, ToggleableController,
+};
+
+use bevy::{
+    app::prelude::*,
+    core::Time,
+    ecs::{bundle::Bundle, prelude::*},
+    input::{
+        gamepad::{GamepadAxis, GamepadButton, Gamepads},
+        prelude::*,
+    },
+    math::prelude::*,
+    transform::components::{GlobalTransform, Transform},
+    utils::Instant,
+    window::Windows,
+};
+use serde::{Deserialize, Serialize};
+use std::f32::consts::PI;
+
+#[derive(Default)]
+pub struct ChaseCameraPlugin {
+    /// Skips the built-in input mapping, so the camera is only driven by `ChaseControlEvent`s that you send.
+    pub override_input_system: bool,
+}
+
+impl Plugin for ChaseCameraPlugin {
+    fn build(&self, app: &mut App) {
+        app.add_camera_controller::<ChaseControlEvent, _, _>(
+            map_chase_input.system(),
+            control_chase_camera.system(),
+            self.override_input_system,
+        )
+        .add_control_toggle::<ChaseCameraController>()
+        .add_controller_state::<ChaseCameraController, ChaseCameraState>();
+    }
+}
+
+#[derive(Bundle)]
+pub struct ChaseCameraBundle {
+    controller: ChaseCameraController,
+    state: ChaseCameraState,
+}
+
+impl ChaseCameraBundle {
+    pub fn new(controller: ChaseCameraController) -> Self {
+        Self {
+            controller,
+            state: Default::default(),
+        }
+    }
+}
+
+/// A racing-game camera that sits behind a vehicle and swings around after it when it turns.
+///
+/// The heading of the eye is pulled toward the back of the chased entity by an angular spring, so the camera lags through
+/// corners and settles once the vehicle drives straight. Put a `Smoother` on the camera to also smooth out bumps in the
+/// vehicle's position.
+#[derive(Clone, Copy, Debug)]
+pub struct ChaseCameraController {
+    pub enabled: bool,
+    /// The devices that drive this camera. Defaults to all of them.
+    pub input_source: InputSource,
+    /// The entity to chase. It must have a `GlobalTransform`.
+    pub chased: Entity,
+    /// The chased entity's forward direction in its own space. Defaults to its negative Z axis.
+    pub forward: Vec3,
+    /// The world's up direction, which the heading turns around and the eye is raised along. Defaults to `Vec3::Y`.
+    pub up: Vec3,
+    /// Added to the chased entity's translation to get the target, e.g. to look over the roof of a car.
+    pub target_offset: Vec3,
+    /// The horizontal distance from the eye to the target.
+    pub distance: f32,
+    /// How far the eye sits above the target.
+    pub height: f32,
+    /// How strongly the heading is pulled toward the back of the chased entity. Higher values follow turns more tightly.
+    pub heading_stiffness: f32,
+    /// How quickly the heading's swing dies out. `2.0 * heading_stiffness.sqrt()` settles without overshooting, and lower
+    /// values let the camera swing past the back of the vehicle before settling.
+    pub heading_damping: f32,
+    /// Moves the target ahead of the chased entity by its velocity times this many seconds, so the view leads the
+    /// vehicle at speed.
+    pub look_ahead_seconds: f32,
+    /// The farthest the target can be moved ahead of the chased entity.
+    pub max_look_ahead: f32,
+    /// Scales horizontal mouse motion into yaw in radians, for glancing around the vehicle. The spring swings the camera
+    /// back behind it afterwards.
+    pub mouse_rotate: InputAxisConfig,
+    /// Filters jitter out of mouse motion before it's mapped. Off by default.
+    pub mouse_filter: InputFilter,
+    /// Scales the `CameraAction::LookLeft` and `LookRight` bindings, e.g. the right stick, into yaw in radians per
+    /// second. Only X is used.
+    pub gamepad_rotate: InputAxisConfig,
+}
+
+impl ChaseCameraController {
+    pub fn new(chased: Entity) -> Self {
+        let heading_stiffness = 20.0f32;
+        Self {
+            enabled: true,
+            input_source: InputSource::Any,
+            chased,
+            forward: -Vec3::Z,
+            up: Vec3::Y,
+            target_offset: Vec3::new(0.0, 1.0, 0.0),
+            distance: 6.0,
+            height: 2.0,
+            heading_stiffness,
+            heading_damping: 2.0 * heading_stiffness.sqrt(),
+            look_ahead_seconds: 0.2,
+            max_look_ahead: 5.0,
+            mouse_rotate: InputAxisConfig::new(Vec2::splat(0.006)),
+            mouse_filter: InputFilter::None,
+            gamepad_rotate: InputAxisConfig::new(Vec2::splat(3.0)).with_deadzone(0.15),
+        }
+    }
+}
+
+impl ToggleableController for ChaseCameraController {
+    fn enabled(&self) -> bool {
+        self.enabled
+    }
+
+    fn enabled_mut(&mut self) -> &mut bool {
+        &mut self.enabled
+    }
+}
+
+/// Per-camera state that the chase controller carries between frames.
+#[derive(Clone, Copy, Debug, Default)]
+pub struct ChaseCameraState {
+    /// The heading of the eye around the chased entity, in radians, once the chase has started.
+    heading: Option<f32>,
+    /// How fast the heading is swinging, in radians per second.
+    heading_velocity: f32,
+    /// The chased entity's translation in the last frame, for estimating its velocity.
+    previous_translation: Option<Vec3>,
+    /// Seconds of control steps since `previous_translation` was taken.
+    translation_age: f32,
+    /// The chased entity's estimated velocity.
+    velocity: Vec3,
+    /// The frame that `previous_translation` was taken in, so later steps in the same frame keep the estimate.
+    translation_frame: Option<Instant>,
+}
+
+impl ChaseCameraState {
+    /// Forgets the heading and velocity, so the camera starts over behind the chased entity instead of swinging to it.
+    pub(crate) fn reset_motion(&mut self) {
+        *self = Self::default();
+    }
+}
+
+/// Control events for the chase controller.
+#[derive(Clone, Copy, Debug, Deserialize, PartialEq, Serialize)]
+pub enum ChaseControlEvent {
+    /// Swings the eye around the chased entity by this yaw in radians, matching the direction of mouse motion. The heading
+    /// spring brings it back.
+    LookAround(Entity, f32),
+}
+
+impl ChaseControlEvent {
+    /// The camera entity that this event controls.
+    pub fn camera(&self) -> Entity {
+        match *self {
+            Self::LookAround(camera, _) => camera,
+        }
+    }
+}
+
+pub fn map_chase_input(
+    controller_enabled: Res<ControllerEnabled>,
+    active_camera: Res<ActiveCameraEntity>,
+    input_capture: Res<InputCaptureState>,
+    pointer_normalization: Res<PointerNormalization>,
+    time: Res<Time>,
+    timestep: Res<ControlTimestep>,
+    mut events: EventWriter<ChaseControlEvent>,
+    input_map: Res<CameraInputMap>,
+    keyboard: Res<Input<KeyCode>>,
+    mouse_buttons: Res<Input<MouseButton>>,
+    gamepads: Res<Gamepads>,
+    gamepad_axes: Res<Axis<GamepadAxis>>,
+    gamepad_buttons: Res<Input<GamepadButton>>,
+    mut accumulated_input: ResMut<AccumulatedInput>,
+    windows: Res<Windows>,
+    mut filters: Local<InputFilterState>,
+    controllers: Query<
+        (
+            Entity,
+            &ChaseCameraController,
+            Option<&ControllerViewport>,
+            Option<&CameraInputMap>,
+        ),
+        With<Transform>,
+    >,
+) {
+    // Take the input even when it's ignored, so it doesn't build up while the controllers are paused.
+    let pointer_delta = accumulated_input.take::<ChaseControlEvent>();
+    if !controller_enabled.0 {
+        return;
+    }
+
+    let dt = timestep.delta_seconds(&time);
+    let input = BindingInput::new(
+        &keyboard,
+        &mouse_buttons,
+        &gamepads,
+        &gamepad_axes,
+        &gamepad_buttons,
+    );
+
+    // Forget the filter history of cameras that were despawned or lost their controller.
+    filters.retain(|camera| controllers.get(camera).is_ok());
+
+    for (entity, controller, viewport, camera_input_map) in controllers.iter() {
+        if !controller.enabled || !active_camera.allows(entity) {
+            continue;
+        }
+
+        if !input_capture.pointer_captured
+            && controller.input_source.accepts_keyboard_mouse()
+            && pointer_in_viewport(&windows, viewport)
+        {
+            let cursor_delta = pointer_normalization
+                .normalize(pointer_delta.motion, viewport_window(&windows, viewport));
+            let cursor_delta = filters.apply(entity, controller.mouse_filter, cursor_delta, dt);
+            let yaw = controller.mouse_rotate.apply(cursor_delta).x;
+            if yaw != 0.0 {
+                events.send(ChaseControlEvent::LookAround(entity, yaw));
+            }
+        }
+
+        if input_capture.keyboard_captured {
+            continue;
+        }
+
+        let input_map = camera_input_map.unwrap_or(&input_map);
+        let input = input.with_source(controller.input_source);
+        let look = input_map.axis(CameraAction::LookRight, CameraAction::LookLeft, &input);
+        let yaw = controller.gamepad_rotate.apply(Vec2::new(look, 0.0)).x;
+        if yaw != 0.0 {
+            events.send(ChaseControlEvent::LookAround(entity, dt * yaw));
+        }
+    }
+}
+
+pub fn control_chase_camera(
+    time: Res<Time>,
+    timestep: Res<ControlTimestep>,
+    reduced_motion: Res<ReducedMotion>,
+    mut events: EventReader<ChaseControlEvent>,
+    mut cameras: Query<
+        (Entity, &ChaseCameraController, &mut ChaseCameraState, &mut LookTransform),
+        (With<Transform>, Without<LookTransformTween>),
+    >,
+    chased: Query<&GlobalTransform>,
+) {
+    // Every camera only consumes the events that were routed to it.
+    let events: Vec<&ChaseControlEvent> = events.iter().collect();
+    let dt = timestep.delta_seconds(&time);
+
+    for (entity, controller, mut state, mut look_transform) in cameras.iter_mut() {
+        if !controller.enabled {
+            continue;
+        }
+
+        let mut transform = *look_transform;
+
+        let chased_transform = if let Ok(t) = chased.get(controller.chased) {
+            t
+        } else {
+            continue;
+        };
+
+        // Work in a Y-up frame, so headings are measured around the controller's `up`.
+        let to_world = y_up_rotation(controller.up);
+        let translation = chased_transform.translation;
+        let forward = to_world.inverse() * (chased_transform.rotation * controller.forward);
+        // Keep the last heading while the vehicle points straight up or down.
+        let target_heading = if forward.x.abs() > 1e-6 || forward.z.abs() > 1e-6 {
+            heading_of(forward)
+        } else {
+            state.heading.unwrap_or(0.0)
+        };
+
+        let mut heading = state.heading.unwrap_or(target_heading);
+        for event in events.iter().filter(|e| e.camera() == entity) {
+            match event {
+                ChaseControlEvent::LookAround(_, yaw) => heading -= yaw,
+            }
+        }
+        // With reduced motion, the heading settles without swinging past the back of the vehicle.
+        let heading_damping = if reduced_motion.allows_inertia() {
+            controller.heading_damping
+        } else {
+            controller.heading_damping.max(2.0 * controller.heading_stiffness.sqrt())
+        };
+        let (heading, heading_velocity) = spring_heading(
+            heading,
+            state.heading_velocity,
+            target_heading,
+            controller.heading_stiffness,
+            heading_damping,
+            dt,
+        );
+        state.heading = Some(heading);
+        state.heading_velocity = heading_velocity;
+
+        // The chased entity only moves once per frame, so a second fixed step in the same frame keeps the last estimate
+        // instead of seeing it stand still.
+        state.translation_age += dt;
+        if time.last_update() != state.translation_frame {
+            state.velocity = match state.previous_translation {
+                Some(previous) if state.translation_age > 0.0 => {
+                    (translation - previous) / state.translation_age
+                }
+                _ => Vec3::ZERO,
+            };
+            state.previous_translation = Some(translation);
+            state.translation_age = 0.0;
+            state.translation_frame = time.last_update();
+        }
+        let look_ahead = state.velocity * controller.look_ahead_seconds;
+        let look_ahead = if look_ahead.length() > controller.max_look_ahead {
+            look_ahead.normalize() * controller.max_look_ahead
+        } else {
+            look_ahead
+        };
+
+        let behind = to_world * -Vec3::new(heading.sin(), 0.0, heading.cos());
+        let up = to_world * Vec3::Y;
+        let anchor = translation + controller.target_offset;
+        transform.eye = anchor + controller.distance * behind + controller.height * up;
+        transform.up = up;
+        transform.target = anchor + look_ahead;
+        set_look_transform_if_changed(&mut look_transform, transform);
+    }
+}
+
+/// The yaw of `direction` around the Y axis, where `0.0` faces positive Z.
+fn heading_of(direction: Vec3) -> f32 {
+    direction.x.atan2(direction.z)
+}
+
+/// Steps a damped angular spring that pulls `heading` toward `target`, the short way around. Returns the new heading and
+/// angular velocity.
+fn spring_heading(
+    heading: f32,
+    velocity: f32,
+    target: f32,
+    stiffness: f32,
+    damping: f32,
+    dt: f32,
+) -> (f32, f32) {
+    let error = (target - heading + PI).rem_euclid(2.0 * PI) - PI;
+    let (offset, velocity) = damped_spring(-error, velocity, stiffness, damping, dt);
+
+    (heading + error + offset, velocity)
+}
+
+// ████████╗███████╗███████╗████████╗
+// ╚══██╔══╝██╔════╝██╔════╝╚══██╔══╝
+//    ██║   █████╗  ███████╗   ██║
+//    ██║   ██╔══╝  ╚════██║   ██║
+//    ██║   ███████╗███████║   ██║
+//    ╚═╝   ╚══════╝╚══════╝   ╚═╝
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_heading_spring_settles_the_short_way_around() {
+        // Just short of a half turn to the left, so the short way is counterclockwise through the wrap.
+        let target = 3.0;
+        let (mut heading, mut velocity) = (-3.0, 0.0);
+        let stiffness = 20.0f32;
+        let damping = 2.0 * stiffness.sqrt();
+
+        let (first, _) = spring_heading(heading, velocity, target, stiffness, damping, 1.0 / 60.0);
+        assert!(first < heading);
+
+        for _ in 0..600 {
+            let (h, v) = spring_heading(heading, velocity, target, stiffness, damping, 1.0 / 60.0);
+            heading = h;
+            velocity = v;
+        }
+        let error = (target - heading + PI).rem_euclid(2.0 * PI) - PI;
+        assert!(error.abs() < 1e-3);
+        assert!(velocity.abs() < 1e-3);
+
+        // Long frames settle rather than blowing up.
+        let (heading, velocity) = spring_heading(0.0, 0.0, 1.0, stiffness, 8.9, 0.5);
+        assert!(heading > 0.0 && heading <= 1.0);
+        assert!(velocity.abs() < 10.0);
+    }
+}
@@ -0,0 +1,259 @@
+use crate::{
+    input_filter::InputFilterState, set_look_transform_if_changed,
+    viewport::{pointer_in_viewport, viewport_window}, AccumulatedInput, ActiveCameraEntity,
+    AddCameraController, BindingInput, CameraAction, CameraInputMap, ControlTimestep,
+    ControllerEnabled, ControllerViewport, InputAxisConfig, InputCaptureState, InputFilter,
+    InputSource, LookAngles, LookTransform, LookTransformTween, PointerNormalization,
+    ToggleableController,
+};
+
+use bevy::{
+    app::prelude::*,
+    core::Time,
+    ecs::{bundle::Bundle, prelude::*},
+    input::{
+        gamepad::{GamepadAxis, GamepadButton, Gamepads},
+        prelude::*,
+    },
+    math::prelude::*,
+    transform::components::{GlobalTransform, Transform},
+    window::Windows,
+};
+use serde::{Deserialize, Serialize};
+use std::f32::consts::FRAC_PI_2;
+
+#[derive(Default)]
+pub struct FollowCameraPlugin {
+    /// Skips the built-in input mapping, so the camera is only driven by `FollowControlEvent`s that you send.
+    pub override_input_system: bool,
+}
+
+impl Plugin for FollowCameraPlugin {
+    fn build(&self, app: &mut App) {
+        app.add_camera_controller::<FollowControlEvent, _, _>(
+            map_follow_input.system(),
+            control_follow_camera.system(),
+            self.override_input_system,
+        )
+        .add_control_toggle::<FollowCameraController>();
+    }
+}
+
+#[derive(Bundle)]
+pub struct FollowCameraBundle {
+    controller: FollowCameraController,
+}
+
+impl FollowCameraBundle {
+    pub fn new(controller: FollowCameraController) -> Self {
+        Self { controller }
+    }
+}
+
+/// A 3rd person camera that keeps its target on another entity and orbits around it with the mouse or the
+/// `CameraAction::LookLeft`, `LookRight`, `LookUp`, and `LookDown` bindings.
+///
+/// Put a `Smoother` on the camera to have it trail smoothly behind the followed entity.
+#[derive(Clone, Copy, Debug)]
+pub struct FollowCameraController {
+    pub enabled: bool,
+    /// The devices that drive this camera. Defaults to all of them.
+    pub input_source: InputSource,
+    /// The entity to follow. It must have a `GlobalTransform`.
+    pub followed: Entity,
+    /// Added to the followed entity's translation to get the target, e.g. to look at a character's head instead of its feet.
+    pub target_offset: Vec3,
+    /// The distance from the eye to the target.
+    pub distance: f32,
+    /// The lowest allowed pitch of the eye relative to the target, in radians.
+    pub min_pitch: f32,
+    /// The highest allowed pitch of the eye relative to the target, in radians.
+    pub max_pitch: f32,
+    /// Scales mouse motion into yaw (X) and pitch (Y) in radians.
+    pub mouse_rotate: InputAxisConfig,
+    /// Filters jitter out of mouse motion before it's mapped. Off by default.
+    pub mouse_filter: InputFilter,
+    /// Scales the look bindings, e.g. the right stick, into yaw (X) and pitch (Y) in radians per second.
+    pub gamepad_rotate: InputAxisConfig,
+}
+
+impl FollowCameraController {
+    pub fn new(followed: Entity) -> Self {
+        Self {
+            enabled: true,
+            input_source: InputSource::Any,
+            followed,
+            target_offset: Vec3::ZERO,
+            distance: 5.0,
+            min_pitch: -FRAC_PI_2,
+            max_pitch: FRAC_PI_2,
+            mouse_rotate: InputAxisConfig::new(Vec2::splat(0.006)),
+            mouse_filter: InputFilter::None,
+            gamepad_rotate: InputAxisConfig::new(Vec2::splat(3.0)).with_deadzone(0.15),
+        }
+    }
+}
+
+impl ToggleableController for FollowCameraController {
+    fn enabled(&self) -> bool {
+        self.enabled
+    }
+
+    fn enabled_mut(&mut self) -> &mut bool {
+        &mut self.enabled
+    }
+}
+
+/// Control events for the follow controller.
+#[derive(Clone, Copy, Debug, Deserialize, PartialEq, Serialize)]
+pub enum FollowControlEvent {
+    /// Orbits the eye around the target by yaw (X) and pitch (Y) in radians, matching the direction of mouse motion.
+    Orbit(Entity, Vec2),
+}
+
+impl FollowControlEvent {
+    /// The camera entity that this event controls.
+    pub fn camera(&self) -> Entity {
+        match *self {
+            Self::Orbit(camera, _) => camera,
+        }
+    }
+}
+
+pub fn map_follow_input(
+    controller_enabled: Res<ControllerEnabled>,
+    active_camera: Res<ActiveCameraEntity>,
+    input_capture: Res<InputCaptureState>,
+    pointer_normalization: Res<PointerNormalization>,
+    time: Res<Time>,
+    timestep: Res<ControlTimestep>,
+    mut events: EventWriter<FollowControlEvent>,
+    input_map: Res<CameraInputMap>,
+    keyboard: Res<Input<KeyCode>>,
+    mouse_buttons: Res<Input<MouseButton>>,
+    gamepads: Res<Gamepads>,
+    gamepad_axes: Res<Axis<GamepadAxis>>,
+    gamepad_buttons: Res<Input<GamepadButton>>,
+    mut accumulated_input: ResMut<AccumulatedInput>,
+    windows: Res<Windows>,
+    mut filters: Local<InputFilterState>,
+    controllers: Query<
+        (
+            Entity,
+            &FollowCameraController,
+            Option<&ControllerViewport>,
+            Option<&CameraInputMap>,
+        ),
+        With<Transform>,
+    >,
+) {
+    // Take the input even when it's ignored, so it doesn't build up while the controllers are paused.
+    let pointer_delta = accumulated_input.take::<FollowControlEvent>();
+    if !controller_enabled.0 {
+        return;
+    }
+
+    let cursor_delta = pointer_delta.motion;
+    let dt = timestep.delta_seconds(&time);
+    let input = BindingInput::new(
+        &keyboard,
+        &mouse_buttons,
+        &gamepads,
+        &gamepad_axes,
+        &gamepad_buttons,
+    );
+
+    // Forget the filter history of cameras that were despawned or lost their controller.
+    filters.retain(|camera| controllers.get(camera).is_ok());
+
+    for (entity, controller, viewport, camera_input_map) in controllers.iter() {
+        if !controller.enabled || !active_camera.allows(entity) {
+            continue;
+        }
+
+        if !input_capture.pointer_captured
+            && controller.input_source.accepts_keyboard_mouse()
+            && pointer_in_viewport(&windows, viewport)
+        {
+            let cursor_delta =
+                pointer_normalization.normalize(cursor_delta, viewport_window(&windows, viewport));
+            let cursor_delta = filters.apply(entity, controller.mouse_filter, cursor_delta, dt);
+            events.send(FollowControlEvent::Orbit(
+                entity,
+                controller.mouse_rotate.apply(cursor_delta),
+            ));
+        }
+
+        if input_capture.keyboard_captured {
+            continue;
+        }
+
+        let input_map = camera_input_map.unwrap_or(&input_map);
+        let input = input.with_source(controller.input_source);
+        // Pushing the stick up looks up, like moving the mouse up.
+        let rotation = controller.gamepad_rotate.apply(Vec2::new(
+            input_map.axis(CameraAction::LookRight, CameraAction::LookLeft, &input),
+            input_map.axis(CameraAction::LookDown, CameraAction::LookUp, &input),
+        ));
+        if rotation != Vec2::ZERO {
+            events.send(FollowControlEvent::Orbit(entity, dt * rotation));
+        }
+    }
+}
+
+pub fn control_follow_camera(
+    mut events: EventReader<FollowControlEvent>,
+    mut cameras: Query<
+        (
+            Entity,
+            &FollowCameraController,
+            ChangeTrackers<FollowCameraController>,
+            &mut LookTransform,
+        ),
+        (With<Transform>, Without<LookTransformTween>),
+    >,
+    followed: Query<&GlobalTransform>,
+) {
+    // Every camera only consumes the events that were routed to it.
+    let events: Vec<&FollowControlEvent> = events.iter().collect();
+
+    for (entity, controller, controller_tracker, mut look_transform) in cameras.iter_mut() {
+        if !controller.enabled {
+            continue;
+        }
+
+        let mut transform = *look_transform;
+
+        let followed_transform = if let Ok(t) = followed.get(controller.followed) {
+            t
+        } else {
+            continue;
+        };
+
+        let mut look_angles = LookAngles::from_vector(-transform.look_direction());
+        let mut turned = false;
+
+        for event in events.iter().filter(|e| e.camera() == entity) {
+            match event {
+                FollowControlEvent::Orbit(_, delta) => {
+                    look_angles.add_yaw(-delta.x);
+                    look_angles.add_pitch(delta.y);
+                    turned |= *delta != Vec2::ZERO;
+                }
+            }
+        }
+
+        let target = followed_transform.translation + controller.target_offset;
+        // Rebuilding the eye from angles rounds a little differently every step, which would count as a change to the
+        // `LookTransform`, so a camera is left alone unless it turns, its target moves, or its settings change.
+        if !turned && target == transform.target && !controller_tracker.is_changed() {
+            continue;
+        }
+
+        look_angles.clamp_pitch(controller.min_pitch, controller.max_pitch);
+
+        transform.target = target;
+        transform.eye = transform.target + controller.distance * look_angles.unit_vector();
+        set_look_transform_if_changed(&mut look_transform, transform);
+    }
+}
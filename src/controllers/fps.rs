@@ -1,147 +1,1099 @@
-use crate::{LookAngles, LookTransform, LookTransformBundle, Smoother};
+use crate::{
+    control_timestep::control_system_set, control_toggle::set_cursor_grab,
+    input_filter::InputFilterState, look_angles::y_up_rotation, set_look_transform_if_changed,
+    smooth, viewport::{pointer_in_viewport, viewport_window}, AccumulatedInput, ActiveCameraEntity,
+    AddCameraController, BindingInput, CameraAction, CameraInputMap, ControlTimestep, ControlToggle,
+    ControllerEnabled, ControllerViewport, DollyZoom, InputAxisConfig, InputCaptureState,
+    InputFilter, InputSource, LookAngles, LookTransform, LookTransformBundle, LookTransformOffset,
+    LookTransformSystem, LookTransformTween, PointerNormalization, ReducedMotion, ResponseCurve,
+    Smoother, ToggleableController,
+};
 
 use bevy::{
     app::prelude::*,
-    ecs::{bundle::Bundle, prelude::*},
-    input::{mouse::MouseMotion, prelude::*},
+    core::Time,
+    ecs::{bundle::Bundle, prelude::*, reflect::ReflectComponent},
+    input::{
+        gamepad::{GamepadAxis, GamepadButton, Gamepads},
+        prelude::*,
+    },
     math::prelude::*,
-    render::prelude::*,
-    transform::components::Transform,
+    reflect::Reflect,
+    render::{camera::PerspectiveProjection, prelude::*},
+    transform::components::{GlobalTransform, Transform},
+    window::Windows,
 };
 use serde::{Deserialize, Serialize};
+use std::f32::consts::{FRAC_PI_2, FRAC_PI_4, TAU};
 
-pub struct FpsCameraPlugin;
+#[derive(Default)]
+pub struct FpsCameraPlugin {
+    /// Skips the built-in input mapping, so the camera is only driven by `FPSControlEvent`s that you send.
+    pub override_input_system: bool,
+}
 
 impl Plugin for FpsCameraPlugin {
     fn build(&self, app: &mut App) {
-        app.add_system(map_fps_input.system())
-            .add_system(control_fps_camera.system())
-            .add_event::<FPSControlEvent>();
+        app.add_camera_controller::<FPSControlEvent, _, _>(
+            map_fps_input.system(),
+            control_fps_camera.system(),
+            self.override_input_system,
+        )
+        .add_control_toggle::<FpsCameraController>()
+        .add_controller_state::<FpsCameraController, FpsCameraState>()
+        .add_controller_state::<FpsCameraController, LookTransformOffset>()
+        .register_type::<FpsCameraController>()
+        .register_type::<FpsMovementMode>()
+        .register_type::<FpsHeadBob>()
+        .register_type::<FpsGravity>()
+        .register_type::<FpsZoom>()
+        .init_resource::<PointerLockState>();
+
+        if !self.override_input_system {
+            app.add_system_set(
+                control_system_set(app)
+                    .label(LookTransformSystem::InputMap)
+                    .with_system(map_fps_gamepad_input.system()),
+            );
+
+            // The input map reads the `PointerLockState` that the grab leaves behind on the same frame.
+            #[cfg(not(all(feature = "web_pointer_lock", target_arch = "wasm32")))]
+            app.add_system(grab_fps_cursor.system().before(LookTransformSystem::InputMap));
+            #[cfg(all(feature = "web_pointer_lock", target_arch = "wasm32"))]
+            app.add_system(grab_fps_cursor_web.system().before(LookTransformSystem::InputMap));
+        }
     }
 }
 
+/// Everything a camera needs to be driven by the FPS controller, so it can be spawned in one go.
 #[derive(Bundle)]
 pub struct FpsCameraBundle {
     controller: FpsCameraController,
+    state: FpsCameraState,
+    offset: LookTransformOffset,
+    #[bundle]
+    look_transform: LookTransformBundle,
+    #[bundle]
+    perspective: PerspectiveCameraBundle,
 }
 
 impl FpsCameraBundle {
-    pub fn new( controller: FpsCameraController) -> Self {
-        Self { controller }
+    /// Looks from `eye` to `target`, with the controller's `up` direction and `smoothing_weight`. The `perspective`
+    /// bundle's `Transform` is overwritten to match, so the camera doesn't jump on its first frame.
+    pub fn new(
+        controller: FpsCameraController,
+        mut perspective: PerspectiveCameraBundle,
+        eye: Vec3,
+        target: Vec3,
+    ) -> Self {
+        let (look_transform, transform) =
+            LookTransformBundle::look_at(eye, target, controller.up, controller.smoothing_weight);
+        perspective.transform = transform;
+
+        Self {
+            controller,
+            state: FpsCameraState::default(),
+            offset: LookTransformOffset::default(),
+            look_transform,
+            perspective,
+        }
+    }
+}
+
+/// Per-camera state that the FPS controller carries between frames.
+#[derive(Clone, Copy, Debug, Default, Deserialize, Serialize)]
+pub struct FpsCameraState {
+    /// Where the head bob is in its cycle, in radians.
+    bob_phase: f32,
+    /// How strongly the head bob is applied, from `0.0` when standing still to `1.0` when moving.
+    bob_intensity: f32,
+    /// The head bob's share of the camera's `LookTransformOffset`.
+    bob_offset: Vec3,
+    /// How fast the eye is falling under gravity, in units per second.
+    fall_speed: f32,
+}
+
+impl FpsCameraState {
+    /// Stops the head bob and any fall in progress, and takes the bob back out of `offset`.
+    pub(crate) fn reset_motion(&mut self, offset: Option<Mut<LookTransformOffset>>) {
+        if let Some(mut offset) = offset {
+            if self.bob_offset != Vec3::ZERO {
+                offset.0 -= self.bob_offset;
+            }
+        }
+        *self = Self::default();
     }
 }
 
 /// Your typical first-person camera controller.
-#[derive(Clone, Copy, Debug, Deserialize, Serialize)]
+#[derive(Clone, Copy, Debug, Deserialize, Reflect, Serialize)]
+#[reflect(Component)]
 pub struct FpsCameraController {
     pub enabled: bool,
-    pub mouse_rotate_sensitivity: Vec2,
+    /// The devices that drive this camera. Defaults to all of them.
+    pub input_source: InputSource,
+    /// The world's up direction, which yaw turns around, walking stays perpendicular to, and `gravity` pulls against, e.g.
+    /// `Vec3::Z` for a Z-up world.
+    pub up: Vec3,
+    /// The lowest allowed pitch, in radians.
+    pub min_pitch: f32,
+    /// The highest allowed pitch, in radians.
+    pub max_pitch: f32,
+    /// The lag weight of the `Smoother` that `FpsCameraBundle::new` adds, between `0.0` and `1.0`, where higher is
+    /// smoother.
+    pub smoothing_weight: f32,
+    /// Scales mouse motion into yaw (X) and pitch (Y) in radians.
+    pub mouse_rotate: InputAxisConfig,
+    /// Shapes mouse rotation before `mouse_rotate` is applied, e.g. to turn faster on quick flicks.
+    pub rotate_response_curve: ResponseCurve,
+    /// Filters jitter out of mouse motion before it's mapped. Off by default.
+    pub mouse_filter: InputFilter,
+    /// Translation speed in units per second.
     pub translate_sensitivity: f32,
+    /// Multiplies `translate_sensitivity` while a `CameraAction::Sprint` binding is held.
+    pub sprint_multiplier: f32,
+    /// Divides `translate_sensitivity` while a `CameraAction::Slow` binding is held.
+    pub slow_divisor: f32,
+    pub movement_mode: FpsMovementMode,
+    /// Locks and hides the cursor in the primary window while this controller is active. Mouse look is ignored while the
+    /// cursor is released. `CameraAction::ToggleCursorGrab` grabs or releases it, except on a camera with a `ControlToggle`,
+    /// whose key does the grabbing instead. With the `web_pointer_lock` feature in a browser, the lock is requested on the
+    /// first click instead, and if the `PointerLockState` is `Unavailable`, the mouse only looks around while a
+    /// `CameraAction::DragLook` binding is held.
+    pub grab_cursor: bool,
+    /// Scales the `Look` bindings, the right stick by default, into rotation speed in radians per second.
+    pub gamepad_rotate: InputAxisConfig,
+    /// Scales the gamepad's `Move` bindings, the left stick by default, before `translate_sensitivity` is applied.
+    pub gamepad_translate: InputAxisConfig,
+    /// Bobs the eye up and down while the camera is translating.
+    pub head_bob: Option<FpsHeadBob>,
+    /// Keeps the eye at a fixed height above the ground, falling when it's higher, for a walking camera.
+    pub gravity: Option<FpsGravity>,
+    /// Narrows the field of view while a `CameraAction::Aim` binding is held, like aiming down sights. Requires a
+    /// `PerspectiveProjection`.
+    pub zoom: Option<FpsZoom>,
+    /// Keeps the camera aimed at this entity's `GlobalTransform` while the eye still translates, like Z-targeting in action
+    /// games. Rotation input is ignored while it's set. It isn't serialized, since entities don't keep their IDs across a
+    /// save and load.
+    #[reflect(ignore)]
+    #[serde(skip)]
+    pub lock_target: Option<Entity>,
 }
 
 impl Default for FpsCameraController {
     fn default() -> Self {
         Self {
             enabled: true,
-            mouse_rotate_sensitivity: Vec2::splat(0.002),
-            translate_sensitivity: 0.5,
+            input_source: InputSource::Any,
+            up: Vec3::Y,
+            min_pitch: -FRAC_PI_2,
+            max_pitch: FRAC_PI_2,
+            smoothing_weight: 0.9,
+            mouse_rotate: InputAxisConfig::new(Vec2::splat(0.002)),
+            mouse_filter: InputFilter::None,
+            rotate_response_curve: ResponseCurve::Linear,
+            translate_sensitivity: 2.0,
+            sprint_multiplier: 2.0,
+            slow_divisor: 4.0,
+            movement_mode: FpsMovementMode::Walk,
+            grab_cursor: false,
+            gamepad_rotate: InputAxisConfig::new(Vec2::splat(3.0)).with_deadzone(0.15),
+            gamepad_translate: InputAxisConfig::default().with_deadzone(0.15),
+            head_bob: None,
+            gravity: None,
+            zoom: None,
+            lock_target: None,
+        }
+    }
+}
+
+impl FpsCameraController {
+    /// `translate_sensitivity`, sped up while a `Sprint` binding is held and slowed down while a `Slow` one is.
+    fn translate_speed(&self, input_map: &CameraInputMap, input: &BindingInput) -> f32 {
+        let mut speed = self.translate_sensitivity;
+        if input_map.pressed(CameraAction::Sprint, input) {
+            speed *= self.sprint_multiplier;
+        }
+        if input_map.pressed(CameraAction::Slow, input) {
+            speed /= self.slow_divisor;
+        }
+        speed
+    }
+}
+
+impl ToggleableController for FpsCameraController {
+    fn enabled(&self) -> bool {
+        self.enabled
+    }
+
+    fn enabled_mut(&mut self) -> &mut bool {
+        &mut self.enabled
+    }
+}
+
+/// How forward and back translation follows the look direction.
+#[derive(Clone, Copy, Debug, Deserialize, Eq, PartialEq, Reflect, Serialize)]
+#[reflect_value(PartialEq, Serialize, Deserialize)]
+pub enum FpsMovementMode {
+    /// Forward moves along the ground, ignoring pitch.
+    Walk,
+    /// Forward moves wherever the camera is looking, including up and down.
+    Fly,
+}
+
+/// Pulls the eye down, against the controller's `up`, until it's `eye_height` above the ground. The ground is given by the
+/// `GroundHeight` resource if there is one, and is otherwise a flat plane at `ground_height`. Onto higher ground, like
+/// stairs or a slope, the eye rises straight away, or eases up with `step_lag_weight`.
+///
+/// Translating up still works while gravity is on, so it can be used for simple jumps.
+#[derive(Clone, Copy, Debug, Deserialize, Reflect, Serialize)]
+pub struct FpsGravity {
+    /// How far above the ground to keep the eye.
+    pub eye_height: f32,
+    /// The height of the ground plane when there's no `GroundHeight` resource, or it has no height under the eye.
+    pub ground_height: f32,
+    /// Downward acceleration in units per second squared.
+    pub acceleration: f32,
+    /// Eases the eye up onto higher ground instead of jumping to it, so walking up stairs doesn't jolt the view. Clamped to
+    /// `[0.0, 1.0)`, where higher is smoother and `0.0` disables step smoothing, so the eye always makes it up.
+    pub step_lag_weight: f32,
+}
+
+impl Default for FpsGravity {
+    fn default() -> Self {
+        Self {
+            eye_height: 1.7,
+            ground_height: 0.0,
+            acceleration: 9.81,
+            step_lag_weight: 0.0,
+        }
+    }
+}
+
+impl FpsGravity {
+    /// Steps an eye at `height` that's falling at `fall_speed` by `dt` seconds, stopping it on the ground. Returns the new
+    /// height and fall speed.
+    fn fall(&self, height: f32, fall_speed: f32, ground: f32, dt: f32) -> (f32, f32) {
+        let floor = ground + self.eye_height;
+        if height < floor {
+            // The ground rose under the eye, so ease up onto it, and snap once we're close enough.
+            let height = smooth(height, floor, self.step_lag_weight, dt);
+            let height = if floor - height > 1e-3 { height } else { floor };
+            return (height, 0.0);
+        }
+
+        let fall_speed = fall_speed + dt * self.acceleration;
+        let height = height - dt * fall_speed;
+
+        if height <= floor {
+            (floor, 0.0)
+        } else {
+            (height, fall_speed)
+        }
+    }
+}
+
+/// Returns the height of the ground at a point on the XZ plane, for `FpsGravity`. This lets the FPS camera walk over terrain
+/// or level geometry without the crate depending on a physics engine. Heights are measured along the controller's `up`, so
+/// in a Z-up world the height is along Z and the point is the eye's X and -Y:
+///
+/// ```rust
+/// app.insert_resource(GroundHeight::new(|xz| my_terrain.height_at(xz.x, xz.y)));
+/// ```
+///
+/// For ground that doesn't cover the whole world, e.g. a raycast against level geometry that can miss, use `partial`.
+pub struct GroundHeight(Box<dyn Fn(Vec2) -> Option<f32> + Send + Sync>);
+
+impl GroundHeight {
+    pub fn new(height_at: impl Fn(Vec2) -> f32 + Send + Sync + 'static) -> Self {
+        Self::partial(move |xz| Some(height_at(xz)))
+    }
+
+    /// Ground that only has a height where `height_at` returns one. Elsewhere, the eye lands on the `ground_height` plane
+    /// of the controller's `FpsGravity`.
+    pub fn partial(height_at: impl Fn(Vec2) -> Option<f32> + Send + Sync + 'static) -> Self {
+        Self(Box::new(height_at))
+    }
+
+    pub fn height_at(&self, xz: Vec2) -> Option<f32> {
+        (self.0)(xz)
+    }
+}
+
+/// Whether the cursor is locked for mouse look, kept up to date by the FPS controller's cursor grab system.
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+pub enum PointerLockState {
+    Unlocked,
+    /// The lock was requested from the browser, which hasn't answered yet.
+    Requested,
+    Locked,
+    /// The browser denied the lock, e.g. in an iframe without permission, so FPS controllers fall back to looking around
+    /// while a `CameraAction::DragLook` binding is held. Set this back to `Unlocked` to request the lock again on the next click.
+    Unavailable,
+}
+
+impl Default for PointerLockState {
+    fn default() -> Self {
+        Self::Unlocked
+    }
+}
+
+impl PointerLockState {
+    pub fn is_locked(&self) -> bool {
+        *self == Self::Locked
+    }
+}
+
+/// Aim-down-sights zoom for the FPS camera. While a `CameraAction::Aim` binding is held, the field of view eases from
+/// `normal_fov` to `zoomed_fov`, and it eases back when it's released. It pauses while a `DollyZoom` plays on the camera.
+#[derive(Clone, Copy, Debug, Deserialize, Reflect, Serialize)]
+pub struct FpsZoom {
+    /// The vertical field of view when not zoomed, in radians.
+    pub normal_fov: f32,
+    /// The vertical field of view while zoomed, in radians.
+    pub zoomed_fov: f32,
+    /// Between `0.0` and `1.0`, where higher zooms in and out more slowly.
+    pub lag_weight: f32,
+}
+
+impl Default for FpsZoom {
+    fn default() -> Self {
+        Self {
+            normal_fov: FRAC_PI_4,
+            zoomed_fov: 0.35,
+            lag_weight: 0.8,
+        }
+    }
+}
+
+impl FpsZoom {
+    /// Eases `fov` toward the zoomed or normal field of view over `dt` seconds, snapping once it's close.
+    fn step(&self, fov: f32, zoomed: bool, dt: f32) -> f32 {
+        let goal = if zoomed {
+            self.zoomed_fov
+        } else {
+            self.normal_fov
+        };
+        let fov = smooth(fov, goal, self.lag_weight, dt);
+
+        if (fov - goal).abs() < 1e-4 {
+            goal
+        } else {
+            fov
         }
     }
 }
 
+/// A walking head bob for the FPS camera. The eye bobs vertically `frequency` times per second and sways sideways at half that
+/// rate, like it would with each footstep.
+#[derive(Clone, Copy, Debug, Deserialize, Reflect, Serialize)]
+pub struct FpsHeadBob {
+    /// The height of each bob, in units.
+    pub vertical_amplitude: f32,
+    /// The distance of each sideways sway, in units.
+    pub lateral_amplitude: f32,
+    /// Bobs per second.
+    pub frequency: f32,
+    /// Between `0.0` and `1.0`, where higher fades the bob in and out more slowly when the camera starts or stops moving.
+    pub lag_weight: f32,
+}
+
+impl Default for FpsHeadBob {
+    fn default() -> Self {
+        Self {
+            vertical_amplitude: 0.05,
+            lateral_amplitude: 0.025,
+            frequency: 4.0,
+            lag_weight: 0.9,
+        }
+    }
+}
+
+impl FpsHeadBob {
+    /// The offset of the eye at `phase` radians through the cycle, where `right` and `up` are the camera's unit right and up
+    /// vectors.
+    fn offset(&self, phase: f32, intensity: f32, right: Vec3, up: Vec3) -> Vec3 {
+        let vertical = self.vertical_amplitude * phase.sin();
+        let lateral = self.lateral_amplitude * (0.5 * phase).sin();
+
+        intensity * (vertical * up + lateral * right)
+    }
+}
+
+/// Control events for the FPS controller.
+#[derive(Clone, Copy, Debug, Deserialize, PartialEq, Serialize)]
 pub enum FPSControlEvent {
-    Rotate(Vec2),
-    TranslateEye(Vec3),
+    /// Turns the camera by yaw (X) and pitch (Y) in radians. Positive X turns right and positive Y looks down, matching the
+    /// direction of mouse motion.
+    Rotate(Entity, Vec2),
+    /// Eye velocity in units per second, which is scaled by the frame time when applied.
+    TranslateEye(Entity, Vec3),
+    /// Holds the `zoom` in for this frame. Without it, the field of view eases back out.
+    Zoom(Entity),
+}
+
+impl FPSControlEvent {
+    /// The camera entity that this event controls.
+    pub fn camera(&self) -> Entity {
+        match *self {
+            Self::Rotate(camera, _) | Self::TranslateEye(camera, _) | Self::Zoom(camera) => camera,
+        }
+    }
 }
 
 pub fn map_fps_input(
+    controller_enabled: Res<ControllerEnabled>,
+    active_camera: Res<ActiveCameraEntity>,
+    input_capture: Res<InputCaptureState>,
+    pointer_normalization: Res<PointerNormalization>,
+    time: Res<Time>,
+    timestep: Res<ControlTimestep>,
     mut events: EventWriter<FPSControlEvent>,
+    windows: Res<Windows>,
+    input_map: Res<CameraInputMap>,
     keyboard: Res<Input<KeyCode>>,
-    mut mouse_motion_events: EventReader<MouseMotion>,
-    controllers: Query<&FpsCameraController, With<Transform>>,
+    mouse_buttons: Res<Input<MouseButton>>,
+    gamepads: Res<Gamepads>,
+    gamepad_axes: Res<Axis<GamepadAxis>>,
+    gamepad_buttons: Res<Input<GamepadButton>>,
+    mut accumulated_input: ResMut<AccumulatedInput>,
+    mut filters: Local<InputFilterState>,
+    pointer_lock: Res<PointerLockState>,
+    controllers: Query<
+        (
+            Entity,
+            &FpsCameraController,
+            Option<&ControllerViewport>,
+            Option<&CameraInputMap>,
+        ),
+        With<Transform>,
+    >,
 ) {
-    // Can only control one camera at a time.
-    let controller = if let Some(controller) = controllers.iter().next() {
-        controller
-    } else {
+    // Take the input even when it's ignored, so it doesn't build up while the controllers are paused.
+    let pointer_delta = accumulated_input.take::<FPSControlEvent>();
+    if !controller_enabled.0 {
         return;
-    };
-    let FpsCameraController {
-        enabled,
-        translate_sensitivity,
-        mouse_rotate_sensitivity,
-        ..
-    } = *controller;
-
-    if !enabled {
+    }
+
+    let cursor_delta = pointer_delta.motion;
+    let dt = timestep.delta_seconds(&time);
+    // Gamepad bindings are read by `map_fps_gamepad_input`, one gamepad at a time.
+    let input = BindingInput::new(
+        &keyboard,
+        &mouse_buttons,
+        &gamepads,
+        &gamepad_axes,
+        &gamepad_buttons,
+    )
+    .with_source(InputSource::KeyboardMouse);
+
+    // Forget the filter history of cameras that were despawned or lost their controller.
+    filters.retain(|camera| controllers.get(camera).is_ok());
+
+    for (entity, controller, viewport, camera_input_map) in controllers.iter() {
+        let FpsCameraController {
+            enabled,
+            grab_cursor,
+            ..
+        } = *controller;
+
+        if !enabled
+            || !active_camera.allows(entity)
+            || !controller.input_source.accepts_keyboard_mouse()
+        {
+            continue;
+        }
+
+        let input_map = camera_input_map.unwrap_or(&input_map);
+
+        // Without a lock, e.g. when the browser denies it, fall back to looking around while a button is held.
+        let drag_look = grab_cursor && *pointer_lock == PointerLockState::Unavailable;
+        // A grabbed cursor is locked in place, so it's always in the viewport.
+        let pointer_free = !input_capture.pointer_captured
+            && if grab_cursor && !drag_look {
+                pointer_lock.is_locked()
+            } else {
+                pointer_in_viewport(&windows, viewport)
+            };
+        let cursor_delta =
+            pointer_normalization.normalize(cursor_delta, viewport_window(&windows, viewport));
+        let cursor_delta = filters.apply(entity, controller.mouse_filter, cursor_delta, dt);
+        if pointer_free {
+            if !drag_look || input_map.pressed(CameraAction::DragLook, &input) {
+                let rotation = controller.rotate_response_curve.apply(cursor_delta, dt);
+                events.send(FPSControlEvent::Rotate(entity, controller.mouse_rotate.apply(rotation)));
+            }
+
+            if controller.zoom.is_some() && input_map.pressed(CameraAction::Aim, &input) {
+                events.send(FPSControlEvent::Zoom(entity));
+            }
+        }
+
+        if input_capture.keyboard_captured {
+            continue;
+        }
+
+        let translate_sensitivity = controller.translate_speed(input_map, &input);
+        for (action, dir) in [
+            (CameraAction::MoveForward, Vec3::Z),
+            (CameraAction::MoveLeft, Vec3::X),
+            (CameraAction::MoveBack, -Vec3::Z),
+            (CameraAction::MoveRight, -Vec3::X),
+            (CameraAction::MoveDown, -Vec3::Y),
+            (CameraAction::MoveUp, Vec3::Y),
+        ]
+        .iter()
+        .cloned()
+        {
+            if input_map.pressed(action, &input) {
+                events.send(FPSControlEvent::TranslateEye(
+                    entity,
+                    translate_sensitivity * dir,
+                ));
+            }
+        }
+    }
+}
+
+/// Maps the gamepad bindings of the `CameraInputMap` for each gamepad. By default, the right stick rotates, the left stick
+/// translates on the XZ plane, and the shoulder buttons translate vertically.
+pub fn map_fps_gamepad_input(
+    controller_enabled: Res<ControllerEnabled>,
+    active_camera: Res<ActiveCameraEntity>,
+    time: Res<Time>,
+    timestep: Res<ControlTimestep>,
+    mut events: EventWriter<FPSControlEvent>,
+    input_map: Res<CameraInputMap>,
+    keyboard: Res<Input<KeyCode>>,
+    mouse_buttons: Res<Input<MouseButton>>,
+    gamepads: Res<Gamepads>,
+    gamepad_axes: Res<Axis<GamepadAxis>>,
+    gamepad_buttons: Res<Input<GamepadButton>>,
+    controllers: Query<(Entity, &FpsCameraController, Option<&CameraInputMap>), With<Transform>>,
+) {
+    if !controller_enabled.0 {
         return;
     }
 
-    let mut cursor_delta = Vec2::ZERO;
-    for event in mouse_motion_events.iter() {
-        cursor_delta += event.delta;
+    let dt = timestep.delta_seconds(&time);
+    let input = BindingInput::new(
+        &keyboard,
+        &mouse_buttons,
+        &gamepads,
+        &gamepad_axes,
+        &gamepad_buttons,
+    );
+
+    for &gamepad in gamepads.iter() {
+        let input = input.with_source(InputSource::Gamepad(gamepad));
+
+        for (entity, controller, camera_input_map) in controllers.iter() {
+            if !controller.enabled
+                || !active_camera.allows(entity)
+                || !controller.input_source.accepts_gamepad(gamepad)
+            {
+                continue;
+            }
+
+            let input_map = camera_input_map.unwrap_or(&input_map);
+            let axis = |positive, negative| input_map.axis(positive, negative, &input);
+
+            // Pushing the stick up looks up, like moving the mouse up.
+            let rotation = controller.gamepad_rotate.apply(Vec2::new(
+                axis(CameraAction::LookRight, CameraAction::LookLeft),
+                axis(CameraAction::LookDown, CameraAction::LookUp),
+            ));
+            if rotation != Vec2::ZERO {
+                events.send(FPSControlEvent::Rotate(entity, dt * rotation));
+            }
+
+            let stick = controller.gamepad_translate.apply(Vec2::new(
+                axis(CameraAction::MoveRight, CameraAction::MoveLeft),
+                axis(CameraAction::MoveForward, CameraAction::MoveBack),
+            ));
+            let vertical = axis(CameraAction::MoveUp, CameraAction::MoveDown);
+            let dir = Vec3::new(-stick.x, vertical, stick.y);
+            if dir != Vec3::ZERO {
+                events.send(FPSControlEvent::TranslateEye(
+                    entity,
+                    controller.translate_speed(input_map, &input) * dir,
+                ));
+            }
+
+            if controller.zoom.is_some() && input_map.pressed(CameraAction::Aim, &input) {
+                events.send(FPSControlEvent::Zoom(entity));
+            }
+        }
     }
+}
 
-    events.send(FPSControlEvent::Rotate(
-        mouse_rotate_sensitivity * cursor_delta,
-    ));
+/// Grabs the cursor when a controller with `grab_cursor` is spawned, then toggles the grab with the
+/// `CameraAction::ToggleCursorGrab` bindings.
+pub fn grab_fps_cursor(
+    mut windows: ResMut<Windows>,
+    mut pointer_lock: ResMut<PointerLockState>,
+    input_capture: Res<InputCaptureState>,
+    mut accumulated_input: ResMut<AccumulatedInput>,
+    input_map: Res<CameraInputMap>,
+    keyboard: Res<Input<KeyCode>>,
+    mouse_buttons: Res<Input<MouseButton>>,
+    gamepads: Res<Gamepads>,
+    gamepad_axes: Res<Axis<GamepadAxis>>,
+    gamepad_buttons: Res<Input<GamepadButton>>,
+    added_controllers: Query<&FpsCameraController, Added<FpsCameraController>>,
+    controllers: Query<(&FpsCameraController, Option<&CameraInputMap>), Without<ControlToggle>>,
+) {
+    // Take the presses every frame, so they don't build up while there's no window.
+    let presses = accumulated_input.take_presses::<PointerLockState>();
+    let input = BindingInput::new(
+        &keyboard,
+        &mouse_buttons,
+        &gamepads,
+        &gamepad_axes,
+        &gamepad_buttons,
+    );
+
+    let window = if let Some(window) = windows.get_primary_mut() {
+        window
+    } else {
+        return;
+    };
 
-    for (key, dir) in [
-        (KeyCode::W, Vec3::Z),
-        (KeyCode::A, Vec3::X),
-        (KeyCode::S, -Vec3::Z),
-        (KeyCode::D, -Vec3::X),
-        (KeyCode::LShift, -Vec3::Y),
-        (KeyCode::Space, Vec3::Y),
-    ]
-    .iter()
-    .cloned()
+    if added_controllers
+        .iter()
+        .any(|controller| controller.enabled && controller.grab_cursor)
     {
-        if keyboard.pressed(key) {
-            events.send(FPSControlEvent::TranslateEye(translate_sensitivity * dir));
+        set_cursor_grab(window, true);
+    }
+
+    let toggle_pressed = controllers.iter().any(|(controller, camera_input_map)| {
+        controller.enabled
+            && controller.grab_cursor
+            && !input_capture.keyboard_captured
+            && camera_input_map.unwrap_or(&input_map).just_pressed(
+                CameraAction::ToggleCursorGrab,
+                &presses,
+                &input.with_source(controller.input_source),
+            )
+    });
+    if toggle_pressed {
+        let grab = !window.cursor_locked();
+        set_cursor_grab(window, grab);
+    }
+
+    let state = if window.cursor_locked() {
+        PointerLockState::Locked
+    } else {
+        PointerLockState::Unlocked
+    };
+    if *pointer_lock != state {
+        *pointer_lock = state;
+    }
+}
+
+/// The browser version of `grab_fps_cursor`. Browsers only grant pointer lock in response to a click or key press, so the
+/// lock is requested on a click in the page, or with a `CameraAction::ToggleCursorGrab` binding, rather than when the
+/// controller is spawned. The browser releases the lock by itself when Escape is pressed.
+#[cfg(all(feature = "web_pointer_lock", target_arch = "wasm32"))]
+pub(crate) fn grab_fps_cursor_web(
+    mut pointer_lock: ResMut<PointerLockState>,
+    lock_errors: Local<web_pointer_lock::LockErrors>,
+    input_capture: Res<InputCaptureState>,
+    mut accumulated_input: ResMut<AccumulatedInput>,
+    input_map: Res<CameraInputMap>,
+    keyboard: Res<Input<KeyCode>>,
+    mouse_buttons: Res<Input<MouseButton>>,
+    gamepads: Res<Gamepads>,
+    gamepad_axes: Res<Axis<GamepadAxis>>,
+    gamepad_buttons: Res<Input<GamepadButton>>,
+    controllers: Query<(&FpsCameraController, Option<&CameraInputMap>), Without<ControlToggle>>,
+) {
+    let presses = accumulated_input.take_presses::<PointerLockState>();
+    let input = BindingInput::new(
+        &keyboard,
+        &mouse_buttons,
+        &gamepads,
+        &gamepad_axes,
+        &gamepad_buttons,
+    );
+    let grabbing = || {
+        controllers
+            .iter()
+            .filter(|(controller, _)| controller.enabled && controller.grab_cursor)
+    };
+    let wants_lock = grabbing().next().is_some();
+    let toggle_pressed = !input_capture.keyboard_captured
+        && grabbing().any(|(controller, camera_input_map)| {
+            camera_input_map.unwrap_or(&input_map).just_pressed(
+                CameraAction::ToggleCursorGrab,
+                &presses,
+                &input.with_source(controller.input_source),
+            )
+        });
+    let clicked = !input_capture.pointer_captured && mouse_buttons.just_pressed(MouseButton::Left);
+
+    let state = match *pointer_lock {
+        _ if web_pointer_lock::is_locked() => {
+            if toggle_pressed {
+                web_pointer_lock::exit();
+            }
+            PointerLockState::Locked
+        }
+        PointerLockState::Requested if lock_errors.take() => PointerLockState::Unavailable,
+        PointerLockState::Requested => PointerLockState::Requested,
+        PointerLockState::Unavailable => PointerLockState::Unavailable,
+        PointerLockState::Locked | PointerLockState::Unlocked => {
+            if wants_lock && (clicked || toggle_pressed) {
+                if web_pointer_lock::request() {
+                    PointerLockState::Requested
+                } else {
+                    PointerLockState::Unavailable
+                }
+            } else {
+                PointerLockState::Unlocked
+            }
         }
+    };
+    if *pointer_lock != state {
+        *pointer_lock = state;
+    }
+}
+
+#[cfg(all(feature = "web_pointer_lock", target_arch = "wasm32"))]
+mod web_pointer_lock {
+    use std::sync::{
+        atomic::{AtomicBool, Ordering},
+        Arc,
+    };
+    use wasm_bindgen::{closure::Closure, JsCast};
+
+    fn document() -> Option<web_sys::Document> {
+        web_sys::window()?.document()
+    }
+
+    /// Whether the browser has refused a pointer lock request since the last `take`. The flag is set by a DOM event
+    /// listener, which lives for as long as the page.
+    pub struct LockErrors(Arc<AtomicBool>);
+
+    impl Default for LockErrors {
+        fn default() -> Self {
+            let failed = Arc::new(AtomicBool::new(false));
+            if let Some(document) = document() {
+                let listener_failed = failed.clone();
+                let listener = Closure::wrap(Box::new(move || {
+                    listener_failed.store(true, Ordering::Relaxed);
+                }) as Box<dyn FnMut()>);
+                let _ = document.add_event_listener_with_callback(
+                    "pointerlockerror",
+                    listener.as_ref().unchecked_ref(),
+                );
+                listener.forget();
+            }
+
+            Self(failed)
+        }
+    }
+
+    impl LockErrors {
+        pub fn take(&self) -> bool {
+            self.0.swap(false, Ordering::Relaxed)
+        }
+    }
+
+    /// Requests pointer lock on the page's first canvas, which is where Bevy renders. Returns `false` if there's no canvas.
+    pub fn request() -> bool {
+        match document().and_then(|document| document.query_selector("canvas").ok().flatten()) {
+            Some(canvas) => {
+                canvas.request_pointer_lock();
+                true
+            }
+            None => false,
+        }
+    }
+
+    pub fn exit() {
+        if let Some(document) = document() {
+            document.exit_pointer_lock();
+        }
+    }
+
+    pub fn is_locked() -> bool {
+        document()
+            .and_then(|document| document.pointer_lock_element())
+            .is_some()
     }
 }
 
 pub fn control_fps_camera(
+    time: Res<Time>,
+    timestep: Res<ControlTimestep>,
+    reduced_motion: Res<ReducedMotion>,
+    ground_height: Option<Res<GroundHeight>>,
     mut events: EventReader<FPSControlEvent>,
-    mut cameras: Query<(&FpsCameraController, &mut LookTransform, With<Transform>)>,
+    lock_targets: Query<&GlobalTransform>,
+    mut cameras: Query<
+        (
+            Entity,
+            &FpsCameraController,
+            &mut FpsCameraState,
+            &mut LookTransform,
+            Option<&mut PerspectiveProjection>,
+            Option<&mut LookTransformOffset>,
+            Option<&DollyZoom>,
+        ),
+        (With<Transform>, Without<LookTransformTween>),
+    >,
 ) {
-    // Can only control one camera at a time.
-    let (controller, mut transform) =
-        if let Some((controller, transform, _)) = cameras.iter_mut().next() {
-            (controller, transform)
-        } else {
-            return;
-        };
+    // Every camera only consumes the events that were routed to it.
+    let events: Vec<&FPSControlEvent> = events.iter().collect();
+    let dt = timestep.delta_seconds(&time);
+
+    for (entity, controller, mut state, mut look_transform, projection, offset, dolly_zoom) in
+        cameras.iter_mut()
+    {
+        if !controller.enabled {
+            // Don't leave a disabled camera frozen mid-bob.
+            if state.bob_offset != Vec3::ZERO {
+                if let Some(mut offset) = offset {
+                    offset.0 -= state.bob_offset;
+                }
+                state.bob_offset = Vec3::ZERO;
+            }
+            continue;
+        }
 
-    if controller.enabled {
+        let mut transform = *look_transform;
+
+        let up = controller.up;
         let look_vector = transform.look_direction();
-        let mut look_angles = LookAngles::from_vector(look_vector);
+        let look_angles = LookAngles::from_vector_with_up(look_vector, up);
 
-        let yaw_rot = Quat::from_axis_angle(Vec3::Y, look_angles.get_yaw());
+        let yaw_rot = y_up_rotation(up) * Quat::from_axis_angle(Vec3::Y, look_angles.get_yaw());
         let rot_x = yaw_rot * Vec3::X;
         let rot_y = yaw_rot * Vec3::Y;
-        let rot_z = yaw_rot * Vec3::Z;
+        let rot_z = match controller.movement_mode {
+            FpsMovementMode::Walk => yaw_rot * Vec3::Z,
+            FpsMovementMode::Fly => look_vector,
+        };
 
-        for event in events.iter() {
+        let mut rotation = Vec2::ZERO;
+        let mut moving = false;
+        let mut zoomed = false;
+
+        for event in events.iter().filter(|e| e.camera() == entity) {
             match event {
-                FPSControlEvent::Rotate(delta) => {
-                    // Rotates with pitch and yaw.
-                    look_angles.add_yaw(-delta.x);
-                    look_angles.add_pitch(-delta.y);
+                FPSControlEvent::Rotate(_, delta) => {
+                    rotation += *delta;
                 }
-                FPSControlEvent::TranslateEye(delta) => {
+                FPSControlEvent::TranslateEye(_, delta) => {
                     // Translates up/down (Y) left/right (X) and forward/back (Z).
-                    transform.eye += delta.x * rot_x + delta.y * rot_y + delta.z * rot_z;
+                    transform.eye += dt * (delta.x * rot_x + delta.y * rot_y + delta.z * rot_z);
+                    moving |= *delta != Vec3::ZERO;
+                }
+                FPSControlEvent::Zoom(_) => {
+                    zoomed = true;
                 }
             }
         }
 
-        look_angles.assert_not_looking_up();
+        if let Some(mut gravity) = controller.gravity {
+            gravity.step_lag_weight = reduced_motion.lag_weight(gravity.step_lag_weight);
+            // Fall in a Y-up frame, so heights are measured along the controller's `up`.
+            let to_world = y_up_rotation(up);
+            let mut eye = to_world.inverse() * transform.eye;
+            let ground = ground_height
+                .as_ref()
+                .and_then(|g| g.height_at(Vec2::new(eye.x, eye.z)))
+                .unwrap_or(gravity.ground_height);
+            let (height, fall_speed) = gravity.fall(eye.y, state.fall_speed, ground, dt);
+            eye.y = height;
+            transform.eye = to_world * eye;
+            state.fall_speed = fall_speed;
+        }
 
-        transform.target = transform.eye + transform.radius() * look_angles.unit_vector();
-    } else {
-        events.iter(); // Drop the events.
+        // Aim at the locked target from wherever the eye moved to, unless it's gone or the eye is right on top of it.
+        let locked_direction = controller
+            .lock_target
+            .and_then(|target| lock_targets.get(target).ok())
+            .map(|target| target.translation - transform.eye)
+            .filter(|direction| direction.length_squared() > 1e-6);
+        let turn = match locked_direction {
+            Some(direction) => Some((direction.normalize(), Vec2::ZERO)),
+            None if rotation != Vec2::ZERO => Some((look_vector, rotation)),
+            None => None,
+        };
+        match turn {
+            Some((look_vector, rotation)) => transform.set_look_direction(fps_rotate(
+                look_vector,
+                up,
+                rotation,
+                controller.min_pitch,
+                controller.max_pitch,
+            )),
+            // Rebuilding the look direction rounds a little differently every step, which would count as a change to the
+            // `LookTransform`, so a view that isn't turning only moves along with the eye.
+            None => transform.target += transform.eye - look_transform.eye,
+        }
+        transform.up = up;
+
+        let head_bob = controller.head_bob.filter(|_| !reduced_motion.enabled);
+        let bob_offset = if let Some(head_bob) = head_bob {
+            let goal_intensity = if moving { 1.0 } else { 0.0 };
+            state.bob_intensity =
+                smooth(state.bob_intensity, goal_intensity, head_bob.lag_weight, dt);
+            // The sideways sway completes a cycle every two bobs.
+            state.bob_phase = (state.bob_phase + dt * head_bob.frequency * TAU) % (2.0 * TAU);
+            head_bob.offset(state.bob_phase, state.bob_intensity, rot_x, rot_y)
+        } else {
+            Vec3::ZERO
+        };
+        // The bob only moves the view, so it goes in the offset rather than the `LookTransform`.
+        if let Some(mut offset) = offset {
+            if bob_offset != state.bob_offset {
+                offset.0 += bob_offset - state.bob_offset;
+            }
+            state.bob_offset = bob_offset;
+        }
+        set_look_transform_if_changed(&mut look_transform, transform);
+
+        // A dolly zoom owns the field of view until it's finished.
+        if let (Some(mut zoom), Some(mut projection), None) =
+            (controller.zoom, projection, dolly_zoom)
+        {
+            zoom.lag_weight = reduced_motion.lag_weight(zoom.lag_weight);
+            let fov = zoom.step(projection.fov, zoomed, dt);
+            if fov != projection.fov {
+                projection.fov = fov;
+            }
+        }
+    }
+}
+
+/// Rotates the look direction by yaw around `up` and pitch toward it, keeping the pitch within `[min_pitch, max_pitch]`.
+fn fps_rotate(
+    look_direction: Vec3,
+    up: Vec3,
+    rotation: Vec2,
+    min_pitch: f32,
+    max_pitch: f32,
+) -> Vec3 {
+    let mut look_angles = LookAngles::from_vector_with_up(look_direction, up);
+    look_angles.add_yaw(-rotation.x);
+    look_angles.add_pitch(-rotation.y);
+    look_angles.clamp_pitch(min_pitch, max_pitch);
+
+    look_angles.unit_vector_with_up(up)
+}
+
+// ████████╗███████╗███████╗████████╗
+// ╚══██╔══╝██╔════╝██╔════╝╚══██╔══╝
+//    ██║   █████╗  ███████╗   ██║
+//    ██║   ██╔══╝  ╚════██║   ██║
+//    ██║   ███████╗███████║   ██║
+//    ╚═╝   ╚══════╝╚══════╝   ╚═╝
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    use approx::assert_relative_eq;
+    use std::f32::consts::PI;
+
+    #[test]
+    fn test_head_bob_offset() {
+        let head_bob = FpsHeadBob {
+            vertical_amplitude: 0.1,
+            lateral_amplitude: 0.05,
+            ..Default::default()
+        };
+
+        let peak = head_bob.offset(0.5 * PI, 1.0, Vec3::X, Vec3::Y);
+        assert_relative_eq!(peak.y, 0.1);
+        assert_relative_eq!(peak.x, 0.05 * (0.25 * PI).sin());
+
+        assert_eq!(head_bob.offset(0.5 * PI, 0.0, Vec3::X, Vec3::Y), Vec3::ZERO);
+    }
+
+    #[test]
+    fn test_gravity_falls_and_lands() {
+        let gravity = FpsGravity {
+            eye_height: 2.0,
+            ground_height: 0.0,
+            acceleration: 10.0,
+            ..Default::default()
+        };
+
+        let (height, fall_speed) = gravity.fall(10.0, 0.0, 0.0, 0.5);
+        assert_relative_eq!(fall_speed, 5.0);
+        assert_relative_eq!(height, 7.5);
+
+        let (height, fall_speed) = gravity.fall(2.1, 20.0, 0.0, 0.5);
+        assert_relative_eq!(height, 2.0);
+        assert_relative_eq!(fall_speed, 0.0);
+    }
+
+    #[test]
+    fn test_gravity_eases_up_steps() {
+        let gravity = FpsGravity {
+            eye_height: 2.0,
+            step_lag_weight: 0.5,
+            ..Default::default()
+        };
+
+        // A step half a unit up.
+        let (height, fall_speed) = gravity.fall(2.0, 0.0, 0.5, 1.0 / 60.0);
+        assert_relative_eq!(height, 2.25);
+        assert_relative_eq!(fall_speed, 0.0);
+
+        let mut height = height;
+        for _ in 0..20 {
+            height = gravity.fall(height, 0.0, 0.5, 1.0 / 60.0).0;
+        }
+        assert_eq!(height, 2.5);
+
+        // Without smoothing, the eye steps right up.
+        let gravity = FpsGravity {
+            step_lag_weight: 0.0,
+            ..gravity
+        };
+        assert_eq!(gravity.fall(2.0, 0.0, 0.5, 1.0 / 60.0).0, 2.5);
+    }
+
+    #[test]
+    fn test_rotation_stays_within_pitch_limits_and_undoes() {
+        let (min_pitch, max_pitch) = (-1.0, 1.2);
+        let mut look_direction = Vec3::new(0.0, 0.0, -1.0);
+        for i in 0..200 {
+            let rotation = Vec2::new((i as f32 * 1.3).sin(), 3.0 * (i as f32 * 0.7).cos());
+            look_direction = fps_rotate(look_direction, Vec3::Y, rotation, min_pitch, max_pitch);
+
+            assert_relative_eq!(look_direction.length(), 1.0, epsilon = 1e-5);
+            let pitch = look_direction.y.asin();
+            assert!(pitch >= min_pitch - 1e-4 && pitch <= max_pitch + 1e-4);
+        }
+
+        // Away from the limits, turning back by the same amount returns to the start.
+        let rotation = Vec2::new(0.4, -0.3);
+        let start = Vec3::new(0.0, 0.0, -1.0);
+        let turned = fps_rotate(start, Vec3::Y, rotation, min_pitch, max_pitch);
+        let back = fps_rotate(turned, Vec3::Y, -rotation, min_pitch, max_pitch);
+        assert!(back.abs_diff_eq(start, 1e-5));
+    }
+
+    #[test]
+    fn test_zoom_eases_in_and_back_out() {
+        let zoom = FpsZoom::default();
+
+        let mut fov = zoom.normal_fov;
+        for _ in 0..100 {
+            fov = zoom.step(fov, true, 1.0 / 60.0);
+            assert!(fov >= zoom.zoomed_fov);
+        }
+        assert_eq!(fov, zoom.zoomed_fov);
+
+        for _ in 0..100 {
+            fov = zoom.step(fov, false, 1.0 / 60.0);
+        }
+        assert_eq!(fov, zoom.normal_fov);
     }
 }
@@ -0,0 +1,323 @@
+use crate::{
+    input_filter::InputFilterState, look_angles::{any_orthogonal, normalize_or},
+    set_look_transform_if_changed, viewport::{pointer_in_viewport, viewport_window},
+    AccumulatedInput, ActiveCameraEntity, AddCameraController, BindingInput, CameraAction,
+    CameraInputMap, ControlTimestep, ControllerEnabled, ControllerViewport, InputAxisConfig,
+    InputCaptureState, InputFilter, InputSource, LookTransform, LookTransformTween,
+    PointerNormalization, ReplayableControlEvent, ToggleableController,
+};
+
+use bevy::{
+    app::prelude::*,
+    core::Time,
+    ecs::{bundle::Bundle, prelude::*},
+    input::{
+        gamepad::{GamepadAxis, GamepadButton, Gamepads},
+        prelude::*,
+    },
+    math::prelude::*,
+    transform::components::Transform,
+    window::Windows,
+};
+use serde::{Deserialize, Serialize};
+
+#[derive(Default)]
+pub struct FreeFlyCameraPlugin {
+    /// Skips the built-in input mapping, so the camera is only driven by `FreeFlyControlEvent`s that you send.
+    pub override_input_system: bool,
+}
+
+impl Plugin for FreeFlyCameraPlugin {
+    fn build(&self, app: &mut App) {
+        app.add_camera_controller::<FreeFlyControlEvent, _, _>(
+            map_free_fly_input.system(),
+            control_free_fly_camera.system(),
+            self.override_input_system,
+        )
+        .add_control_toggle::<FreeFlyCameraController>();
+    }
+}
+
+#[derive(Bundle, Deserialize, Serialize)]
+pub struct FreeFlyCameraBundle {
+    controller: FreeFlyCameraController,
+}
+
+impl FreeFlyCameraBundle {
+    pub fn new(controller: FreeFlyCameraController) -> Self {
+        Self { controller }
+    }
+}
+
+/// A 6-DOF flying camera. Unlike the FPS controller, all rotation and translation happens relative to the camera's own axes,
+/// and the camera can roll about its look direction, so there is no fixed "up."
+#[derive(Clone, Copy, Debug, Deserialize, Serialize)]
+pub struct FreeFlyCameraController {
+    pub enabled: bool,
+    /// The devices that drive this camera. Defaults to all of them.
+    pub input_source: InputSource,
+    /// Scales mouse motion into yaw (X) and pitch (Y) in radians.
+    pub mouse_rotate: InputAxisConfig,
+    /// Filters jitter out of mouse motion before it's mapped. Off by default.
+    pub mouse_filter: InputFilter,
+    /// Scales the `LookLeft`, `LookRight`, `LookUp`, and `LookDown` bindings, e.g. the right stick, into yaw (X) and
+    /// pitch (Y) in radians per second.
+    pub gamepad_rotate: InputAxisConfig,
+    /// Translation speed in units per second.
+    pub translate_sensitivity: f32,
+    /// Roll speed in radians per second.
+    pub roll_sensitivity: f32,
+}
+
+impl Default for FreeFlyCameraController {
+    fn default() -> Self {
+        Self {
+            enabled: true,
+            input_source: InputSource::Any,
+            mouse_rotate: InputAxisConfig::new(Vec2::splat(0.002)),
+            mouse_filter: InputFilter::None,
+            gamepad_rotate: InputAxisConfig::new(Vec2::splat(3.0)).with_deadzone(0.15),
+            translate_sensitivity: 2.0,
+            roll_sensitivity: 1.0,
+        }
+    }
+}
+
+impl ToggleableController for FreeFlyCameraController {
+    fn enabled(&self) -> bool {
+        self.enabled
+    }
+
+    fn enabled_mut(&mut self) -> &mut bool {
+        &mut self.enabled
+    }
+}
+
+/// Control events for the free-fly controller.
+#[derive(Clone, Copy, Debug, Deserialize, PartialEq, Serialize)]
+pub enum FreeFlyControlEvent {
+    /// Turns the camera about its local axes by yaw (X) and pitch (Y) in radians. Positive X turns right and positive Y looks
+    /// down, matching the direction of mouse motion.
+    Rotate(Entity, Vec2),
+    /// Roll velocity in radians per second, which is scaled by the frame time when applied.
+    Roll(Entity, f32),
+    /// Eye velocity in units per second, which is scaled by the frame time when applied.
+    TranslateEye(Entity, Vec3),
+}
+
+impl FreeFlyControlEvent {
+    /// The camera entity that this event controls.
+    pub fn camera(&self) -> Entity {
+        match *self {
+            Self::Rotate(camera, _) | Self::Roll(camera, _) | Self::TranslateEye(camera, _) => {
+                camera
+            }
+        }
+    }
+
+    /// Whether this event moves the camera at all, unlike the zero rotation that's sent while the mouse is still.
+    fn moves(&self) -> bool {
+        match *self {
+            Self::Rotate(_, delta) => delta != Vec2::ZERO,
+            Self::Roll(_, delta) => delta != 0.0,
+            Self::TranslateEye(_, delta) => delta != Vec3::ZERO,
+        }
+    }
+}
+
+pub fn map_free_fly_input(
+    controller_enabled: Res<ControllerEnabled>,
+    active_camera: Res<ActiveCameraEntity>,
+    input_capture: Res<InputCaptureState>,
+    pointer_normalization: Res<PointerNormalization>,
+    time: Res<Time>,
+    timestep: Res<ControlTimestep>,
+    mut events: EventWriter<FreeFlyControlEvent>,
+    input_map: Res<CameraInputMap>,
+    keyboard: Res<Input<KeyCode>>,
+    mouse_buttons: Res<Input<MouseButton>>,
+    gamepads: Res<Gamepads>,
+    gamepad_axes: Res<Axis<GamepadAxis>>,
+    gamepad_buttons: Res<Input<GamepadButton>>,
+    mut accumulated_input: ResMut<AccumulatedInput>,
+    windows: Res<Windows>,
+    mut filters: Local<InputFilterState>,
+    controllers: Query<
+        (
+            Entity,
+            &FreeFlyCameraController,
+            Option<&ControllerViewport>,
+            Option<&CameraInputMap>,
+        ),
+        With<Transform>,
+    >,
+) {
+    // Take the input even when it's ignored, so it doesn't build up while the controllers are paused.
+    let pointer_delta = accumulated_input.take::<FreeFlyControlEvent>();
+    if !controller_enabled.0 {
+        return;
+    }
+
+    let cursor_delta = pointer_delta.motion;
+    let dt = timestep.delta_seconds(&time);
+    let input = BindingInput::new(
+        &keyboard,
+        &mouse_buttons,
+        &gamepads,
+        &gamepad_axes,
+        &gamepad_buttons,
+    );
+
+    // Forget the filter history of cameras that were despawned or lost their controller.
+    filters.retain(|camera| controllers.get(camera).is_ok());
+
+    for (entity, controller, viewport, camera_input_map) in controllers.iter() {
+        let FreeFlyCameraController {
+            enabled,
+            translate_sensitivity,
+            roll_sensitivity,
+            ..
+        } = *controller;
+
+        if !enabled || !active_camera.allows(entity) {
+            continue;
+        }
+
+        let cursor_delta =
+            pointer_normalization.normalize(cursor_delta, viewport_window(&windows, viewport));
+        let cursor_delta = filters.apply(entity, controller.mouse_filter, cursor_delta, dt);
+        if !input_capture.pointer_captured
+            && controller.input_source.accepts_keyboard_mouse()
+            && pointer_in_viewport(&windows, viewport)
+        {
+            events.send(FreeFlyControlEvent::Rotate(
+                entity,
+                controller.mouse_rotate.apply(cursor_delta),
+            ));
+        }
+
+        if input_capture.keyboard_captured {
+            continue;
+        }
+
+        let input_map = camera_input_map.unwrap_or(&input_map);
+        let input = input.with_source(controller.input_source);
+        let pressed = |action| input_map.pressed(action, &input);
+
+        // Pushing the stick up looks up, like moving the mouse up.
+        let rotation = controller.gamepad_rotate.apply(Vec2::new(
+            input_map.axis(CameraAction::LookRight, CameraAction::LookLeft, &input),
+            input_map.axis(CameraAction::LookDown, CameraAction::LookUp, &input),
+        ));
+        if rotation != Vec2::ZERO {
+            events.send(FreeFlyControlEvent::Rotate(entity, dt * rotation));
+        }
+
+        for (action, dir) in [(CameraAction::RollLeft, -1.0), (CameraAction::RollRight, 1.0)]
+            .iter()
+            .cloned()
+        {
+            if pressed(action) {
+                events.send(FreeFlyControlEvent::Roll(entity, roll_sensitivity * dir));
+            }
+        }
+
+        for (action, dir) in [
+            (CameraAction::MoveForward, Vec3::Z),
+            (CameraAction::MoveLeft, Vec3::X),
+            (CameraAction::MoveBack, -Vec3::Z),
+            (CameraAction::MoveRight, -Vec3::X),
+            (CameraAction::MoveDown, -Vec3::Y),
+            (CameraAction::MoveUp, Vec3::Y),
+        ]
+        .iter()
+        .cloned()
+        {
+            if pressed(action) {
+                events.send(FreeFlyControlEvent::TranslateEye(
+                    entity,
+                    translate_sensitivity * dir,
+                ));
+            }
+        }
+    }
+}
+
+pub fn control_free_fly_camera(
+    time: Res<Time>,
+    timestep: Res<ControlTimestep>,
+    mut events: EventReader<FreeFlyControlEvent>,
+    mut cameras: Query<
+        (Entity, &FreeFlyCameraController, &mut LookTransform),
+        (With<Transform>, Without<LookTransformTween>),
+    >,
+) {
+    // Every camera only consumes the events that were routed to it.
+    let events: Vec<&FreeFlyControlEvent> = events.iter().collect();
+    let dt = timestep.delta_seconds(&time);
+
+    for (entity, controller, mut look_transform) in cameras.iter_mut() {
+        if !controller.enabled {
+            continue;
+        }
+
+        let mut transform = *look_transform;
+        let camera_events: Vec<&FreeFlyControlEvent> = events
+            .iter()
+            .filter(|e| e.camera() == entity && e.moves())
+            .cloned()
+            .collect();
+        // Rebuilding the camera's axes rounds a little differently every step, which would count as a change to the
+        // `LookTransform`, so a camera is left alone when nothing moves it.
+        if camera_events.is_empty() {
+            continue;
+        }
+        FreeFlyControlEvent::apply_step(&mut transform, &mut (), &camera_events, dt);
+        set_look_transform_if_changed(&mut look_transform, transform);
+    }
+}
+
+impl ReplayableControlEvent for FreeFlyControlEvent {
+    /// Free-fly events only act on the `LookTransform`.
+    type Context = ();
+
+    fn camera(&self) -> Entity {
+        FreeFlyControlEvent::camera(self)
+    }
+
+    fn apply_step(transform: &mut LookTransform, _: &mut (), events: &[&Self], delta_seconds: f32) {
+        let mut forward = transform.look_direction();
+        // Keep an orthonormal basis so the axes don't drift as rotations accumulate.
+        let mut up = normalize_or(
+            transform.up - transform.up.dot(forward) * forward,
+            any_orthogonal(forward),
+        );
+
+        for event in events {
+            match event {
+                FreeFlyControlEvent::Rotate(_, delta) => {
+                    // Yaw about the local up axis, then pitch about the local right axis.
+                    let yaw = Quat::from_axis_angle(up, -delta.x);
+                    forward = yaw * forward;
+
+                    let right = forward.cross(up);
+                    let pitch = Quat::from_axis_angle(right, -delta.y);
+                    forward = pitch * forward;
+                    up = pitch * up;
+                }
+                FreeFlyControlEvent::Roll(_, delta) => {
+                    up = Quat::from_axis_angle(forward, delta_seconds * delta) * up;
+                }
+                FreeFlyControlEvent::TranslateEye(_, delta) => {
+                    // Translates up/down (Y) left/right (X) and forward/back (Z) in the camera's frame.
+                    let left = up.cross(forward);
+                    transform.eye +=
+                        delta_seconds * (delta.x * left + delta.y * up + delta.z * forward);
+                }
+            }
+        }
+
+        transform.set_look_direction(forward);
+        transform.up = up;
+    }
+}
@@ -0,0 +1,986 @@
+//! Tests that run the built-in controllers in a headless `App`, driven by scripted control events instead of input.
+
+use super::{
+    chase::{ChaseCameraBundle, ChaseCameraController, ChaseCameraPlugin, ChaseControlEvent},
+    fps::{
+        FPSControlEvent, FpsCameraBundle, FpsCameraController, FpsCameraPlugin, FpsGravity,
+        FpsHeadBob, GroundHeight,
+    },
+    follow::{FollowCameraBundle, FollowCameraController, FollowCameraPlugin, FollowControlEvent},
+    free_fly::{
+        FreeFlyCameraBundle, FreeFlyCameraController, FreeFlyCameraPlugin, FreeFlyControlEvent,
+    },
+    isometric::{
+        IsometricCameraBundle, IsometricCameraController, IsometricCameraPlugin,
+        IsometricControlEvent,
+    },
+    orbit::{
+        OrbitCameraBundle, OrbitCameraController, OrbitCameraPlugin, OrbitCameraState,
+        OrbitControlEvent, OrbitZoomMode, StandardView,
+    },
+    pancam::{PanCamBundle, PanCamControlEvent, PanCamController, PanCamPlugin},
+    rail::{CameraRail, RailCameraBundle, RailCameraController, RailCameraPlugin, RailControlEvent},
+    rig::{CameraRig, CameraRigPlugin, RigController, SwitchCameraController},
+    rts::{RtsCameraBundle, RtsCameraController, RtsCameraPlugin, RtsControlEvent},
+};
+use crate::{
+    apply_events, ActiveCameraEntity, AddCameraController, CameraJumpEvent, ControlEventRecorder,
+    ControlTimestep, ControlToggle, Easing, LookTransform, LookTransformBundle, LookTransformOffset,
+    LookTransformPlugin, LookTransformSpace, LookTransformTween, PathInterpolation, ReducedMotion,
+    ScriptedControlEvents,
+};
+
+use approx::assert_relative_eq;
+use bevy::{
+    app::prelude::*,
+    core::CorePlugin,
+    ecs::{component::Component, prelude::*},
+    input::{
+        keyboard::{KeyCode, KeyboardInput},
+        ElementState, InputPlugin,
+    },
+    math::prelude::*,
+    render::{camera::OrthographicProjection, prelude::*},
+    transform::components::{GlobalTransform, Parent, Transform},
+    window::WindowPlugin,
+};
+use std::{f32::consts::{FRAC_PI_2, PI}, time::Duration};
+
+/// A headless app that runs the controller added by `plugin`, with its input mapping overridden by a
+/// `ScriptedControlEvents<E>`. The controllers and the `Smoother` step by a sixtieth of a second on every update.
+fn harness<E: Component, P: Plugin>(plugin: P) -> App {
+    let mut app = App::new();
+    app.add_plugin(CorePlugin)
+        .add_plugin(InputPlugin)
+        .add_plugin(WindowPlugin::default())
+        .insert_resource(ControlTimestep::Stepped(1.0 / 60.0))
+        .add_plugin(LookTransformPlugin)
+        .add_plugin(plugin)
+        .add_scripted_control_events::<E>();
+
+    app
+}
+
+/// Queues `(step, event)` pairs, then runs updates until every scripted event has been sent.
+fn run_script<E: Component>(app: &mut App, events: Vec<(u64, E)>) {
+    {
+        let mut script = app
+            .world
+            .get_resource_mut::<ScriptedControlEvents<E>>()
+            .unwrap();
+        for (step, event) in events {
+            script.push(step, event);
+        }
+    }
+
+    while !app
+        .world
+        .get_resource::<ScriptedControlEvents<E>>()
+        .unwrap()
+        .is_finished()
+    {
+        app.update();
+    }
+}
+
+fn look_transform(app: &App, camera: Entity) -> LookTransform {
+    *app.world.get::<LookTransform>(camera).unwrap()
+}
+
+fn spawn_orbit_camera(app: &mut App) -> Entity {
+    app.world
+        .spawn()
+        .insert_bundle(OrbitCameraBundle::new(
+            OrbitCameraController::default(),
+            PerspectiveCameraBundle::default(),
+            Vec3::new(0.0, 0.0, 5.0),
+            Vec3::ZERO,
+        ))
+        .id()
+}
+
+#[test]
+fn test_orbit_rotates_around_target_and_zooms() {
+    let mut app = harness::<OrbitControlEvent, _>(OrbitCameraPlugin {
+        override_input_system: true,
+    });
+    let camera = spawn_orbit_camera(&mut app);
+
+    run_script(
+        &mut app,
+        vec![(
+            0,
+            OrbitControlEvent::Orbit(camera, Vec2::new(FRAC_PI_2, 0.0)),
+        )],
+    );
+    let transform = look_transform(&app, camera);
+    assert_eq!(transform.target, Vec3::ZERO);
+    assert_relative_eq!(transform.radius(), 5.0, epsilon = 1e-4);
+    assert_relative_eq!(transform.eye.x.abs(), 5.0, epsilon = 1e-4);
+    assert_relative_eq!(transform.eye.y, 0.0, epsilon = 1e-4);
+
+    run_script(&mut app, vec![(1, OrbitControlEvent::Zoom(camera, 0.5))]);
+    let transform = look_transform(&app, camera);
+    assert_relative_eq!(transform.radius(), 2.5, epsilon = 1e-4);
+}
+
+#[test]
+fn test_orbit_pans_orthographic_cameras_by_their_scale() {
+    let mut app = harness::<OrbitControlEvent, _>(OrbitCameraPlugin {
+        override_input_system: true,
+    });
+    let camera = spawn_orbit_camera(&mut app);
+    app.world.entity_mut(camera).insert(OrthographicProjection {
+        scale: 2.0,
+        ..Default::default()
+    });
+
+    // Scripted panning covers as much of the screen as it would at a scale of one.
+    run_script(&mut app, vec![(0, OrbitControlEvent::TranslateTarget(camera, Vec2::X))]);
+    let transform = look_transform(&app, camera);
+    assert!(transform.target.abs_diff_eq(Vec3::new(-2.0, 0.0, 0.0), 1e-4));
+    assert!((transform.eye - transform.target).abs_diff_eq(Vec3::new(0.0, 0.0, 5.0), 1e-4));
+}
+
+#[test]
+fn test_orbit_camera_without_state_still_moves() {
+    let mut app = harness::<OrbitControlEvent, _>(OrbitCameraPlugin {
+        override_input_system: true,
+    });
+    let (look_transform_bundle, transform) =
+        LookTransformBundle::new(Vec3::new(0.0, 0.0, 5.0), Vec3::ZERO, 0.0);
+    let camera = app
+        .world
+        .spawn()
+        .insert_bundle(look_transform_bundle)
+        .insert(transform)
+        .insert(OrbitCameraController::default())
+        .id();
+
+    run_script(&mut app, vec![(1, OrbitControlEvent::Zoom(camera, 0.5))]);
+    assert!(app.world.get::<OrbitCameraState>(camera).is_some());
+    assert_relative_eq!(look_transform(&app, camera).radius(), 2.5, epsilon = 1e-4);
+}
+
+#[test]
+fn test_camera_jump_stops_a_zoom_in_progress() {
+    let mut app = harness::<OrbitControlEvent, _>(OrbitCameraPlugin {
+        override_input_system: true,
+    });
+    let camera = spawn_orbit_camera(&mut app);
+    app.world.get_mut::<OrbitCameraController>(camera).unwrap().zoom_lag_weight = 0.9;
+
+    // Jump away while the zoom is still easing in.
+    run_script(&mut app, vec![(0, OrbitControlEvent::Zoom(camera, 0.5))]);
+    assert!(look_transform(&app, camera).radius() > 2.5 + 1e-2);
+    app.world
+        .get_resource_mut::<Events<CameraJumpEvent>>()
+        .unwrap()
+        .send(CameraJumpEvent::new(camera, Vec3::new(0.0, 0.0, 20.0), Vec3::ZERO));
+
+    for _ in 0..60 {
+        app.update();
+    }
+    assert_relative_eq!(look_transform(&app, camera).radius(), 20.0, epsilon = 1e-4);
+}
+
+#[test]
+fn test_orbit_zooms_to_exact_radius_within_limits() {
+    let mut app = harness::<OrbitControlEvent, _>(OrbitCameraPlugin {
+        override_input_system: true,
+    });
+    let camera = spawn_orbit_camera(&mut app);
+    app.world.get_mut::<OrbitCameraController>(camera).unwrap().zoom_mode =
+        OrbitZoomMode::Linear {
+            reference_radius: 10.0,
+        };
+
+    run_script(&mut app, vec![(0, OrbitControlEvent::ZoomTo(camera, 8.0))]);
+    assert_relative_eq!(look_transform(&app, camera).radius(), 8.0, epsilon = 1e-4);
+
+    // The factor is exact even though the controller zooms linearly.
+    run_script(&mut app, vec![(1, OrbitControlEvent::ZoomBy(camera, 0.25))]);
+    assert_relative_eq!(look_transform(&app, camera).radius(), 2.0, epsilon = 1e-4);
+
+    run_script(
+        &mut app,
+        vec![
+            (2, OrbitControlEvent::ZoomBy(camera, 0.0)),
+            (3, OrbitControlEvent::ZoomBy(camera, -1.0)),
+        ],
+    );
+    assert_relative_eq!(look_transform(&app, camera).radius(), 2.0, epsilon = 1e-4);
+
+    run_script(&mut app, vec![(4, OrbitControlEvent::ZoomTo(camera, 1e6))]);
+    assert_relative_eq!(look_transform(&app, camera).radius(), 1000.0, epsilon = 1e-2);
+}
+
+#[test]
+fn test_orbit_runs_are_reproducible() {
+    let run = || {
+        let mut app = harness::<OrbitControlEvent, _>(OrbitCameraPlugin {
+            override_input_system: true,
+        });
+        let camera = spawn_orbit_camera(&mut app);
+        let events = (0..30)
+            .map(|step| {
+                (
+                    step,
+                    OrbitControlEvent::Orbit(camera, Vec2::new(0.05, 0.02)),
+                )
+            })
+            .chain(vec![(
+                10,
+                OrbitControlEvent::TranslateTarget(camera, Vec2::X),
+            )])
+            .collect();
+        run_script(&mut app, events);
+
+        (
+            look_transform(&app, camera),
+            *app.world.get::<Transform>(camera).unwrap(),
+        )
+    };
+
+    // The `Smoother` steps by the same amount as the controller, so even the smoothed `Transform` matches.
+    assert_eq!(run(), run());
+}
+
+#[test]
+fn test_smooth_views_and_tweens_are_reproducible() {
+    let run = |frame_pause_millis: u64| {
+        let mut app = harness::<OrbitControlEvent, _>(OrbitCameraPlugin {
+            override_input_system: true,
+        });
+        let camera = spawn_orbit_camera(&mut app);
+        run_script(&mut app, vec![(0, OrbitControlEvent::SetView(camera, StandardView::Right, true))]);
+
+        let end = LookTransform::new(Vec3::new(0.0, 5.0, 5.0), Vec3::ZERO);
+        app.world.entity_mut(camera).insert(LookTransformTween::ease_to(end, 0.5, Easing::EaseInOut));
+        for _ in 0..10 {
+            std::thread::sleep(Duration::from_millis(frame_pause_millis));
+            app.update();
+        }
+
+        (
+            look_transform(&app, camera),
+            *app.world.get::<Transform>(camera).unwrap(),
+        )
+    };
+
+    // The real frame times differ between the runs, but the tween and the `Smoother` step by the `ControlTimestep`.
+    assert_eq!(run(0), run(2));
+}
+
+#[test]
+fn test_fps_walks_level_and_keeps_pitch_in_limits() {
+    let mut app = harness::<FPSControlEvent, _>(FpsCameraPlugin {
+        override_input_system: true,
+    });
+    let camera = app
+        .world
+        .spawn()
+        .insert_bundle(FpsCameraBundle::new(
+            FpsCameraController::default(),
+            PerspectiveCameraBundle::default(),
+            Vec3::new(0.0, 1.0, 0.0),
+            Vec3::new(0.0, 1.0, -1.0),
+        ))
+        .id();
+
+    // A second of walking at one unit per second.
+    let events = (0..60)
+        .map(|step| (step, FPSControlEvent::TranslateEye(camera, Vec3::Z)))
+        .collect();
+    run_script(&mut app, events);
+    let transform = look_transform(&app, camera);
+    assert_relative_eq!(transform.eye.y, 1.0, epsilon = 1e-4);
+    assert_relative_eq!(transform.eye.length(), 2.0f32.sqrt(), epsilon = 1e-4);
+    assert!(transform.look_direction().abs_diff_eq(-Vec3::Z, 1e-4));
+
+    run_script(
+        &mut app,
+        vec![(60, FPSControlEvent::Rotate(camera, Vec2::new(0.0, -10.0)))],
+    );
+    let look_direction = look_transform(&app, camera).look_direction();
+    assert!(look_direction.y > 0.99 && look_direction.y < 1.0);
+}
+
+#[test]
+fn test_fps_gravity_follows_the_controller_up() {
+    let mut app = harness::<FPSControlEvent, _>(FpsCameraPlugin {
+        override_input_system: true,
+    });
+    let controller = FpsCameraController {
+        up: Vec3::Z,
+        gravity: Some(FpsGravity::default()),
+        ..Default::default()
+    };
+    let camera = app
+        .world
+        .spawn()
+        .insert_bundle(FpsCameraBundle::new(
+            controller,
+            PerspectiveCameraBundle::default(),
+            Vec3::new(1.0, 2.0, 5.0),
+            Vec3::new(1.0, 3.0, 5.0),
+        ))
+        .id();
+
+    for _ in 0..300 {
+        app.update();
+    }
+    let eye = look_transform(&app, camera).eye;
+    assert!(eye.abs_diff_eq(Vec3::new(1.0, 2.0, 1.7), 1e-4));
+}
+
+#[test]
+fn test_fps_gravity_falls_back_to_the_ground_plane_where_ground_height_misses() {
+    let mut app = harness::<FPSControlEvent, _>(FpsCameraPlugin {
+        override_input_system: true,
+    });
+    // A raised platform west of the origin, and no ground data east of it.
+    app.insert_resource(GroundHeight::partial(|xz| if xz.x < 0.0 { Some(3.0) } else { None }));
+    let mut spawn = |eye: Vec3, step_lag_weight: f32| {
+        let controller = FpsCameraController {
+            gravity: Some(FpsGravity {
+                step_lag_weight,
+                ..Default::default()
+            }),
+            ..Default::default()
+        };
+        app.world
+            .spawn()
+            .insert_bundle(FpsCameraBundle::new(
+                controller,
+                PerspectiveCameraBundle::default(),
+                eye,
+                eye - Vec3::Z,
+            ))
+            .id()
+    };
+    let off_platform = spawn(Vec3::new(5.0, 10.0, 0.0), 0.0);
+    // The top of the documented range still eases up instead of leaving the eye under the platform.
+    let under_platform = spawn(Vec3::new(-5.0, 0.0, 0.0), 1.0);
+
+    for _ in 0..300 {
+        app.update();
+    }
+    let eye = look_transform(&app, off_platform).eye;
+    assert!(eye.abs_diff_eq(Vec3::new(5.0, 1.7, 0.0), 1e-4));
+    let height = look_transform(&app, under_platform).eye.y;
+    assert!(height > 0.0 && height < 4.7);
+}
+
+#[test]
+fn test_fps_head_bob_only_moves_the_view() {
+    let mut app = harness::<FPSControlEvent, _>(FpsCameraPlugin {
+        override_input_system: true,
+    });
+    let controller = FpsCameraController {
+        head_bob: Some(FpsHeadBob::default()),
+        ..Default::default()
+    };
+    let camera = app
+        .world
+        .spawn()
+        .insert_bundle(FpsCameraBundle::new(
+            controller,
+            PerspectiveCameraBundle::default(),
+            Vec3::new(0.0, 1.0, 0.0),
+            Vec3::new(0.0, 1.0, -1.0),
+        ))
+        .id();
+
+    let events = (0..30)
+        .map(|step| (step, FPSControlEvent::TranslateEye(camera, Vec3::Z)))
+        .collect();
+    run_script(&mut app, events);
+    assert_relative_eq!(look_transform(&app, camera).eye.y, 1.0, epsilon = 1e-4);
+    assert_ne!(app.world.get::<LookTransformOffset>(camera).unwrap().0, Vec3::ZERO);
+
+    // Disabling the controller mid-bob takes the bob back out of the view.
+    app.world.get_mut::<FpsCameraController>(camera).unwrap().enabled = false;
+    app.update();
+    assert_eq!(app.world.get::<LookTransformOffset>(camera).unwrap().0, Vec3::ZERO);
+}
+
+#[test]
+fn test_free_fly_rolls_about_look_direction() {
+    let mut app = harness::<FreeFlyControlEvent, _>(FreeFlyCameraPlugin {
+        override_input_system: true,
+    });
+    let (look_transform_bundle, transform) = LookTransformBundle::new(Vec3::ZERO, -Vec3::Z, 0.0);
+    let camera = app
+        .world
+        .spawn()
+        .insert_bundle(look_transform_bundle)
+        .insert(transform)
+        .insert_bundle(FreeFlyCameraBundle::new(FreeFlyCameraController::default()))
+        .id();
+
+    // A second of rolling at one radian per second.
+    let events = (0..60)
+        .map(|step| (step, FreeFlyControlEvent::Roll(camera, 1.0)))
+        .collect();
+    run_script(&mut app, events);
+    let transform = look_transform(&app, camera);
+    assert!(transform.look_direction().abs_diff_eq(-Vec3::Z, 1e-4));
+    assert_relative_eq!(transform.up.dot(Vec3::Y), 1.0f32.cos(), epsilon = 1e-4);
+}
+
+#[test]
+fn test_pancam_pans_by_projection_scale() {
+    let mut app = harness::<PanCamControlEvent, _>(PanCamPlugin {
+        override_input_system: true,
+    });
+    let (look_transform_bundle, transform) =
+        LookTransformBundle::new(Vec3::new(0.0, 0.0, 10.0), Vec3::ZERO, 0.0);
+    let camera = app
+        .world
+        .spawn()
+        .insert_bundle(look_transform_bundle)
+        .insert(transform)
+        .insert(OrthographicProjection::default())
+        .insert_bundle(PanCamBundle::new(PanCamController::default()))
+        .id();
+
+    run_script(
+        &mut app,
+        vec![
+            (0, PanCamControlEvent::Zoom(camera, 2.0)),
+            (1, PanCamControlEvent::Pan(camera, Vec2::new(10.0, 0.0))),
+        ],
+    );
+    let projection = app.world.get::<OrthographicProjection>(camera).unwrap();
+    assert_relative_eq!(projection.scale, 2.0);
+    // The world follows the cursor, so the view moves the other way.
+    let transform = look_transform(&app, camera);
+    assert_relative_eq!(transform.target.x, -20.0);
+    assert_relative_eq!(transform.eye.x, -20.0);
+}
+
+#[test]
+fn test_chase_swings_back_behind_the_chased_entity() {
+    let mut app = harness::<ChaseControlEvent, _>(ChaseCameraPlugin {
+        override_input_system: true,
+    });
+    let car = app.world.spawn().insert(GlobalTransform::identity()).id();
+    let (look_transform_bundle, transform) =
+        LookTransformBundle::new(Vec3::new(0.0, 3.0, 6.0), Vec3::ZERO, 0.0);
+    let camera = app
+        .world
+        .spawn()
+        .insert_bundle(look_transform_bundle)
+        .insert(transform)
+        .insert_bundle(ChaseCameraBundle::new(ChaseCameraController::new(car)))
+        .id();
+
+    // Glance to the side, then give the heading spring a few seconds to settle. The empty glance keeps the script running.
+    run_script(
+        &mut app,
+        vec![
+            (0, ChaseControlEvent::LookAround(camera, 1.0)),
+            (300, ChaseControlEvent::LookAround(camera, 0.0)),
+        ],
+    );
+    let transform = look_transform(&app, camera);
+    let controller = ChaseCameraController::new(car);
+    // The car faces negative Z, so behind it is positive Z.
+    assert_relative_eq!(transform.eye.x, 0.0, epsilon = 1e-3);
+    assert_relative_eq!(transform.eye.z, controller.distance, epsilon = 1e-3);
+}
+
+#[test]
+fn test_follow_keeps_target_on_the_followed_entity() {
+    let mut app = harness::<FollowControlEvent, _>(FollowCameraPlugin {
+        override_input_system: true,
+    });
+    let player = app.world.spawn().insert(GlobalTransform::from_translation(Vec3::new(3.0, 0.0, 0.0))).id();
+    let controller = FollowCameraController {
+        target_offset: Vec3::Y,
+        max_pitch: 0.5,
+        ..FollowCameraController::new(player)
+    };
+    let (look_transform_bundle, transform) =
+        LookTransformBundle::new(Vec3::new(0.0, 0.0, 5.0), Vec3::ZERO, 0.0);
+    let camera = app
+        .world
+        .spawn()
+        .insert_bundle(look_transform_bundle)
+        .insert(transform)
+        .insert_bundle(FollowCameraBundle::new(controller))
+        .id();
+
+    // Orbiting far past the pitch limit stops at it.
+    run_script(&mut app, vec![(0, FollowControlEvent::Orbit(camera, Vec2::new(0.0, 10.0)))]);
+    let transform = look_transform(&app, camera);
+    assert!(transform.target.abs_diff_eq(Vec3::new(3.0, 1.0, 0.0), 1e-5));
+    assert_relative_eq!(transform.radius(), controller.distance, epsilon = 1e-4);
+    assert_relative_eq!((transform.eye - transform.target).y / transform.radius(), 0.5f32.sin(), epsilon = 1e-4);
+
+    app.world.get_mut::<GlobalTransform>(player).unwrap().translation = Vec3::new(10.0, 0.0, 0.0);
+    app.update();
+    assert!(look_transform(&app, camera).target.abs_diff_eq(Vec3::new(10.0, 1.0, 0.0), 1e-5));
+}
+
+#[test]
+fn test_isometric_pans_over_the_ground_by_projection_scale() {
+    let mut app = harness::<IsometricControlEvent, _>(IsometricCameraPlugin {
+        override_input_system: true,
+    });
+    let (look_transform_bundle, transform) =
+        LookTransformBundle::new(Vec3::new(0.0, 10.0, 10.0), Vec3::ZERO, 0.0);
+    let camera = app
+        .world
+        .spawn()
+        .insert_bundle(look_transform_bundle)
+        .insert(transform)
+        .insert(OrthographicProjection::default())
+        .insert_bundle(IsometricCameraBundle::new(IsometricCameraController::default()))
+        .id();
+
+    // A second of panning at 1 unit per second, at a scale of 2.
+    run_script(
+        &mut app,
+        vec![
+            (0, IsometricControlEvent::Zoom(camera, 2.0)),
+            (1, IsometricControlEvent::Pan(camera, Vec2::new(60.0, 0.0))),
+        ],
+    );
+    assert_relative_eq!(app.world.get::<OrthographicProjection>(camera).unwrap().scale, 2.0);
+    let transform = look_transform(&app, camera);
+    assert_relative_eq!(transform.target.y, 0.0, epsilon = 1e-5);
+    assert_relative_eq!(transform.target.length(), 2.0, epsilon = 1e-4);
+    assert_relative_eq!(transform.radius(), IsometricCameraController::default().distance, epsilon = 1e-3);
+}
+
+#[test]
+fn test_rts_rotates_and_zooms_within_height_limits() {
+    let mut app = harness::<RtsControlEvent, _>(RtsCameraPlugin {
+        override_input_system: true,
+    });
+    let (look_transform_bundle, transform) =
+        LookTransformBundle::new(Vec3::new(0.0, 10.0, 10.0), Vec3::ZERO, 0.0);
+    let camera = app
+        .world
+        .spawn()
+        .insert_bundle(look_transform_bundle)
+        .insert(transform)
+        .insert_bundle(RtsCameraBundle::new(RtsCameraController::default()))
+        .id();
+
+    run_script(
+        &mut app,
+        vec![
+            (0, RtsControlEvent::Zoom(camera, 100.0)),
+            (1, RtsControlEvent::Rotate(camera, 30.0)),
+            (2, RtsControlEvent::Pan(camera, Vec2::new(0.0, 60.0))),
+        ],
+    );
+    let controller = RtsCameraController::default();
+    let transform = look_transform(&app, camera);
+    // The pan slid the target one unit over the ground.
+    assert_relative_eq!(transform.target.y, 0.0, epsilon = 1e-5);
+    assert_relative_eq!(transform.target.length(), 1.0, epsilon = 1e-4);
+    // The eye stopped at the highest it can be, at the controller's pitch, and turned half a radian around the target.
+    let back = transform.eye - transform.target;
+    assert_relative_eq!(back.y, controller.max_height, epsilon = 1e-3);
+    assert_relative_eq!(back.y / back.length(), controller.pitch.sin(), epsilon = 1e-4);
+    assert_relative_eq!(back.x.atan2(back.z).abs(), 0.5, epsilon = 1e-4);
+}
+
+#[test]
+fn test_fps_lock_target_aims_while_strafing() {
+    let mut app = harness::<FPSControlEvent, _>(FpsCameraPlugin {
+        override_input_system: true,
+    });
+    let target = app
+        .world
+        .spawn()
+        .insert(GlobalTransform::from_translation(Vec3::new(0.0, 1.0, -5.0)))
+        .id();
+    let camera = app
+        .world
+        .spawn()
+        .insert_bundle(FpsCameraBundle::new(
+            FpsCameraController {
+                lock_target: Some(target),
+                ..Default::default()
+            },
+            PerspectiveCameraBundle::default(),
+            Vec3::new(0.0, 1.0, 0.0),
+            Vec3::new(0.0, 1.0, -1.0),
+        ))
+        .id();
+
+    // Strafe for a second while trying to look away. The rotation is ignored.
+    let events = (0..60)
+        .flat_map(|step| {
+            vec![
+                (step, FPSControlEvent::TranslateEye(camera, Vec3::X)),
+                (step, FPSControlEvent::Rotate(camera, Vec2::new(1.0, 0.0))),
+            ]
+        })
+        .collect();
+    run_script(&mut app, events);
+    let transform = look_transform(&app, camera);
+    let to_target = (Vec3::new(0.0, 1.0, -5.0) - transform.eye).normalize();
+    assert!(transform.eye.x.abs() > 0.5);
+    assert!(transform.look_direction().abs_diff_eq(to_target, 1e-3));
+}
+
+#[test]
+fn test_rail_keeps_eye_on_rail_and_target_on_player() {
+    let mut app = harness::<RailControlEvent, _>(RailCameraPlugin {
+        override_input_system: true,
+    });
+    let player = app
+        .world
+        .spawn()
+        .insert(GlobalTransform::from_translation(Vec3::new(3.0, 0.0, 0.0)))
+        .id();
+    let rail = CameraRail::new(
+        vec![Vec3::new(-10.0, 2.0, 5.0), Vec3::new(10.0, 2.0, 5.0)],
+        PathInterpolation::Linear,
+    );
+    let (look_transform_bundle, transform) =
+        LookTransformBundle::new(Vec3::new(0.0, 2.0, 5.0), Vec3::ZERO, 0.0);
+    let camera = app
+        .world
+        .spawn()
+        .insert_bundle(look_transform_bundle)
+        .insert(transform)
+        .insert_bundle(RailCameraBundle::new(RailCameraController::new(player, rail)))
+        .id();
+
+    app.update();
+    let transform = look_transform(&app, camera);
+    assert!(transform.eye.abs_diff_eq(Vec3::new(3.0, 2.0, 5.0), 1e-4));
+    assert_eq!(transform.target, Vec3::new(3.0, 0.0, 0.0));
+}
+
+#[derive(Default)]
+struct LookTransformChanges(usize);
+
+fn count_look_transform_changes(
+    mut changes: ResMut<LookTransformChanges>,
+    cameras: Query<&LookTransform, Changed<LookTransform>>,
+) {
+    changes.0 += cameras.iter().count();
+}
+
+#[test]
+fn test_idle_cameras_dont_trigger_change_detection() {
+    let mut app = harness::<OrbitControlEvent, _>(OrbitCameraPlugin {
+        override_input_system: true,
+    });
+    app.init_resource::<LookTransformChanges>()
+        .add_system_to_stage(CoreStage::PostUpdate, count_look_transform_changes.system());
+    let camera = spawn_orbit_camera(&mut app);
+
+    // Let the spawn itself go by.
+    app.update();
+    app.world.get_resource_mut::<LookTransformChanges>().unwrap().0 = 0;
+    for _ in 0..10 {
+        app.update();
+    }
+    assert_eq!(app.world.get_resource::<LookTransformChanges>().unwrap().0, 0);
+
+    run_script(
+        &mut app,
+        vec![(11, OrbitControlEvent::Orbit(camera, Vec2::new(0.1, 0.0)))],
+    );
+    assert!(app.world.get_resource::<LookTransformChanges>().unwrap().0 > 0);
+}
+
+#[test]
+fn test_slow_steps_far_from_the_origin_add_up() {
+    let mut app = harness::<FPSControlEvent, _>(FpsCameraPlugin {
+        override_input_system: true,
+    });
+    let start = Vec3::new(1e4, 1.0, 0.0);
+    let camera = app
+        .world
+        .spawn()
+        .insert_bundle(FpsCameraBundle::new(
+            FpsCameraController::default(),
+            PerspectiveCameraBundle::default(),
+            start,
+            start - Vec3::Z,
+        ))
+        .id();
+
+    // A second of creeping along at half a unit per second, a fraction of a hundredth of a unit per step.
+    let events = (0..60)
+        .map(|step| (step, FPSControlEvent::TranslateEye(camera, 0.5 * Vec3::Z)))
+        .collect();
+    run_script(&mut app, events);
+    assert_relative_eq!(look_transform(&app, camera).eye.distance(start), 0.5, epsilon = 1e-3);
+}
+
+#[test]
+fn test_control_toggle_key_flips_enabled() {
+    let mut app = harness::<OrbitControlEvent, _>(OrbitCameraPlugin {
+        override_input_system: true,
+    });
+    let camera = spawn_orbit_camera(&mut app);
+    let split_screen_camera = spawn_orbit_camera(&mut app);
+    for &entity in [camera, split_screen_camera].iter() {
+        app.world
+            .entity_mut(entity)
+            .insert(ControlToggle::new(KeyCode::Tab));
+    }
+    app.world.get_resource_mut::<ActiveCameraEntity>().unwrap().set_active(camera);
+
+    let press_tab = |app: &mut App| {
+        let mut keyboard = app
+            .world
+            .get_resource_mut::<Events<KeyboardInput>>()
+            .unwrap();
+        for state in [ElementState::Pressed, ElementState::Released].iter() {
+            keyboard.send(KeyboardInput {
+                scan_code: 0,
+                key_code: Some(KeyCode::Tab),
+                state: *state,
+            });
+        }
+        app.update();
+        app.world.get::<OrbitCameraController>(camera).unwrap().enabled
+    };
+
+    assert!(!press_tab(&mut app));
+    // Only the active camera takes the key.
+    assert!(app.world.get::<OrbitCameraController>(split_screen_camera).unwrap().enabled);
+    app.update();
+    assert!(press_tab(&mut app));
+}
+
+#[test]
+fn test_recorded_free_fly_events_replay_outside_the_app() {
+    let mut app = harness::<FreeFlyControlEvent, _>(FreeFlyCameraPlugin {
+        override_input_system: true,
+    });
+    app.add_control_event_recorder::<FreeFlyControlEvent>();
+    let start = LookTransform::new(Vec3::new(1.0, 2.0, 3.0), Vec3::ZERO);
+    let (look_transform_bundle, transform) = LookTransformBundle::new(start.eye, start.target, 0.0);
+    let camera = app
+        .world
+        .spawn()
+        .insert_bundle(look_transform_bundle)
+        .insert(transform)
+        .insert_bundle(FreeFlyCameraBundle::new(FreeFlyCameraController::default()))
+        .id();
+
+    let events = (0..30)
+        .flat_map(|step| {
+            vec![
+                (step, FreeFlyControlEvent::Rotate(camera, Vec2::new(0.01, -0.02))),
+                (step, FreeFlyControlEvent::TranslateEye(camera, Vec3::new(0.5, 0.0, 2.0))),
+                (2 * step, FreeFlyControlEvent::Roll(camera, 0.3)),
+            ]
+        })
+        .collect();
+    run_script(&mut app, events);
+
+    let recording = app
+        .world
+        .get_resource::<ControlEventRecorder<FreeFlyControlEvent>>()
+        .unwrap()
+        .events()
+        .to_vec();
+    let mut replayed = start;
+    apply_events(camera, &mut replayed, &mut (), &recording);
+    assert!(replayed.abs_diff_eq(look_transform(&app, camera), 1e-5));
+}
+
+#[test]
+fn test_world_space_look_transform_under_a_moved_parent() {
+    let mut app = harness::<OrbitControlEvent, _>(OrbitCameraPlugin {
+        override_input_system: true,
+    });
+    let vehicle = app
+        .world
+        .spawn()
+        .insert(GlobalTransform::from_translation(Vec3::new(10.0, 0.0, 0.0)))
+        .id();
+    let (look_transform_bundle, transform) =
+        LookTransformBundle::new(Vec3::new(0.0, 0.0, 5.0), Vec3::ZERO, 0.0);
+    let camera = app
+        .world
+        .spawn()
+        .insert_bundle(look_transform_bundle)
+        .insert(transform)
+        .insert(Parent(vehicle))
+        .insert(LookTransformSpace::World)
+        .id();
+
+    app.update();
+    let local = *app.world.get::<Transform>(camera).unwrap();
+    assert!(local.translation.abs_diff_eq(Vec3::new(-10.0, 0.0, 5.0), 1e-4));
+    assert!((local.rotation * -Vec3::Z).abs_diff_eq(-Vec3::Z, 1e-4));
+
+    // In the parent's space, the same look is relative to the vehicle instead.
+    app.world.entity_mut(camera).insert(LookTransformSpace::Parent);
+    app.update();
+    let local = *app.world.get::<Transform>(camera).unwrap();
+    assert!(local.translation.abs_diff_eq(Vec3::new(0.0, 0.0, 5.0), 1e-4));
+}
+
+#[test]
+fn test_world_space_orbit_pans_along_the_view_under_a_rotated_parent() {
+    let mut app = harness::<OrbitControlEvent, _>(OrbitCameraPlugin {
+        override_input_system: true,
+    });
+    let turntable = app
+        .world
+        .spawn()
+        .insert(GlobalTransform::from_rotation(Quat::from_rotation_y(FRAC_PI_2)))
+        .id();
+    let camera = spawn_orbit_camera(&mut app);
+    app.world
+        .entity_mut(camera)
+        .insert(Parent(turntable))
+        .insert(LookTransformSpace::World);
+
+    // Screen left is world -X however the parent is turned.
+    run_script(&mut app, vec![(0, OrbitControlEvent::TranslateTarget(camera, Vec2::X))]);
+    let transform = look_transform(&app, camera);
+    assert!(transform.target.abs_diff_eq(Vec3::new(-1.0, 0.0, 0.0), 1e-4));
+    assert!((transform.eye - transform.target).abs_diff_eq(Vec3::new(0.0, 0.0, 5.0), 1e-4));
+}
+
+#[test]
+fn test_reduced_motion_stops_orbit_momentum() {
+    for &enabled in [false, true].iter() {
+        let mut app = harness::<OrbitControlEvent, _>(OrbitCameraPlugin {
+            override_input_system: true,
+        });
+        app.insert_resource(ReducedMotion::new(enabled));
+        let camera = spawn_orbit_camera(&mut app);
+        app.world.get_mut::<OrbitCameraController>(camera).unwrap().momentum_enabled = true;
+
+        run_script(&mut app, vec![(0, OrbitControlEvent::DragOrbit(camera, Vec2::new(0.1, 0.0)))]);
+        let released = look_transform(&app, camera);
+        for _ in 0..10 {
+            app.update();
+        }
+
+        // Only a camera without reduced motion keeps spinning once the drag is released.
+        let coasted = look_transform(&app, camera).eye.distance(released.eye) > 1e-3;
+        assert_eq!(coasted, !enabled);
+    }
+}
+
+#[test]
+fn test_orbit_steps_do_not_coast() {
+    let mut app = harness::<OrbitControlEvent, _>(OrbitCameraPlugin {
+        override_input_system: true,
+    });
+    let camera = spawn_orbit_camera(&mut app);
+    app.world.get_mut::<OrbitCameraController>(camera).unwrap().momentum_enabled = true;
+
+    // Like a key press of `keyboard_orbit_step`, right after a drag.
+    run_script(
+        &mut app,
+        vec![
+            (0, OrbitControlEvent::DragOrbit(camera, Vec2::new(0.1, 0.0))),
+            (1, OrbitControlEvent::Orbit(camera, Vec2::new(PI / 12.0, 0.0))),
+        ],
+    );
+    let stepped = look_transform(&app, camera);
+    for _ in 0..10 {
+        app.update();
+    }
+
+    assert!(look_transform(&app, camera).eye.abs_diff_eq(stepped.eye, 1e-5));
+}
+
+#[test]
+fn test_reduced_motion_stops_idle_rotation_and_cuts_tweens() {
+    for &enabled in [false, true].iter() {
+        let mut app = harness::<OrbitControlEvent, _>(OrbitCameraPlugin {
+            override_input_system: true,
+        });
+        app.insert_resource(ReducedMotion::new(enabled));
+        let camera = spawn_orbit_camera(&mut app);
+        {
+            let mut controller = app.world.get_mut::<OrbitCameraController>(camera).unwrap();
+            controller.idle_rotation_speed = 1.0;
+            controller.idle_delay = 0.0;
+        }
+
+        let start = look_transform(&app, camera);
+        for _ in 0..10 {
+            app.update();
+        }
+        let spun = look_transform(&app, camera).eye.distance(start.eye) > 1e-3;
+        assert_eq!(spun, !enabled);
+
+        let end = LookTransform::new(Vec3::new(0.0, 5.0, 5.0), Vec3::ZERO);
+        app.world.entity_mut(camera).insert(LookTransformTween::ease_to(end, 0.5, Easing::EaseInOut));
+        app.update();
+        let arrived = look_transform(&app, camera).eye.distance(end.eye) < 1e-4;
+        assert_eq!(arrived, enabled);
+    }
+}
+
+#[derive(Default)]
+struct PanCamChanges(usize);
+
+fn count_pancam_changes(
+    mut changes: ResMut<PanCamChanges>,
+    cameras: Query<&PanCamController, Changed<PanCamController>>,
+) {
+    changes.0 += cameras.iter().count();
+}
+
+#[test]
+fn test_rig_only_switches_the_controllers_that_change() {
+    let mut app = harness::<FPSControlEvent, _>(FpsCameraPlugin {
+        override_input_system: true,
+    });
+    app.add_plugin(OrbitCameraPlugin {
+        override_input_system: true,
+    })
+    .add_plugin(CameraRigPlugin)
+    .init_resource::<PanCamChanges>()
+    .add_system_to_stage(CoreStage::PostUpdate, count_pancam_changes.system());
+    let camera = app
+        .world
+        .spawn()
+        .insert_bundle(FpsCameraBundle::new(
+            FpsCameraController::default(),
+            PerspectiveCameraBundle::default(),
+            Vec3::new(0.0, 1.0, 5.0),
+            Vec3::new(0.0, 1.0, 0.0),
+        ))
+        .insert(OrbitCameraController::default())
+        .insert(OrbitCameraState::default())
+        .insert(PanCamController {
+            enabled: false,
+            ..Default::default()
+        })
+        .insert(CameraRig::new(RigController::Fps))
+        .id();
+
+    // Let the spawn itself go by.
+    app.update();
+    assert!(app.world.get::<FpsCameraController>(camera).unwrap().enabled);
+    assert!(!app.world.get::<OrbitCameraController>(camera).unwrap().enabled);
+    app.world.get_resource_mut::<PanCamChanges>().unwrap().0 = 0;
+
+    app.world
+        .get_resource_mut::<Events<SwitchCameraController>>()
+        .unwrap()
+        .send(SwitchCameraController {
+            camera,
+            controller: RigController::Orbit,
+            transition: None,
+        });
+    app.update();
+    assert!(!app.world.get::<FpsCameraController>(camera).unwrap().enabled);
+    assert!(app.world.get::<OrbitCameraController>(camera).unwrap().enabled);
+    // The PanCam controller was already off, so the switch leaves it alone.
+    assert_eq!(app.world.get_resource::<PanCamChanges>().unwrap().0, 0);
+}
@@ -0,0 +1,364 @@
+use crate::{
+    set_look_transform_if_changed, viewport::{pointer_in_viewport, viewport_cursor},
+    AccumulatedInput, ActiveCameraEntity, AddCameraController, BindingInput, CameraAction,
+    CameraInputMap, ControlTimestep, ControllerEnabled, ControllerViewport, InputAxisConfig,
+    InputCaptureState, InputSource, LookAngles, LookTransform, LookTransformTween,
+    ReplayableControlEvent, ToggleableController,
+};
+
+use bevy::{
+    app::prelude::*,
+    core::Time,
+    ecs::{bundle::Bundle, prelude::*},
+    input::{
+        gamepad::{GamepadAxis, GamepadButton, Gamepads},
+        prelude::*,
+    },
+    math::prelude::*,
+    render::camera::OrthographicProjection,
+    window::Windows,
+};
+use serde::{Deserialize, Serialize};
+use std::f32::consts::FRAC_PI_4;
+
+#[derive(Default)]
+pub struct IsometricCameraPlugin {
+    /// Skips the built-in input mapping, so the camera is only driven by `IsometricControlEvent`s that you send.
+    pub override_input_system: bool,
+}
+
+impl Plugin for IsometricCameraPlugin {
+    fn build(&self, app: &mut App) {
+        app.add_camera_controller::<IsometricControlEvent, _, _>(
+            map_isometric_input.system(),
+            control_isometric_camera.system(),
+            self.override_input_system,
+        )
+        .add_control_toggle::<IsometricCameraController>()
+        .add_controller_state::<IsometricCameraController, IsometricCameraState>();
+    }
+}
+
+#[derive(Bundle, Deserialize, Serialize)]
+pub struct IsometricCameraBundle {
+    controller: IsometricCameraController,
+    state: IsometricCameraState,
+}
+
+impl IsometricCameraBundle {
+    pub fn new(controller: IsometricCameraController) -> Self {
+        Self {
+            controller,
+            state: Default::default(),
+        }
+    }
+}
+
+/// An isometric camera for an `OrthographicProjection`. The eye looks down at the target from a fixed angle, the target
+/// slides over the ground plane, and zooming changes the projection's scale.
+#[derive(Clone, Copy, Debug, Deserialize, Serialize)]
+pub struct IsometricCameraController {
+    pub enabled: bool,
+    /// The devices that drive this camera. Defaults to all of them.
+    pub input_source: InputSource,
+    /// The angle of the eye above the ground plane, in radians. Defaults to the angle of a true isometric projection.
+    pub pitch: f32,
+    /// The angle of the eye around the vertical axis, in radians, where `0.0` looks down the negative Z axis.
+    pub yaw: f32,
+    /// The distance from the eye to the target. It doesn't affect the size of the view, but it should be far enough for the
+    /// eye to stay in front of the scene.
+    pub distance: f32,
+    /// Panning speed in units per second at a projection scale of `1.0`, from the keys and window edges. Panning speeds up
+    /// as the view zooms out.
+    pub pan: InputAxisConfig,
+    pub edge_pan_enabled: bool,
+    /// Panning starts when the cursor is within this many pixels of a window edge.
+    pub edge_pan_margin: f32,
+    pub mouse_wheel_zoom_sensitivity: f32,
+    pub min_scale: f32,
+    pub max_scale: f32,
+    /// If set, the target snaps to the nearest multiple of this size on the ground plane, e.g. to keep pixel art or tiles
+    /// aligned.
+    pub grid_size: Option<f32>,
+}
+
+impl Default for IsometricCameraController {
+    fn default() -> Self {
+        Self {
+            enabled: true,
+            input_source: InputSource::Any,
+            pitch: (1.0 / 2.0f32.sqrt()).atan(),
+            yaw: FRAC_PI_4,
+            distance: 100.0,
+            pan: InputAxisConfig::new(Vec2::splat(2.0)),
+            edge_pan_enabled: true,
+            edge_pan_margin: 10.0,
+            mouse_wheel_zoom_sensitivity: 0.15,
+            min_scale: 0.1,
+            max_scale: 10.0,
+            grid_size: None,
+        }
+    }
+}
+
+impl ToggleableController for IsometricCameraController {
+    fn enabled(&self) -> bool {
+        self.enabled
+    }
+
+    fn enabled_mut(&mut self) -> &mut bool {
+        &mut self.enabled
+    }
+}
+
+/// Per-camera state that the isometric controller carries between frames.
+#[derive(Clone, Copy, Debug, Default, Deserialize, Serialize)]
+pub struct IsometricCameraState {
+    /// The target before grid snapping, so that pans smaller than a grid cell still add up.
+    unsnapped_target: Option<Vec3>,
+}
+
+/// Control events for the isometric controller.
+#[derive(Clone, Copy, Debug, Deserialize, PartialEq, Serialize)]
+pub enum IsometricControlEvent {
+    /// Target velocity on the ground plane in units per second, where X is screen right and Y is screen up.
+    Pan(Entity, Vec2),
+    /// Scales the projection. Values less than `1.0` zoom in.
+    Zoom(Entity, f32),
+}
+
+impl IsometricControlEvent {
+    /// The camera entity that this event controls.
+    pub fn camera(&self) -> Entity {
+        match *self {
+            Self::Pan(camera, _) | Self::Zoom(camera, _) => camera,
+        }
+    }
+}
+
+pub fn map_isometric_input(
+    controller_enabled: Res<ControllerEnabled>,
+    active_camera: Res<ActiveCameraEntity>,
+    input_capture: Res<InputCaptureState>,
+    mut events: EventWriter<IsometricControlEvent>,
+    windows: Res<Windows>,
+    input_map: Res<CameraInputMap>,
+    keyboard: Res<Input<KeyCode>>,
+    mouse_buttons: Res<Input<MouseButton>>,
+    gamepads: Res<Gamepads>,
+    gamepad_axes: Res<Axis<GamepadAxis>>,
+    gamepad_buttons: Res<Input<GamepadButton>>,
+    mut accumulated_input: ResMut<AccumulatedInput>,
+    controllers: Query<(
+        Entity,
+        &IsometricCameraController,
+        Option<&ControllerViewport>,
+        Option<&CameraInputMap>,
+    )>,
+) {
+    // Take the input even when it's ignored, so it doesn't build up while the controllers are paused.
+    let pointer_delta = accumulated_input.take::<IsometricControlEvent>();
+    if !controller_enabled.0 {
+        return;
+    }
+
+    let input = BindingInput::new(
+        &keyboard,
+        &mouse_buttons,
+        &gamepads,
+        &gamepad_axes,
+        &gamepad_buttons,
+    );
+
+    for (entity, controller, viewport, camera_input_map) in controllers.iter() {
+        let IsometricCameraController {
+            enabled,
+            pan,
+            edge_pan_enabled,
+            edge_pan_margin,
+            mouse_wheel_zoom_sensitivity,
+            ..
+        } = *controller;
+
+        if !enabled || !active_camera.allows(entity) {
+            continue;
+        }
+
+        let pointer_free =
+            !input_capture.pointer_captured && controller.input_source.accepts_keyboard_mouse();
+        let mut pan_dir = Vec2::ZERO;
+
+        if !input_capture.keyboard_captured {
+            let input_map = camera_input_map.unwrap_or(&input_map);
+            let input = input.with_source(controller.input_source);
+            for (action, dir) in [
+                (CameraAction::MoveForward, Vec2::Y),
+                (CameraAction::MoveLeft, -Vec2::X),
+                (CameraAction::MoveBack, -Vec2::Y),
+                (CameraAction::MoveRight, Vec2::X),
+            ]
+            .iter()
+            .cloned()
+            {
+                if input_map.pressed(action, &input) {
+                    pan_dir += dir;
+                }
+            }
+        }
+
+        let (window, cursor_position) = viewport_cursor(&windows, viewport);
+        let edge_pan_window = if edge_pan_enabled && pointer_free {
+            // Don't pan while the app is in the background and the cursor happens to rest near an edge.
+            window.filter(|w| w.is_focused()).zip(cursor_position)
+        } else {
+            None
+        };
+        if let Some((window, cursor)) = edge_pan_window {
+            // The cursor origin is at the bottom-left of the window.
+            let (min, max) = viewport
+                .map(|v| v.bounds(window))
+                .unwrap_or_else(|| (Vec2::ZERO, Vec2::new(window.width(), window.height())));
+            if cursor.x < min.x + edge_pan_margin {
+                pan_dir.x -= 1.0;
+            }
+            if cursor.x > max.x - edge_pan_margin {
+                pan_dir.x += 1.0;
+            }
+            if cursor.y < min.y + edge_pan_margin {
+                pan_dir.y -= 1.0;
+            }
+            if cursor.y > max.y - edge_pan_margin {
+                pan_dir.y += 1.0;
+            }
+        }
+
+        if pan_dir != Vec2::ZERO {
+            events.send(IsometricControlEvent::Pan(entity, pan.apply(pan_dir)));
+        }
+
+        if pointer_free && pointer_in_viewport(&windows, viewport) {
+            let scalar = (-pointer_delta.wheel() * mouse_wheel_zoom_sensitivity).exp();
+            if scalar != 1.0 {
+                events.send(IsometricControlEvent::Zoom(entity, scalar));
+            }
+        }
+    }
+}
+
+pub fn control_isometric_camera(
+    time: Res<Time>,
+    timestep: Res<ControlTimestep>,
+    mut events: EventReader<IsometricControlEvent>,
+    mut cameras: Query<
+        (
+            Entity,
+            &IsometricCameraController,
+            &mut IsometricCameraState,
+            &mut LookTransform,
+            &mut OrthographicProjection,
+        ),
+        Without<LookTransformTween>,
+    >,
+) {
+    // Every camera only consumes the events that were routed to it.
+    let events: Vec<&IsometricControlEvent> = events.iter().collect();
+    let dt = timestep.delta_seconds(&time);
+
+    for (entity, controller, mut state, mut look_transform, mut projection) in cameras.iter_mut() {
+        if !controller.enabled {
+            continue;
+        }
+
+        let mut transform = *look_transform;
+        let mut context = (*controller, *state, projection.scale);
+        let camera_events: Vec<&IsometricControlEvent> =
+            events.iter().filter(|e| e.camera() == entity).cloned().collect();
+        IsometricControlEvent::apply_step(&mut transform, &mut context, &camera_events, dt);
+
+        let (_, new_state, scale) = context;
+        *state = new_state;
+        if scale != projection.scale {
+            projection.scale = scale;
+        }
+        set_look_transform_if_changed(&mut look_transform, transform);
+    }
+}
+
+impl ReplayableControlEvent for IsometricControlEvent {
+    /// The controller's settings, its state, and the scale of the camera's `OrthographicProjection`, which zooming changes.
+    type Context = (IsometricCameraController, IsometricCameraState, f32);
+
+    fn camera(&self) -> Entity {
+        IsometricControlEvent::camera(self)
+    }
+
+    fn apply_step(
+        transform: &mut LookTransform,
+        (controller, state, scale): &mut Self::Context,
+        events: &[&Self],
+        delta_seconds: f32,
+    ) {
+        let mut look_angles = LookAngles::default();
+        look_angles.set_yaw(controller.yaw);
+        look_angles.set_pitch(controller.pitch);
+        let back = look_angles.unit_vector();
+
+        // Start from the unsnapped target, unless something else has moved the camera since the last frame.
+        let mut target = state
+            .unsnapped_target
+            .filter(|&t| snap_to_grid(t, controller.grid_size) == transform.target)
+            .unwrap_or(transform.target);
+
+        for event in events {
+            match event {
+                IsometricControlEvent::Pan(_, velocity) => {
+                    let forward = (-back * Vec3::new(1.0, 0.0, 1.0)).normalize();
+                    let right = forward.cross(Vec3::Y);
+                    target += delta_seconds * *scale * (velocity.x * right + velocity.y * forward);
+                }
+                IsometricControlEvent::Zoom(_, scalar) => {
+                    *scale = (*scale * scalar)
+                        .min(controller.max_scale)
+                        .max(controller.min_scale);
+                }
+            }
+        }
+
+        state.unsnapped_target = Some(target);
+        transform.target = snap_to_grid(target, controller.grid_size);
+        transform.eye = transform.target + controller.distance * back;
+    }
+}
+
+/// Rounds the X and Z coordinates of `point` to the nearest multiple of `grid_size`.
+fn snap_to_grid(point: Vec3, grid_size: Option<f32>) -> Vec3 {
+    match grid_size {
+        Some(size) if size > 0.0 => Vec3::new(
+            (point.x / size).round() * size,
+            point.y,
+            (point.z / size).round() * size,
+        ),
+        _ => point,
+    }
+}
+
+// ████████╗███████╗███████╗████████╗
+// ╚══██╔══╝██╔════╝██╔════╝╚══██╔══╝
+//    ██║   █████╗  ███████╗   ██║
+//    ██║   ██╔══╝  ╚════██║   ██║
+//    ██║   ███████╗███████║   ██║
+//    ╚═╝   ╚══════╝╚══════╝   ╚═╝
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_snap_to_grid_rounds_ground_coordinates() {
+        let point = Vec3::new(1.2, 0.7, -2.6);
+
+        assert_eq!(snap_to_grid(point, None), point);
+        assert_eq!(snap_to_grid(point, Some(0.5)), Vec3::new(1.0, 0.7, -2.5));
+        assert_eq!(snap_to_grid(point, Some(2.0)), Vec3::new(2.0, 0.7, -2.0));
+    }
+}
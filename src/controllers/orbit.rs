@@ -1,150 +1,1494 @@
-use crate::{LookAngles, LookTransform, LookTransformBundle, Smoother, ControllerEnabled};
+use crate::{
+    bounding_sphere, input_filter::InputFilterState,
+    look_angles::{any_orthogonal, normalize_or, y_up_rotation}, look_space_transform,
+    orthographic_framing_scale, perspective_framing_distance, pointer_ray::cursor_ray,
+    set_look_transform_if_changed, smooth,
+    viewport::{pointer_in_viewport, viewport_cursor, viewport_size, DragState},
+    world_units_per_pixel, AccumulatedInput, ActiveCameraEntity, AddCameraController, BindingInput,
+    CameraAction, CameraInputMap, CameraRaycaster, ConsumedDrags, ControlTimestep,
+    ControllerEnabled, ControllerViewport, InputAxisConfig, InputCaptureState, InputFilter,
+    InputSource, LookAngles, LookTransform, LookTransformBundle, LookTransformSpace,
+    LookTransformTween, PointerArbitration, PointerDelta, PointerNormalization, ReducedMotion,
+    ResponseCurve, Smoother, ToggleableController, TouchGesture,
+};
 
 use bevy::{
     app::prelude::*,
-    ecs::{bundle::Bundle, prelude::*},
+    core::Time,
+    ecs::{bundle::Bundle, prelude::*, reflect::ReflectComponent},
     input::{
-        mouse::{MouseMotion, MouseWheel},
+        gamepad::{GamepadAxis, GamepadButton, Gamepads},
         prelude::*,
+        touch::Touches,
     },
     math::prelude::*,
-    render::prelude::*,
-    transform::components::Transform,
+    reflect::Reflect,
+    render::{
+        camera::{Camera, OrthographicProjection},
+        prelude::*,
+    },
+    transform::components::{GlobalTransform, Transform},
+    window::Windows,
 };
 use serde::{Deserialize, Serialize};
+use std::f32::consts::{FRAC_PI_2, PI};
 
-pub struct OrbitCameraPlugin;
+#[derive(Default)]
+pub struct OrbitCameraPlugin {
+    /// Skips the built-in input mapping, so the camera is only driven by `OrbitControlEvent`s that you send.
+    pub override_input_system: bool,
+}
 
 impl Plugin for OrbitCameraPlugin {
     fn build(&self, app: &mut App) {
-        app.add_system(map_orbit_input.system())
-            .add_system(control_orbit_camera.system())
-            .add_event::<OrbitControlEvent>();
+        app.add_camera_controller::<OrbitControlEvent, _, _>(
+            map_orbit_input.system(),
+            control_orbit_camera.system(),
+            self.override_input_system,
+        )
+        .add_control_toggle::<OrbitCameraController>()
+        .add_controller_state::<OrbitCameraController, OrbitCameraState>()
+        .register_type::<OrbitCameraController>()
+        .register_type::<OrbitPanMode>()
+        .register_type::<OrbitMode>()
+        .register_type::<OrbitZoomMode>()
+        .register_type::<OrbitElasticPitch>();
     }
 }
 
-
+/// Everything a camera needs to be driven by the orbit controller, so it can be spawned in one go.
 #[derive(Bundle)]
 pub struct OrbitCameraBundle {
     controller: OrbitCameraController,
+    state: OrbitCameraState,
+    #[bundle]
+    look_transform: LookTransformBundle,
+    #[bundle]
+    perspective: PerspectiveCameraBundle,
 }
 
 impl OrbitCameraBundle {
-    pub fn new( controller: OrbitCameraController) -> Self {
-        Self { controller }
+    /// Looks from `eye` to `target`, with the controller's `up` direction and `smoothing_weight`. The `perspective`
+    /// bundle's `Transform` is overwritten to match, so the camera doesn't jump on its first frame.
+    pub fn new(
+        controller: OrbitCameraController,
+        mut perspective: PerspectiveCameraBundle,
+        eye: Vec3,
+        target: Vec3,
+    ) -> Self {
+        let (look_transform, transform) =
+            LookTransformBundle::look_at(eye, target, controller.up, controller.smoothing_weight);
+        perspective.transform = transform;
+
+        Self {
+            controller,
+            state: OrbitCameraState::default(),
+            look_transform,
+            perspective,
+        }
+    }
+}
+
+/// Per-camera state that the orbit controller carries between frames.
+#[derive(Clone, Copy, Debug, Default, Deserialize, Serialize)]
+pub struct OrbitCameraState {
+    /// The radius that a smoothed zoom is easing toward, if one is in progress.
+    zoom_target_radius: Option<f32>,
+    /// Seconds since the camera last received a control event.
+    idle_seconds: f32,
+    /// The rotation of the last orbit, in yaw and pitch radians per second, which keeps spinning the camera after a drag.
+    orbit_velocity: Vec2,
+}
+
+impl OrbitCameraState {
+    /// Stops any zoom or momentum in progress, so they don't carry the camera away from where it jumped to.
+    pub(crate) fn reset_motion(&mut self) {
+        self.zoom_target_radius = None;
+        self.orbit_velocity = Vec2::ZERO;
     }
 }
 
 /// A 3rd person camera that orbits around the target.
-#[derive(Clone, Copy, Debug, Deserialize, Serialize)]
+///
+/// If the camera entity has an `OrthographicProjection`, zooming scales the projection instead of the orbit radius, since
+/// moving the eye has no visible effect with an orthographic camera.
+#[derive(Clone, Copy, Debug, Deserialize, Reflect, Serialize)]
+#[reflect(Component)]
 pub struct OrbitCameraController {
     pub enabled: bool,
-    pub mouse_rotate_sensitivity: Vec2,
-    pub mouse_translate_sensitivity: Vec2,
+    /// Lets control events orbit the camera. `SetView`, `LookFrom`, and idle rotation still work while this is off.
+    pub rotate_enabled: bool,
+    /// Lets control events move the target, by panning or picking a pivot. While this is off, zooming toward a point zooms
+    /// straight in instead, e.g. for a product viewer that always orbits the product.
+    pub pan_enabled: bool,
+    /// Lets control events zoom the camera. `Frame`, `ZoomBy`, and `ZoomTo` still zoom while this is off, so buttons in
+    /// the app's UI keep working.
+    pub zoom_enabled: bool,
+    /// The devices that drive this camera. Defaults to all of them.
+    pub input_source: InputSource,
+    /// The world's up direction, e.g. `Vec3::Z` for a Z-up scene imported from Blender or a CAD tool. Turntable orbiting
+    /// yaws around it, ground plane panning stays perpendicular to it, and standard views are rotated to match it.
+    pub up: Vec3,
+    pub orbit_mode: OrbitMode,
+    /// The lowest allowed pitch of the eye relative to the target, in radians. Only applies to `OrbitMode::Turntable`.
+    pub min_pitch: f32,
+    /// The highest allowed pitch of the eye relative to the target, in radians. Only applies to `OrbitMode::Turntable`.
+    pub max_pitch: f32,
+    /// Lets the pitch stretch past `min_pitch` and `max_pitch` while rotating, and springs it back afterwards, instead of
+    /// stopping hard at the limits.
+    pub elastic_pitch: Option<OrbitElasticPitch>,
+    /// The lag weight of the `Smoother` that `OrbitCameraBundle::new` adds, between `0.0` and `1.0`, where higher is
+    /// smoother.
+    pub smoothing_weight: f32,
+    /// Zooms toward the point under the cursor instead of straight along the look direction.
+    pub zoom_to_cursor: bool,
+    /// When a `CameraAction::Orbit` binding is pressed, moves the target to the point under the cursor so the camera orbits around it.
+    /// Requires the `CameraRaycaster` resource to find the point.
+    pub orbit_around_cursor: bool,
+    /// Scales mouse motion into yaw (X) and pitch (Y) in radians.
+    pub mouse_rotate: InputAxisConfig,
+    /// Shapes mouse rotation before `mouse_rotate` is applied, e.g. to turn faster on quick flicks.
+    pub rotate_response_curve: ResponseCurve,
+    /// Scales mouse motion into world units of panning. Only its inversion is used when `pixel_perfect_pan` is on and the
+    /// camera has a `Camera` component to take the projection from. Like the other panning sensitivities, it's multiplied
+    /// by the scale of an `OrthographicProjection`, so panning feels the same at every zoom level.
+    pub mouse_translate: InputAxisConfig,
+    /// Pans by exactly as far as the cursor moved at the target's distance, so the grabbed point stays under the cursor.
+    /// The scale comes from the camera's projection, the height of its `ControllerViewport`, and the current radius. Exact
+    /// in `OrbitPanMode::CameraPlane`.
+    pub pixel_perfect_pan: bool,
+    /// How far in logical pixels the cursor has to move with a button held before dragging rotates, pans, or zooms the
+    /// camera, so clicks on the scene, e.g. for picking, don't nudge it. `ConsumedDrags` tells which presses became drags.
+    /// Defaults to `3.0`; `0.0` starts every drag on the press.
+    pub drag_threshold: f32,
+    /// Zoom per unit of vertical mouse motion while dragging with a `CameraAction::Zoom` binding, where dragging down zooms
+    /// out.
+    pub mouse_drag_zoom_sensitivity: f32,
+    /// Filters jitter out of mouse motion before it's mapped. Off by default.
+    pub mouse_filter: InputFilter,
+    pub pan_mode: OrbitPanMode,
+    /// Pans the target with the `Pan` direction bindings of the `CameraInputMap`, the arrow keys by default, for when
+    /// there's no middle mouse button.
+    pub keyboard_pan_enabled: bool,
+    /// Keyboard panning speed in units per second.
+    pub keyboard_translate_sensitivity: f32,
+    /// Rotates by `keyboard_orbit_step` per press of the `Orbit` direction bindings, and snaps to standard views with the
+    /// view bindings. They're on the numpad by default, like Blender.
+    pub keyboard_orbit_enabled: bool,
+    /// How far each keyboard orbit press rotates the camera, in radians. Presses don't feed `momentum_enabled`.
+    pub keyboard_orbit_step: f32,
+    /// Zooms in and out while the `ZoomIn` and `ZoomOut` bindings are held.
+    pub keyboard_zoom_enabled: bool,
+    /// Keyboard zoom speed. Holding a zoom key for a second scales the radius by `e` to the power of this.
+    pub keyboard_zoom_sensitivity: f32,
+    /// Pans the target while the cursor is near the edge of the window, like an RTS camera.
+    pub edge_pan_enabled: bool,
+    /// Edge panning starts when the cursor is within this many pixels of a window edge.
+    pub edge_pan_margin: f32,
+    /// Edge panning speed in units per second.
+    pub edge_pan_sensitivity: f32,
+    /// Zoom per line scrolled, for mouse wheels that scroll in lines.
     pub mouse_wheel_zoom_sensitivity: f32,
+    /// Zoom per pixel scrolled, for touchpads and other devices that scroll in pixels.
+    pub mouse_wheel_pixel_zoom_sensitivity: f32,
+    /// Whether zooming moves by a fraction of the radius or by a fixed distance.
+    pub zoom_mode: OrbitZoomMode,
+    /// Eases the radius toward the zoomed radius over time instead of jumping to it, like `smooth`. Between `0.0` and `1.0`,
+    /// where higher is smoother and `0.0` disables zoom smoothing.
+    pub zoom_lag_weight: f32,
+    /// Yaws the camera with horizontal scrolling from tilt wheels and touchpads, and pans the target sideways instead of
+    /// zooming while a `CameraAction::WheelPan` binding, Shift by default, is held. Pixel scrolling is converted to lines at
+    /// the ratio of the two wheel zoom sensitivities.
+    pub horizontal_wheel_enabled: bool,
+    /// Yaw in radians per line scrolled horizontally.
+    pub wheel_rotate_sensitivity: f32,
+    /// Panning in world units per line scrolled with a `CameraAction::WheelPan` binding held.
+    pub wheel_translate_sensitivity: f32,
+    /// Applies zoom to the camera's `Smoother` right away, so zooming stays snappy while orbiting and panning are smoothed.
+    pub zoom_bypasses_smoothing: bool,
+    /// The closest the eye can zoom to the target.
+    pub min_radius: f32,
+    /// The farthest the eye can zoom from the target.
+    pub max_radius: f32,
+    pub min_ortho_scale: f32,
+    pub max_ortho_scale: f32,
+    /// Scales one-finger drags, which orbit the camera. Its `invert_x` also flips twisting.
+    pub touch_rotate: InputAxisConfig,
+    /// Scales two-finger drags, which pan the target.
+    pub touch_translate: InputAxisConfig,
+    /// An exponent applied to the pinch scale, which zooms the camera.
+    pub touch_zoom_sensitivity: f32,
+    /// Yaw in radians per radian that two fingers twist. Negative values turn the other way, and `0.0` disables twisting.
+    pub touch_twist_sensitivity: f32,
+    /// Scales the `LookLeft`, `LookRight`, `LookUp`, and `LookDown` bindings, e.g. the right stick, into yaw (X) and
+    /// pitch (Y) in radians per second, which orbit like a mouse drag in that direction.
+    pub gamepad_rotate: InputAxisConfig,
+    /// Spins the camera around the target at this many radians per second once it's idle, like a product viewer. `0.0`
+    /// disables idle rotation.
+    pub idle_rotation_speed: f32,
+    /// How many seconds without any control events before the camera is considered idle.
+    pub idle_delay: f32,
+    /// Keeps the camera spinning after an orbit drag, sent as `DragOrbit` events, is released, slowing down by
+    /// `momentum_damping`.
+    pub momentum_enabled: bool,
+    /// Between `0.0` and `1.0`, the fraction of the spin speed lost every 1/60 s once the drag is released. Lower spins for
+    /// longer.
+    pub momentum_damping: f32,
 }
 
 impl Default for OrbitCameraController {
     fn default() -> Self {
         Self {
-            mouse_rotate_sensitivity: Vec2::splat(0.006),
-            mouse_translate_sensitivity: Vec2::splat(0.008),
+            zoom_to_cursor: false,
+            orbit_around_cursor: false,
+            mouse_rotate: InputAxisConfig::new(Vec2::splat(0.006)),
+            rotate_response_curve: ResponseCurve::Linear,
+            mouse_translate: InputAxisConfig::new(Vec2::splat(0.008)),
+            pixel_perfect_pan: true,
+            drag_threshold: 3.0,
+            mouse_filter: InputFilter::None,
+            mouse_drag_zoom_sensitivity: 0.01,
+            pan_mode: OrbitPanMode::CameraPlane,
+            keyboard_pan_enabled: false,
+            keyboard_translate_sensitivity: 5.0,
+            keyboard_orbit_enabled: false,
+            keyboard_orbit_step: PI / 12.0,
+            keyboard_zoom_enabled: false,
+            keyboard_zoom_sensitivity: 1.5,
+            edge_pan_enabled: false,
+            edge_pan_margin: 10.0,
+            edge_pan_sensitivity: 5.0,
             mouse_wheel_zoom_sensitivity: 0.15,
+            mouse_wheel_pixel_zoom_sensitivity: 0.005,
+            zoom_mode: OrbitZoomMode::Proportional,
+            zoom_lag_weight: 0.0,
+            horizontal_wheel_enabled: false,
+            wheel_rotate_sensitivity: 0.1,
+            wheel_translate_sensitivity: 0.5,
+            zoom_bypasses_smoothing: false,
+            min_radius: 0.1,
+            max_radius: 1000.0,
+            min_ortho_scale: 0.01,
+            max_ortho_scale: 100.0,
+            touch_rotate: InputAxisConfig::new(Vec2::splat(0.006)),
+            touch_translate: InputAxisConfig::new(Vec2::splat(0.008)),
+            touch_zoom_sensitivity: 1.0,
+            touch_twist_sensitivity: 1.0,
+            gamepad_rotate: InputAxisConfig::new(Vec2::splat(3.0)).with_deadzone(0.15),
+            idle_rotation_speed: 0.0,
+            idle_delay: 5.0,
+            momentum_enabled: false,
+            momentum_damping: 0.1,
             enabled: true,
+            rotate_enabled: true,
+            pan_enabled: true,
+            zoom_enabled: true,
+            input_source: InputSource::Any,
+            up: Vec3::Y,
+            orbit_mode: OrbitMode::Turntable,
+            min_pitch: -FRAC_PI_2,
+            max_pitch: FRAC_PI_2,
+            elastic_pitch: None,
+            smoothing_weight: 0.8,
+        }
+    }
+}
+
+impl ToggleableController for OrbitCameraController {
+    fn enabled(&self) -> bool {
+        self.enabled
+    }
+
+    fn enabled_mut(&mut self) -> &mut bool {
+        &mut self.enabled
+    }
+}
+
+impl OrbitCameraController {
+    /// Whether the per-action switches let `event` through. Events that aren't tied to an action, like `SetView`, always
+    /// pass.
+    pub fn allows(&self, event: &OrbitControlEvent) -> bool {
+        match event {
+            OrbitControlEvent::Orbit(..) | OrbitControlEvent::DragOrbit(..) => self.rotate_enabled,
+            OrbitControlEvent::TranslateTarget(..) | OrbitControlEvent::Pivot(..) => self.pan_enabled,
+            OrbitControlEvent::Zoom(..) | OrbitControlEvent::ZoomAt(..) => self.zoom_enabled,
+            OrbitControlEvent::LookFrom(..)
+            | OrbitControlEvent::SetView(..)
+            | OrbitControlEvent::ZoomBy(..)
+            | OrbitControlEvent::ZoomTo(..)
+            | OrbitControlEvent::Frame(..) => true,
+        }
+    }
+}
+
+/// The drags in progress for each orbit camera, kept by `map_orbit_input`. Each action is tracked separately, since they
+/// can share a button.
+#[derive(Default)]
+pub struct OrbitDrags {
+    orbit: DragState,
+    pan: DragState,
+    zoom: DragState,
+}
+
+/// Canonical directions to look at the target from, e.g. for the faces of a view cube widget.
+#[derive(Clone, Copy, Debug, Deserialize, Eq, PartialEq, Serialize)]
+pub enum StandardView {
+    /// Looks from the +Z side of the target.
+    Front,
+    /// Looks from the -Z side of the target.
+    Back,
+    /// Looks from the -X side of the target.
+    Left,
+    /// Looks from the +X side of the target.
+    Right,
+    /// Looks down from above the target.
+    Top,
+    /// Looks up from below the target.
+    Bottom,
+    /// Looks from the front, right, and top at once.
+    Isometric,
+}
+
+impl StandardView {
+    /// The unit direction from the target to the eye in a Y-up world.
+    pub fn eye_direction(self) -> Vec3 {
+        match self {
+            Self::Front => Vec3::Z,
+            Self::Back => -Vec3::Z,
+            Self::Left => -Vec3::X,
+            Self::Right => Vec3::X,
+            Self::Top => Vec3::Y,
+            Self::Bottom => -Vec3::Y,
+            Self::Isometric => Vec3::ONE.normalize(),
+        }
+    }
+
+    /// The unit direction from the target to the eye in a world where `up` is up. For a Z-up world, the front view looks
+    /// from the -Y side, like in most CAD tools.
+    pub fn eye_direction_with_up(self, up: Vec3) -> Vec3 {
+        y_up_rotation(up) * self.eye_direction()
+    }
+}
+
+/// How orbiting rotates the camera around the target.
+#[derive(Clone, Copy, Debug, Deserialize, Eq, PartialEq, Reflect, Serialize)]
+#[reflect_value(PartialEq, Serialize, Deserialize)]
+pub enum OrbitMode {
+    /// Yaws around the world Y axis and pitches up and down, so the horizon always stays level.
+    Turntable,
+    /// Rotates freely around the camera's own up and right axes, so the horizon can tilt, like the trackball in many modeling
+    /// tools.
+    Trackball,
+}
+
+/// Rubber-banding at the pitch limits of a turntable orbit, like in many map apps. While orbit events keep coming, the
+/// pitch can be pushed past a limit, with more resistance the further it goes. Once they stop, it springs back to the
+/// limit, and the `Smoother` eases the camera along as usual. The pitch still can't reach straight up or down.
+#[derive(Clone, Copy, Debug, Deserialize, PartialEq, Reflect, Serialize)]
+pub struct OrbitElasticPitch {
+    /// The furthest the pitch can be stretched past a limit, in radians.
+    pub overshoot: f32,
+    /// Between `0.0` and `1.0`, the fraction of the stretch that's left after a sixtieth of a second once the rotation
+    /// stops, like `smooth`. Higher values spring back more slowly.
+    pub rebound_weight: f32,
+}
+
+impl Default for OrbitElasticPitch {
+    fn default() -> Self {
+        Self {
+            overshoot: 0.2,
+            rebound_weight: 0.8,
         }
     }
 }
 
+impl OrbitElasticPitch {
+    /// Adds `delta` to `pitch`. Past the limits, the pitch moves as if along a rubber band, closing in on `overshoot`
+    /// without reaching it. Unless the camera is `rotating`, the stretch then shrinks back toward the limit over `dt`
+    /// seconds.
+    fn step(
+        &self,
+        pitch: f32,
+        delta: f32,
+        min_pitch: f32,
+        max_pitch: f32,
+        rotating: bool,
+        dt: f32,
+    ) -> f32 {
+        let overshoot = self.overshoot.max(1e-5);
+        let excess = |pitch: f32| pitch - pitch.min(max_pitch).max(min_pitch);
+
+        // Undo the band's resistance to find where the pitch would be without it, move that, and apply the band again.
+        let stretch = (excess(pitch) / overshoot).min(0.999).max(-0.999);
+        let unstretched = pitch - excess(pitch) + overshoot * stretch.atanh();
+        let moved = unstretched + delta;
+        let pitch = moved - excess(moved) + overshoot * (excess(moved) / overshoot).tanh();
+        if rotating {
+            return pitch;
+        }
+
+        let stretch = smooth(excess(pitch), 0.0, self.rebound_weight, dt);
+        // Snap once we're close enough, so the pitch doesn't ease forever.
+        let stretch = if stretch.abs() > 1e-4 { stretch } else { 0.0 };
+
+        pitch.min(max_pitch).max(min_pitch) + stretch
+    }
+}
+
+/// The plane that panning moves the target in.
+#[derive(Clone, Copy, Debug, Deserialize, Eq, PartialEq, Reflect, Serialize)]
+#[reflect_value(PartialEq, Serialize, Deserialize)]
+pub enum OrbitPanMode {
+    /// Moves the target along the camera's right and up directions.
+    CameraPlane,
+    /// Moves the target along the XZ plane, so it keeps its height. Panning up moves the target away from the camera, which
+    /// suits top-down scenes.
+    GroundPlane,
+}
+
+/// How zooming changes the radius, or the scale of an orthographic projection.
+#[derive(Clone, Copy, Debug, Deserialize, PartialEq, Reflect, Serialize)]
+#[reflect_value(PartialEq, Serialize, Deserialize)]
+pub enum OrbitZoomMode {
+    /// Scales the radius, so each step moves a fraction of the distance to the target, like Blender and three.js. Zooming
+    /// slows down close to the target and speeds up far away from it.
+    Proportional,
+    /// Moves the same distance with each step, whatever the radius: as far as a proportional step would move at
+    /// `reference_radius`.
+    Linear { reference_radius: f32 },
+}
+
+impl OrbitZoomMode {
+    /// Converts a zoom scalar from a control event into the scalar to apply at `radius`.
+    fn scalar(self, scalar: f32, radius: f32) -> f32 {
+        match self {
+            Self::Proportional => scalar,
+            // Never zoom through the target, however large the step.
+            Self::Linear { reference_radius } if radius > 0.0 => {
+                (1.0 + (scalar - 1.0) * reference_radius / radius).max(1e-3)
+            }
+            Self::Linear { .. } => scalar,
+        }
+    }
+}
+
+/// Control events for the orbit controller.
+#[derive(Clone, Copy, Debug, Deserialize, PartialEq, Serialize)]
 pub enum OrbitControlEvent {
-    Orbit(Vec2),
-    TranslateTarget(Vec2),
-    Zoom(f32),
+    /// Orbits the eye around the target by yaw (X) and pitch (Y) in radians, matching the direction of mouse motion. Stops
+    /// any momentum, so a step like a key press doesn't keep the camera spinning.
+    Orbit(Entity, Vec2),
+    /// Orbits like `Orbit`, for a mouse or touch drag. With `momentum_enabled`, the camera keeps spinning at the speed of
+    /// the drag once these events stop.
+    DragOrbit(Entity, Vec2),
+    /// Moves the target to this point, keeping the eye in place.
+    Pivot(Entity, Vec3),
+    /// Moves the eye around the target so it's in this direction from the target, keeping the radius.
+    LookFrom(Entity, Vec3),
+    /// Moves the eye to a standard view of the target, keeping the radius. When the flag is set, the move eases through the
+    /// camera's `Smoother`; otherwise the camera jumps straight there.
+    SetView(Entity, StandardView, bool),
+    /// Moves the target and eye in the camera plane by this many world units, times the scale of an
+    /// `OrthographicProjection`, so the same event pans as far on screen at every zoom level. Positive X moves toward screen
+    /// left and positive Y toward screen up, so dragging with the mouse feels like grabbing the scene.
+    TranslateTarget(Entity, Vec2),
+    /// Scales the radius, or the scale of an `OrthographicProjection`. Values less than `1.0` zoom in. The controller's
+    /// `zoom_mode` decides whether this is a fraction of the current radius or a fixed distance.
+    Zoom(Entity, f32),
+    /// Like `Zoom`, but also moves the target so the given world-space point stays fixed on screen.
+    ZoomAt(Entity, f32, Vec3),
+    /// Scales the radius, or the scale of an `OrthographicProjection`, by exactly this factor, whatever the `zoom_mode`.
+    /// Factors of zero or less are ignored.
+    ZoomBy(Entity, f32),
+    /// Zooms to this radius, or this scale of an `OrthographicProjection`, within the controller's limits, e.g. for a zoom
+    /// slider or a button that resets the zoom.
+    ZoomTo(Entity, f32),
+    /// Moves the target to the center of the box between these min and max corners, and zooms so the whole box is in view.
+    /// The look direction is kept, and the move goes through any `Smoother` on the camera, so this can be used to focus on a
+    /// selected object.
+    Frame(Entity, Vec3, Vec3),
+}
+
+impl OrbitControlEvent {
+    /// The camera entity that this event controls.
+    pub fn camera(&self) -> Entity {
+        match *self {
+            Self::Orbit(camera, _)
+            | Self::DragOrbit(camera, _)
+            | Self::Pivot(camera, _)
+            | Self::LookFrom(camera, _)
+            | Self::SetView(camera, _, _)
+            | Self::TranslateTarget(camera, _)
+            | Self::Zoom(camera, _)
+            | Self::ZoomAt(camera, _, _)
+            | Self::ZoomBy(camera, _)
+            | Self::ZoomTo(camera, _)
+            | Self::Frame(camera, _, _) => camera,
+        }
+    }
 }
 
 pub fn map_orbit_input(
+    controller_enabled: Res<ControllerEnabled>,
+    active_camera: Res<ActiveCameraEntity>,
+    input_capture: Res<InputCaptureState>,
+    pointer_normalization: Res<PointerNormalization>,
+    pointer_arbitration: Res<PointerArbitration>,
+    mut consumed_drags: ResMut<ConsumedDrags>,
+    time: Res<Time>,
+    timestep: Res<ControlTimestep>,
     mut events: EventWriter<OrbitControlEvent>,
-    mut mouse_wheel_reader: EventReader<MouseWheel>,
-    mut mouse_motion_events: EventReader<MouseMotion>,
+    mut accumulated_input: ResMut<AccumulatedInput>,
+    input_map: Res<CameraInputMap>,
     mouse_buttons: Res<Input<MouseButton>>,
-    _keyboard: Res<Input<KeyCode>>,
-    controllers: Query<&OrbitCameraController, With<Transform>>,
+    keyboard: Res<Input<KeyCode>>,
+    gamepads: Res<Gamepads>,
+    gamepad_axes: Res<Axis<GamepadAxis>>,
+    gamepad_buttons: Res<Input<GamepadButton>>,
+    touches: Res<Touches>,
+    windows: Res<Windows>,
+    raycaster: Option<Res<CameraRaycaster>>,
+    mut drags: Local<OrbitDrags>,
+    mut filters: Local<InputFilterState>,
+    controllers: Query<(
+        Entity,
+        &OrbitCameraController,
+        &LookTransform,
+        &Transform,
+        Option<&GlobalTransform>,
+        Option<&LookTransformSpace>,
+        Option<&Camera>,
+        Option<&ControllerViewport>,
+        Option<&OrthographicProjection>,
+        Option<&CameraInputMap>,
+    )>,
 ) {
-    // Can only control one camera at a time.
-    let controller = if let Some(controller) = controllers.iter().next() {
-        controller
-    } else {
-        return;
-    };
-    let OrbitCameraController {
-        enabled,
-        mouse_rotate_sensitivity,
-        mouse_translate_sensitivity,
-        mouse_wheel_zoom_sensitivity,
-        ..
-    } = *controller;
-
-    if !enabled {
+    // Take the input even when it's ignored, so it doesn't build up while the controllers are paused.
+    let pointer_delta = accumulated_input.take::<OrbitControlEvent>();
+    let presses = accumulated_input.take_presses::<OrbitControlEvent>();
+    if !controller_enabled.0 {
         return;
     }
 
-    let mut cursor_delta = Vec2::ZERO;
-    for event in mouse_motion_events.iter() {
-        cursor_delta += event.delta;
-    }
+    // Touches often come with emulated mouse input, so only one of them drives each gesture.
+    let mouse_free = pointer_arbitration.accepts_mouse();
+    let pointer_delta = if mouse_free {
+        pointer_delta
+    } else {
+        PointerDelta::default()
+    };
+    let cursor_delta = pointer_delta.motion;
+
+    let touch_gesture = if pointer_arbitration.accepts_touch() {
+        TouchGesture::from_touches(&touches)
+    } else {
+        TouchGesture::None
+    };
+    let input = BindingInput::new(
+        &keyboard,
+        &mouse_buttons,
+        &gamepads,
+        &gamepad_axes,
+        &gamepad_buttons,
+    );
 
-    events.send(OrbitControlEvent::Orbit(mouse_rotate_sensitivity * cursor_delta));
+    // Forget the filter history and drags of cameras that were despawned or lost their controller.
+    let kept = |camera| controllers.get(camera).is_ok();
+    filters.retain(kept);
+    drags.orbit.retain(kept);
+    drags.pan.retain(kept);
+    drags.zoom.retain(kept);
 
-    if mouse_buttons.pressed(MouseButton::Middle) {
-        events.send(OrbitControlEvent::TranslateTarget(
-            mouse_translate_sensitivity * cursor_delta,
-        ));
-    }
+    for (
+        entity,
+        controller,
+        look_transform,
+        transform,
+        global_transform,
+        space,
+        camera,
+        viewport,
+        ortho_projection,
+        camera_input_map,
+    ) in controllers.iter()
+    {
+        let OrbitCameraController {
+            enabled,
+            zoom_to_cursor,
+            orbit_around_cursor,
+            keyboard_pan_enabled,
+            keyboard_translate_sensitivity,
+            keyboard_orbit_enabled,
+            keyboard_orbit_step,
+            keyboard_zoom_enabled,
+            keyboard_zoom_sensitivity,
+            zoom_enabled,
+            edge_pan_enabled,
+            edge_pan_margin,
+            edge_pan_sensitivity,
+            mouse_wheel_zoom_sensitivity,
+            mouse_wheel_pixel_zoom_sensitivity,
+            horizontal_wheel_enabled,
+            wheel_rotate_sensitivity,
+            wheel_translate_sensitivity,
+            touch_zoom_sensitivity,
+            touch_twist_sensitivity,
+            ..
+        } = *controller;
+
+        if !enabled || !active_camera.allows(entity) {
+            continue;
+        }
+
+        let input_map = camera_input_map.unwrap_or(&input_map);
+        let input = input.with_source(controller.input_source);
+
+        // Drop the events of disabled actions here too, so they don't count as input that stops idle rotation.
+        let mut send = |event: OrbitControlEvent| {
+            if controller.allows(&event) {
+                events.send(event);
+            }
+        };
+
+        let (window, cursor_position) = viewport_cursor(&windows, viewport);
+        // Cursor rays are cast from where the camera is on screen, in the same space as the points they pick.
+        let transform = look_space_transform(transform, global_transform, space);
+        // The control system scales panning by an orthographic projection's scale, which pixel-perfect panning already
+        // takes from the projection.
+        let pan_scale = ortho_projection.map_or(1.0, |projection| projection.scale);
+        let pointer_free = !input_capture.pointer_captured
+            && controller.input_source.accepts_keyboard_mouse()
+            && pointer_in_viewport(&windows, viewport);
+        let touch_gesture = if pointer_free {
+            touch_gesture
+        } else {
+            TouchGesture::None
+        };
+        let cursor_delta = pointer_normalization.normalize(cursor_delta, window);
+        let cursor_delta = filters.apply(
+            entity,
+            controller.mouse_filter,
+            cursor_delta,
+            timestep.delta_seconds(&time),
+        );
+        let drag_can_start = pointer_free && mouse_free && cursor_position.is_some();
+        let mut drag = |drags: &mut DragState, action| {
+            let dragging = drags.update_bindings(
+                entity,
+                input_map.bindings(action),
+                &input,
+                window,
+                drag_can_start,
+                controller.drag_threshold,
+            );
+            if let Some(button) = dragging {
+                consumed_drags.consume(button);
+            }
+            dragging
+        };
+        let rotating = drag(&mut drags.orbit, CameraAction::Orbit).is_some();
+        let translating = drag(&mut drags.pan, CameraAction::Pan);
+        let drag_zooming = drag(&mut drags.zoom, CameraAction::Zoom).is_some();
+
+        let orbit_pressed = input_map.just_pressed(CameraAction::Orbit, &presses, &input);
+        if pointer_free && mouse_free && orbit_around_cursor && orbit_pressed {
+            if let (Some(window), Some(cursor), Some(camera), Some(raycaster)) =
+                (window, cursor_position, camera, raycaster.as_ref())
+            {
+                let window_size = Vec2::new(window.width(), window.height());
+                let (origin, direction) = cursor_ray(camera, &transform, window_size, cursor);
+                if let Some(distance) =
+                    raycaster.cast_ray(origin, direction, controller.max_radius)
+                {
+                    send(OrbitControlEvent::Pivot(entity, origin + distance * direction));
+                }
+            }
+        }
+
+        if rotating {
+            let dt = timestep.delta_seconds(&time);
+            let rotation = controller.rotate_response_curve.apply(cursor_delta, dt);
+            send(OrbitControlEvent::DragOrbit(
+                entity,
+                controller.mouse_rotate.apply(rotation),
+            ));
+        }
+
+        if let Some(button) = translating {
+            let delta = match (controller.pixel_perfect_pan, camera, window) {
+                (true, Some(camera), Some(window)) => {
+                    let pixel_delta = drags.pan.cursor_delta(entity, button);
+                    let units_per_pixel = world_units_per_pixel(
+                        &camera.projection_matrix,
+                        look_transform.radius(),
+                        viewport_size(window, viewport).y,
+                    );
+                    // The cursor position has Y pointing up the screen, unlike mouse motion.
+                    let pixel_delta = Vec2::new(pixel_delta.x, -pixel_delta.y);
+                    units_per_pixel / pan_scale * controller.mouse_translate.invert(pixel_delta)
+                }
+                _ => controller.mouse_translate.apply(cursor_delta),
+            };
+            if delta != Vec2::ZERO {
+                send(OrbitControlEvent::TranslateTarget(entity, delta));
+            }
+        }
 
-    let mut scalar = 1.0;
-    for event in mouse_wheel_reader.iter() {
-        scalar *= 1.0 + -event.y * mouse_wheel_zoom_sensitivity;
+        if keyboard_pan_enabled && !input_capture.keyboard_captured {
+            // Positive X pans the target toward screen left and positive Y toward screen up.
+            let mut key_delta = Vec2::ZERO;
+            for (action, dir) in [
+                (CameraAction::PanLeft, Vec2::X),
+                (CameraAction::PanRight, -Vec2::X),
+                (CameraAction::PanUp, Vec2::Y),
+                (CameraAction::PanDown, -Vec2::Y),
+            ]
+            .iter()
+            .cloned()
+            {
+                if input_map.pressed(action, &input) {
+                    key_delta += dir;
+                }
+            }
+            if key_delta != Vec2::ZERO {
+                send(OrbitControlEvent::TranslateTarget(
+                    entity,
+                    keyboard_translate_sensitivity * timestep.delta_seconds(&time) * key_delta,
+                ));
+            }
+        }
+
+        if !input_capture.keyboard_captured {
+            let rotation = controller.gamepad_rotate.apply(Vec2::new(
+                input_map.axis(CameraAction::LookRight, CameraAction::LookLeft, &input),
+                input_map.axis(CameraAction::LookDown, CameraAction::LookUp, &input),
+            ));
+            if rotation != Vec2::ZERO {
+                send(OrbitControlEvent::Orbit(
+                    entity,
+                    timestep.delta_seconds(&time) * rotation,
+                ));
+            }
+        }
+
+        if keyboard_orbit_enabled && !input_capture.keyboard_captured {
+            // Each step rotates like a mouse drag in that direction, but as an `Orbit`, so it stops any momentum rather than
+            // setting the camera spinning at a whole step per frame.
+            for (action, dir) in [
+                (CameraAction::OrbitLeft, -Vec2::X),
+                (CameraAction::OrbitRight, Vec2::X),
+                (CameraAction::OrbitUp, -Vec2::Y),
+                (CameraAction::OrbitDown, Vec2::Y),
+            ]
+            .iter()
+            .cloned()
+            {
+                if input_map.just_pressed(action, &presses, &input) {
+                    send(OrbitControlEvent::Orbit(entity, keyboard_orbit_step * dir));
+                }
+            }
+
+            for (action, view) in [
+                (CameraAction::FrontView, StandardView::Front),
+                (CameraAction::RightView, StandardView::Right),
+                (CameraAction::TopView, StandardView::Top),
+            ]
+            .iter()
+            .cloned()
+            {
+                if input_map.just_pressed(action, &presses, &input) {
+                    send(OrbitControlEvent::SetView(entity, view, true));
+                }
+            }
+        }
+
+        // `ZoomBy` gets through while zooming is off, so the keys check the switch themselves.
+        if keyboard_zoom_enabled && zoom_enabled && !input_capture.keyboard_captured {
+            // Sticks and triggers zoom as fast as they're pushed.
+            let key_zoom = input_map.axis(CameraAction::ZoomOut, CameraAction::ZoomIn, &input);
+            if key_zoom != 0.0 {
+                let dt = timestep.delta_seconds(&time);
+                send(OrbitControlEvent::ZoomBy(
+                    entity,
+                    (key_zoom * keyboard_zoom_sensitivity * dt).exp(),
+                ));
+            }
+        }
+
+        if edge_pan_enabled && pointer_free {
+            // Don't pan while the app is in the background and the cursor happens to rest near an edge.
+            if let (Some(window), Some(cursor)) = (window.filter(|w| w.is_focused()), cursor_position) {
+                let (min, max) = viewport
+                    .map(|v| v.bounds(window))
+                    .unwrap_or_else(|| (Vec2::ZERO, Vec2::new(window.width(), window.height())));
+                // The cursor origin is at the bottom-left. Positive X pans the target toward screen left and positive Y
+                // toward screen up.
+                let mut edge_delta = Vec2::ZERO;
+                if cursor.x < min.x + edge_pan_margin {
+                    edge_delta.x += 1.0;
+                }
+                if cursor.x > max.x - edge_pan_margin {
+                    edge_delta.x -= 1.0;
+                }
+                if cursor.y < min.y + edge_pan_margin {
+                    edge_delta.y -= 1.0;
+                }
+                if cursor.y > max.y - edge_pan_margin {
+                    edge_delta.y += 1.0;
+                }
+                if edge_delta != Vec2::ZERO {
+                    send(OrbitControlEvent::TranslateTarget(
+                        entity,
+                        edge_pan_sensitivity * timestep.delta_seconds(&time) * edge_delta,
+                    ));
+                }
+            }
+        }
+
+        let wheel_pans = horizontal_wheel_enabled
+            && !input_capture.keyboard_captured
+            && input_map.pressed(CameraAction::WheelPan, &input);
+
+        let mut scalar = 1.0;
+        if pointer_free && !wheel_pans {
+            scalar *= (-pointer_delta.wheel_lines * mouse_wheel_zoom_sensitivity).exp();
+            scalar *= (-pointer_delta.wheel_pixels * mouse_wheel_pixel_zoom_sensitivity).exp();
+        }
+        if drag_zooming {
+            scalar *= (cursor_delta.y * controller.mouse_drag_zoom_sensitivity).exp();
+        }
+
+        if pointer_free && horizontal_wheel_enabled {
+            let lines_per_pixel = if mouse_wheel_zoom_sensitivity != 0.0 {
+                mouse_wheel_pixel_zoom_sensitivity / mouse_wheel_zoom_sensitivity
+            } else {
+                0.0
+            };
+            let horizontal_lines = pointer_delta.horizontal_wheel_lines
+                + lines_per_pixel * pointer_delta.horizontal_wheel_pixels;
+            if horizontal_lines != 0.0 {
+                send(OrbitControlEvent::Orbit(
+                    entity,
+                    Vec2::new(wheel_rotate_sensitivity * horizontal_lines, 0.0),
+                ));
+            }
+
+            // Positive X pans the target toward screen left, like scrolling a page up moves it down.
+            let vertical_lines =
+                pointer_delta.wheel_lines + lines_per_pixel * pointer_delta.wheel_pixels;
+            if wheel_pans && vertical_lines != 0.0 {
+                send(OrbitControlEvent::TranslateTarget(
+                    entity,
+                    Vec2::new(wheel_translate_sensitivity * vertical_lines, 0.0),
+                ));
+            }
+        }
+
+        match touch_gesture {
+            TouchGesture::None => {}
+            TouchGesture::Drag(delta) => {
+                let delta = pointer_normalization.normalize(delta, window);
+                send(OrbitControlEvent::DragOrbit(
+                    entity,
+                    controller.touch_rotate.apply(delta),
+                ));
+            }
+            TouchGesture::TwoFingerDrag {
+                delta,
+                pinch_scale,
+                twist,
+            } => {
+                let delta = pointer_normalization.normalize(delta, window);
+                send(OrbitControlEvent::TranslateTarget(
+                    entity,
+                    controller.touch_translate.apply(delta),
+                ));
+                scalar *= pinch_scale.powf(touch_zoom_sensitivity);
+
+                // Twisting only turns the yaw, so it doesn't fight with pinching and panning.
+                if twist != 0.0 && touch_twist_sensitivity != 0.0 {
+                    let yaw = if controller.touch_rotate.invert_x {
+                        -twist
+                    } else {
+                        twist
+                    };
+                    send(OrbitControlEvent::Orbit(
+                        entity,
+                        Vec2::new(touch_twist_sensitivity * yaw, 0.0),
+                    ));
+                }
+            }
+        }
+
+        if scalar == 1.0 {
+            continue;
+        }
+
+        // Zoom around the point under the cursor that's at the same depth as the target.
+        let zoom_point = if zoom_to_cursor {
+            if let (Some(window), Some(cursor), Some(camera)) = (window, cursor_position, camera) {
+                let window_size = Vec2::new(window.width(), window.height());
+                let (origin, direction) = cursor_ray(camera, &transform, window_size, cursor);
+                let look_direction = look_transform.look_direction();
+                let depth = (look_transform.target - origin).dot(look_direction);
+                Some(origin + (depth / direction.dot(look_direction)) * direction)
+            } else {
+                None
+            }
+        } else {
+            None
+        };
+
+        if let Some(point) = zoom_point {
+            send(OrbitControlEvent::ZoomAt(entity, scalar, point));
+        } else {
+            send(OrbitControlEvent::Zoom(entity, scalar));
+        }
     }
-    events.send(OrbitControlEvent::Zoom(scalar));
 }
 
 pub fn control_orbit_camera(
+    time: Res<Time>,
+    timestep: Res<ControlTimestep>,
+    reduced_motion: Res<ReducedMotion>,
     mut events: EventReader<OrbitControlEvent>,
-    mut cameras: Query<(&OrbitCameraController, &mut LookTransform, &Transform, With<Transform>)>,
+    mut cameras: Query<
+        (
+            Entity,
+            &OrbitCameraController,
+            &mut OrbitCameraState,
+            &mut LookTransform,
+            Option<&Camera>,
+            Option<&mut OrthographicProjection>,
+            Option<&mut Smoother>,
+        ),
+        (With<Transform>, Without<LookTransformTween>),
+    >,
 ) {
-    // Can only control one camera at a time.
-    let (controller, mut transform, scene_transform) =
-        if let Some((controller, transform, scene_transform, _)) = cameras.iter_mut().next() {
-            (controller, transform, scene_transform)
-        } else {
-            return;
+    // Every camera only consumes the events that were routed to it.
+    let events: Vec<&OrbitControlEvent> = events.iter().collect();
+    let dt = timestep.delta_seconds(&time);
+
+    for (
+        entity,
+        controller,
+        mut state,
+        mut look_transform,
+        camera,
+        ortho_projection,
+        smoother,
+    ) in cameras.iter_mut()
+    {
+        if !controller.enabled {
+            continue;
+        }
+
+        // Without rubber-banding, the pitch stops at its limits instead of springing back to them.
+        let controller = &OrbitCameraController {
+            elastic_pitch: controller.elastic_pitch.filter(|_| reduced_motion.allows_inertia()),
+            ..*controller
         };
 
-    if controller.enabled {
-        let mut look_angles = LookAngles::from_vector(-transform.look_direction());
+        let mut transform = *look_transform;
+        // Pan in the space of the `LookTransform`, which isn't the `Transform`'s under a parent with
+        // `LookTransformSpace::World`.
+        let pan_view = Transform::from(transform);
+
+        // The direction from the target to the eye, and how far to rotate it this frame.
+        let mut eye_direction = -transform.look_direction();
+        let mut rotation = Vec2::ZERO;
+        let mut radius = transform.radius();
         let mut radius_scalar = 1.0;
+        // The product of this step's relative zooms, which framing keeps on top of the framing scale.
+        let mut relative_zoom = 1.0;
+        let mut snap = false;
+
+        // The quantity that zooming scales, and its limits.
+        let (zoom_scale, min_zoom_scale, max_zoom_scale) = match ortho_projection.as_ref() {
+            Some(projection) => (
+                projection.scale,
+                controller.min_ortho_scale,
+                controller.max_ortho_scale,
+            ),
+            None => (
+                state.zoom_target_radius.unwrap_or(radius),
+                controller.min_radius,
+                controller.max_radius,
+            ),
+        };
+        // An orthographic view covers more of the world as it zooms out, so panning speeds up to match and feels the same
+        // on screen at every zoom level.
+        let pan_scale = ortho_projection.as_ref().map_or(1.0, |projection| projection.scale);
+
+        state.idle_seconds += dt;
+        let mut orbit_delta = None;
+        let mut drag_delta = None;
+
+        for event in events
+            .iter()
+            .filter(|e| e.camera() == entity && controller.allows(e))
+        {
+            // Any input hands control back to the user.
+            state.idle_seconds = 0.0;
 
-        for event in events.iter() {
             match event {
-                OrbitControlEvent::Orbit(delta) => {
-                    look_angles.add_yaw(-delta.x);
-                    look_angles.add_pitch(delta.y);
+                OrbitControlEvent::Orbit(_, delta) => {
+                    rotation += *delta;
+                    *orbit_delta.get_or_insert(Vec2::ZERO) += *delta;
+                }
+                OrbitControlEvent::DragOrbit(_, delta) => {
+                    rotation += *delta;
+                    *orbit_delta.get_or_insert(Vec2::ZERO) += *delta;
+                    *drag_delta.get_or_insert(Vec2::ZERO) += *delta;
+                }
+                OrbitControlEvent::LookFrom(_, direction) => {
+                    eye_direction = direction.normalize();
+                    rotation = Vec2::ZERO;
+                    state.orbit_velocity = Vec2::ZERO;
+                    if controller.orbit_mode == OrbitMode::Trackball {
+                        transform.up = level_up(eye_direction, controller.up);
+                    }
+                }
+                OrbitControlEvent::SetView(_, view, smooth) => {
+                    eye_direction = view.eye_direction_with_up(controller.up);
+                    rotation = Vec2::ZERO;
+                    state.orbit_velocity = Vec2::ZERO;
+                    if controller.orbit_mode == OrbitMode::Trackball {
+                        transform.up = level_up(eye_direction, controller.up);
+                    }
+                    snap = !smooth;
+                }
+                OrbitControlEvent::Pivot(_, point) => {
+                    transform.target = *point;
+                    eye_direction = -transform.look_direction();
+                    radius = transform.radius();
+                    state.zoom_target_radius = None;
                 }
-                OrbitControlEvent::TranslateTarget(delta) => {
-                    let right_dir = scene_transform.rotation * -Vec3::X;
-                    let up_dir = scene_transform.rotation * Vec3::Y;
-                    transform.target += delta.x * right_dir + delta.y * up_dir;
+                OrbitControlEvent::TranslateTarget(_, delta) => {
+                    let right_dir = pan_view.rotation * -Vec3::X;
+                    let up_dir = pan_view.rotation * Vec3::Y;
+                    let (right_dir, up_dir) = match controller.pan_mode {
+                        OrbitPanMode::CameraPlane => (right_dir, up_dir),
+                        OrbitPanMode::GroundPlane => {
+                            // Looking straight down, the forward direction has no horizontal part, but the up
+                            // direction does.
+                            let world_up = controller.up.normalize();
+                            let forward_dir = pan_view.rotation * -Vec3::Z;
+                            let forward_dir = if forward_dir.dot(world_up).abs() < 0.99 {
+                                forward_dir
+                            } else {
+                                up_dir
+                            };
+                            let horizontal = |v: Vec3| v - v.dot(world_up) * world_up;
+                            // Rolled onto its side, the right direction has no horizontal part either.
+                            let forward_dir =
+                                normalize_or(horizontal(forward_dir), any_orthogonal(world_up));
+                            let right_dir =
+                                normalize_or(horizontal(right_dir), world_up.cross(forward_dir));
+                            (right_dir, forward_dir)
+                        }
+                    };
+                    transform.target += pan_scale * (delta.x * right_dir + delta.y * up_dir);
                 }
-                OrbitControlEvent::Zoom(scalar) => {
+                OrbitControlEvent::Zoom(_, scalar) => {
+                    let scalar = controller
+                        .zoom_mode
+                        .scalar(*scalar, radius_scalar * zoom_scale);
                     radius_scalar *= scalar;
+                    relative_zoom *= scalar;
                 }
+                OrbitControlEvent::ZoomAt(_, scalar, point) => {
+                    // Only zoom as far as the limits allow, so the point doesn't drift once we hit them.
+                    let current = radius_scalar * zoom_scale;
+                    let scalar = controller.zoom_mode.scalar(*scalar, current);
+                    let scalar = (current * scalar).min(max_zoom_scale).max(min_zoom_scale) / current;
+                    if controller.pan_enabled {
+                        transform.target = *point + scalar * (transform.target - *point);
+                    }
+                    radius_scalar *= scalar;
+                    relative_zoom *= scalar;
+                }
+                OrbitControlEvent::ZoomBy(_, factor) => {
+                    // A factor of zero or less would flip the eye through the target, so it's ignored.
+                    if *factor > 0.0 {
+                        radius_scalar *= factor;
+                        relative_zoom *= factor;
+                    }
+                }
+                OrbitControlEvent::ZoomTo(_, zoom) => {
+                    // The limits are applied along with those of the other zoom events.
+                    radius_scalar = zoom / zoom_scale;
+                }
+                OrbitControlEvent::Frame(_, min, max) => {
+                    let (center, sphere_radius) = bounding_sphere(*min, *max);
+                    let framing_zoom_scale = match (ortho_projection.as_ref(), camera) {
+                        (Some(projection), _) => {
+                            Some(orthographic_framing_scale(sphere_radius, projection))
+                        }
+                        (None, Some(camera)) => Some(perspective_framing_distance(
+                            sphere_radius,
+                            &camera.projection_matrix,
+                        )),
+                        (None, None) => None,
+                    };
+
+                    transform.target = center;
+                    if let Some(framing_zoom_scale) = framing_zoom_scale {
+                        // Zoom to the framing scale through the same path as the other zoom events, so it respects the
+                        // limits and `zoom_lag_weight`, and keep this step's relative zooms on top of it.
+                        radius_scalar = relative_zoom * framing_zoom_scale / zoom_scale;
+                    }
+                }
+            }
+        }
+
+        if let Some(delta) = drag_delta {
+            // Remember how fast the camera was spinning, so it can coast once the drag is released.
+            if dt > 0.0 {
+                state.orbit_velocity = delta / dt;
+            }
+        } else if orbit_delta.is_some() {
+            state.orbit_velocity = Vec2::ZERO;
+        } else if controller.momentum_enabled && reduced_motion.allows_inertia() {
+            let lag_weight = 1.0 - controller.momentum_damping;
+            state.orbit_velocity = smooth(state.orbit_velocity, Vec2::ZERO, lag_weight, dt);
+            if state.orbit_velocity.length_squared() < 1e-6 {
+                state.orbit_velocity = Vec2::ZERO;
             }
+            rotation += dt * state.orbit_velocity;
+        } else {
+            state.orbit_velocity = Vec2::ZERO;
+        }
+
+        if controller.idle_rotation_speed != 0.0
+            && state.idle_seconds > controller.idle_delay
+            && reduced_motion.allows_inertia()
+        {
+            rotation.x -= dt * controller.idle_rotation_speed;
         }
 
-        look_angles.assert_not_looking_up();
+        let rotating = orbit_delta.is_some();
+        // Rebuilding the eye from its direction rounds a little differently every step, which would count as a change to
+        // the `LookTransform`, so a camera that isn't turning keeps its direction as it is.
+        let turning = rotation != Vec2::ZERO
+            || eye_direction != -look_transform.look_direction()
+            || springing_back(controller, eye_direction);
+        let (eye_direction, up) = if turning {
+            orbit_rotate(controller, eye_direction, transform.up, rotation, rotating, dt)
+        } else {
+            (eye_direction, transform.up)
+        };
+        transform.up = up;
 
-        transform.eye =
-            transform.target + radius_scalar * transform.radius() * look_angles.unit_vector();
+        if let Some(mut projection) = ortho_projection {
+            // Orthographic cameras zoom by scaling the view volume, so the radius is left alone.
+            let new_scale = (radius_scalar * projection.scale)
+                .min(controller.max_ortho_scale)
+                .max(controller.min_ortho_scale);
+            if new_scale != projection.scale {
+                projection.scale = new_scale;
+            }
+            radius_scalar = 1.0;
+        }
+
+        let zoom_target_radius = (radius_scalar * state.zoom_target_radius.unwrap_or(radius))
+            .min(controller.max_radius)
+            .max(controller.min_radius);
+        let eased_radius = smooth(
+            radius,
+            zoom_target_radius,
+            reduced_motion.lag_weight(controller.zoom_lag_weight),
+            dt,
+        );
+
+        // Snap once we're close enough, so the zoom doesn't ease forever.
+        let new_radius = if (eased_radius - zoom_target_radius).abs() > 1e-3 * zoom_target_radius {
+            state.zoom_target_radius = Some(zoom_target_radius);
+            eased_radius
+        } else {
+            state.zoom_target_radius = None;
+            zoom_target_radius
+        };
+
+        if controller.zoom_bypasses_smoothing && new_radius != radius {
+            if let Some(smoother) = smoother.as_mut() {
+                smoother.scale_radius(new_radius / radius);
+            }
+        }
+
+        transform.eye = if turning || new_radius != radius {
+            transform.target + new_radius * eye_direction
+        } else {
+            // Likewise, an eye that neither turns nor zooms only follows the target.
+            transform.eye + (transform.target - look_transform.target)
+        };
+        set_look_transform_if_changed(&mut look_transform, transform);
+
+        if snap {
+            if let Some(mut smoother) = smoother {
+                smoother.reset();
+            }
+        }
+    }
+}
+
+/// Rotates the direction from the target to the eye by `rotation` in the controller's `orbit_mode`. Returns the new
+/// direction and the camera's new up vector. Only the direction changes, so the radius is kept. `rotating` is whether
+/// orbit events came in this step, which holds any `elastic_pitch` stretch in place. Otherwise the stretch springs back over
+/// `dt` seconds.
+fn orbit_rotate(
+    controller: &OrbitCameraController,
+    eye_direction: Vec3,
+    up: Vec3,
+    rotation: Vec2,
+    rotating: bool,
+    dt: f32,
+) -> (Vec3, Vec3) {
+    match (controller.orbit_mode, controller.elastic_pitch) {
+        (OrbitMode::Turntable, None) => (
+            turntable_rotate(
+                eye_direction,
+                controller.up,
+                rotation,
+                controller.min_pitch,
+                controller.max_pitch,
+            ),
+            controller.up,
+        ),
+        (OrbitMode::Turntable, Some(elastic_pitch)) => {
+            let mut look_angles = LookAngles::from_vector_with_up(eye_direction, controller.up);
+            look_angles.add_yaw(-rotation.x);
+            look_angles.set_pitch(elastic_pitch.step(
+                look_angles.get_pitch(),
+                rotation.y,
+                controller.min_pitch,
+                controller.max_pitch,
+                rotating,
+                dt,
+            ));
+
+            (look_angles.unit_vector_with_up(controller.up), controller.up)
+        }
+        (OrbitMode::Trackball, _) => trackball_rotate(eye_direction, up, rotation),
+    }
+}
+
+/// Whether an `elastic_pitch` stretch has the pitch past its limits, so it springs back even without any rotation.
+fn springing_back(controller: &OrbitCameraController, eye_direction: Vec3) -> bool {
+    if controller.orbit_mode != OrbitMode::Turntable || controller.elastic_pitch.is_none() {
+        return false;
+    }
+    let pitch = LookAngles::from_vector_with_up(eye_direction, controller.up).get_pitch();
+    // `OrbitElasticPitch::step` snaps to the limit within this, so a pitch at the limit isn't sprung back forever.
+    (pitch - pitch.min(controller.max_pitch).max(controller.min_pitch)).abs() > 1e-4
+}
+
+/// An up vector that keeps the horizon level in a world where `up` is up, when the eye is in `eye_direction` from the
+/// target. Looking straight down or up, screen up points away from the front view from above and toward it from below.
+fn level_up(eye_direction: Vec3, up: Vec3) -> Vec3 {
+    let up = up.normalize();
+    let elevation = eye_direction.dot(up);
+    if elevation.abs() < 0.999 {
+        up
     } else {
-        events.iter(); // Drop the events.
+        -elevation.signum() * StandardView::Front.eye_direction_with_up(up)
+    }
+}
+
+/// Rotates the direction from the target to the eye by yaw around the world's `up` vector and pitch toward it, keeping the
+/// pitch within `[min_pitch, max_pitch]`.
+fn turntable_rotate(
+    eye_direction: Vec3,
+    up: Vec3,
+    rotation: Vec2,
+    min_pitch: f32,
+    max_pitch: f32,
+) -> Vec3 {
+    let mut look_angles = LookAngles::from_vector_with_up(eye_direction, up);
+    look_angles.add_yaw(-rotation.x);
+    look_angles.add_pitch(rotation.y);
+    look_angles.clamp_pitch(min_pitch, max_pitch);
+
+    look_angles.unit_vector_with_up(up)
+}
+
+/// Rotates the direction from the target to the eye, and the up vector with it, around the camera's own axes. Positive X
+/// yaws the eye to the left around the up vector and positive Y pitches it up, like `LookAngles` does for a level camera.
+fn trackball_rotate(eye_direction: Vec3, up: Vec3, rotation: Vec2) -> (Vec3, Vec3) {
+    let right = normalize_or(up.cross(eye_direction), any_orthogonal(eye_direction));
+    let up = eye_direction.cross(right);
+
+    let yaw = Quat::from_axis_angle(up, -rotation.x);
+    let pitch = Quat::from_axis_angle(right, -rotation.y);
+    let q = yaw * pitch;
+
+    ((q * eye_direction).normalize(), (q * up).normalize())
+}
+
+// ████████╗███████╗███████╗████████╗
+// ╚══██╔══╝██╔════╝██╔════╝╚══██╔══╝
+//    ██║   █████╗  ███████╗   ██║
+//    ██║   ██╔══╝  ╚════██║   ██║
+//    ██║   ███████╗███████║   ██║
+//    ╚═╝   ╚══════╝╚══════╝   ╚═╝
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    use approx::assert_relative_eq;
+
+    #[test]
+    fn test_trackball_matches_turntable_when_level() {
+        let rotation = Vec2::new(0.3, 0.2);
+        let (eye_direction, _) = trackball_rotate(Vec3::Z, Vec3::Y, Vec2::new(rotation.x, 0.0));
+
+        let mut look_angles = LookAngles::from_vector(Vec3::Z);
+        look_angles.add_yaw(-rotation.x);
+        assert!(eye_direction.abs_diff_eq(look_angles.unit_vector(), 1e-5));
+
+        let (eye_direction, _) = trackball_rotate(Vec3::Z, Vec3::Y, Vec2::new(0.0, rotation.y));
+        let mut look_angles = LookAngles::from_vector(Vec3::Z);
+        look_angles.add_pitch(rotation.y);
+        assert!(eye_direction.abs_diff_eq(look_angles.unit_vector(), 1e-5));
+    }
+
+    #[test]
+    fn test_disabled_actions_drop_their_events() {
+        let camera = Entity::new(0);
+        let controller = OrbitCameraController {
+            pan_enabled: false,
+            ..Default::default()
+        };
+
+        assert!(controller.allows(&OrbitControlEvent::Orbit(camera, Vec2::X)));
+        assert!(controller.allows(&OrbitControlEvent::Zoom(camera, 0.5)));
+        assert!(!controller.allows(&OrbitControlEvent::TranslateTarget(camera, Vec2::X)));
+        assert!(!controller.allows(&OrbitControlEvent::Pivot(camera, Vec3::ZERO)));
+        assert!(controller.allows(&OrbitControlEvent::SetView(camera, StandardView::Top, true)));
+
+        let controller = OrbitCameraController {
+            zoom_enabled: false,
+            ..Default::default()
+        };
+        assert!(!controller.allows(&OrbitControlEvent::Zoom(camera, 0.5)));
+        assert!(controller.allows(&OrbitControlEvent::ZoomTo(camera, 5.0)));
+    }
+
+    #[test]
+    fn test_trackball_can_pitch_over_the_top() {
+        let (eye_direction, up) = trackball_rotate(Vec3::Z, Vec3::Y, Vec2::new(0.0, PI));
+
+        assert!(eye_direction.abs_diff_eq(-Vec3::Z, 1e-5));
+        assert!(up.abs_diff_eq(-Vec3::Y, 1e-5));
+    }
+
+    #[test]
+    fn test_trackball_survives_up_along_the_eye_direction() {
+        let (eye_direction, up) = trackball_rotate(Vec3::Y, Vec3::Y, Vec2::new(0.3, 0.2));
+
+        assert!(eye_direction.is_finite() && up.is_finite());
+        assert_relative_eq!(eye_direction.length(), 1.0, epsilon = 1e-5);
+    }
+
+    #[test]
+    fn test_turntable_yaws_around_world_up() {
+        let eye_direction = Vec3::new(0.0, -1.0, 1.0).normalize();
+
+        let yawed = turntable_rotate(eye_direction, Vec3::Z, Vec2::new(0.5, 0.0), -PI, PI);
+        assert_relative_eq!(yawed.z, eye_direction.z, epsilon = 1e-5);
+        assert_relative_eq!(
+            yawed.truncate().angle_between(eye_direction.truncate()).abs(),
+            0.5,
+            epsilon = 1e-5
+        );
+
+        let pitched = turntable_rotate(eye_direction, Vec3::Z, Vec2::new(0.0, 0.2), -PI, PI);
+        assert!(pitched.z > eye_direction.z);
+    }
+
+    #[test]
+    fn test_elastic_pitch_stretches_and_springs_back() {
+        let elastic_pitch = OrbitElasticPitch::default();
+        let (min_pitch, max_pitch) = (-0.5, 0.5);
+        let dt = 1.0 / 60.0;
+
+        // Within the limits, the pitch moves freely.
+        assert_relative_eq!(elastic_pitch.step(0.1, 0.2, min_pitch, max_pitch, true, dt), 0.3);
+
+        // Pushing past a limit gets harder the further it goes, and never reaches the overshoot.
+        let mut pitch = 0.4;
+        let mut previous_gain = f32::MAX;
+        for _ in 0..50 {
+            let next = elastic_pitch.step(pitch, 0.1, min_pitch, max_pitch, true, dt);
+            assert!(next - pitch < previous_gain + 1e-6);
+            assert!(next < max_pitch + elastic_pitch.overshoot);
+            previous_gain = next - pitch;
+            pitch = next;
+        }
+        assert!(pitch > max_pitch + 0.9 * elastic_pitch.overshoot);
+
+        // Once the rotation stops, the pitch springs back to the limit, as far in one long frame as in many short ones.
+        let stretch = pitch - max_pitch;
+        let long = elastic_pitch.step(pitch, 0.0, min_pitch, max_pitch, false, 4.0 * dt);
+        let mut short = pitch;
+        for _ in 0..4 {
+            short = elastic_pitch.step(short, 0.0, min_pitch, max_pitch, false, dt);
+        }
+        assert_relative_eq!(long, short, epsilon = 1e-5);
+        let rebound = elastic_pitch.rebound_weight.powi(4);
+        assert_relative_eq!(long - max_pitch, stretch * rebound, epsilon = 1e-5);
+
+        for _ in 0..100 {
+            let next = elastic_pitch.step(pitch, 0.0, min_pitch, max_pitch, false, dt);
+            assert!(next <= pitch && next >= max_pitch);
+            pitch = next;
+        }
+        assert_eq!(pitch, max_pitch);
+    }
+
+    #[test]
+    fn test_rotation_keeps_radius() {
+        for &orbit_mode in [OrbitMode::Turntable, OrbitMode::Trackball].iter() {
+            let controller = OrbitCameraController {
+                orbit_mode,
+                ..Default::default()
+            };
+            let mut transform =
+                LookTransform::new(Vec3::new(1.0, 2.0, 3.0), Vec3::new(-1.0, 0.5, 0.0));
+            let radius = transform.radius();
+
+            // A fixed spread of rotations, including ones that pitch past the poles.
+            for i in 0..200 {
+                let rotation = Vec2::new((i as f32 * 1.3).sin(), 2.0 * (i as f32 * 0.7).cos());
+                let (eye_direction, up) = orbit_rotate(
+                    &controller,
+                    -transform.look_direction(),
+                    transform.up,
+                    rotation,
+                    true,
+                    1.0 / 60.0,
+                );
+                transform.eye = transform.target + radius * eye_direction;
+                transform.up = up;
+
+                assert_relative_eq!(transform.radius(), radius, epsilon = 1e-4);
+                assert_relative_eq!(transform.up.length(), 1.0, epsilon = 1e-4);
+            }
+        }
+    }
+
+    #[test]
+    fn test_linear_zoom_moves_the_same_distance_at_any_radius() {
+        let linear = OrbitZoomMode::Linear {
+            reference_radius: 10.0,
+        };
+        for radius in [2.0, 10.0, 50.0].iter().cloned() {
+            assert_relative_eq!(radius * linear.scalar(0.9, radius), radius - 1.0, epsilon = 1e-4);
+        }
+        assert_relative_eq!(OrbitZoomMode::Proportional.scalar(0.9, 50.0), 0.9);
+
+        // A step longer than the radius stops short of the target.
+        assert!(linear.scalar(0.5, 1.0) > 0.0);
     }
 }
@@ -0,0 +1,284 @@
+use crate::{
+    set_look_transform_if_changed, viewport::{viewport_cursor, DragState}, AccumulatedInput,
+    ActiveCameraEntity, AddCameraController, BindingInput, CameraAction, CameraInputMap,
+    ControllerEnabled, ControllerViewport, InputAxisConfig, InputCaptureState, InputSource,
+    LookTransform, LookTransformTween, ReplayableControlEvent, ToggleableController,
+};
+
+use bevy::{
+    app::prelude::*,
+    ecs::{bundle::Bundle, prelude::*},
+    input::{
+        gamepad::{GamepadAxis, GamepadButton, Gamepads},
+        prelude::*,
+    },
+    math::prelude::*,
+    render::camera::OrthographicProjection,
+    window::Windows,
+};
+use serde::{Deserialize, Serialize};
+
+#[derive(Default)]
+pub struct PanCamPlugin {
+    /// Skips the built-in input mapping, so the camera is only driven by `PanCamControlEvent`s that you send.
+    pub override_input_system: bool,
+}
+
+impl Plugin for PanCamPlugin {
+    fn build(&self, app: &mut App) {
+        app.add_camera_controller::<PanCamControlEvent, _, _>(
+            map_pancam_input.system(),
+            control_pancam.system(),
+            self.override_input_system,
+        )
+        .add_control_toggle::<PanCamController>();
+    }
+}
+
+#[derive(Bundle, Deserialize, Serialize)]
+pub struct PanCamBundle {
+    controller: PanCamController,
+}
+
+impl PanCamBundle {
+    pub fn new(controller: PanCamController) -> Self {
+        Self { controller }
+    }
+}
+
+/// A 2D camera for an `OrthographicProjection` looking down the Z axis. Dragging with the `CameraAction::Grab` bindings
+/// pans the view so the grabbed point stays under the cursor, and scrolling zooms by changing the projection's scale.
+#[derive(Clone, Copy, Debug, Deserialize, Serialize)]
+pub struct PanCamController {
+    pub enabled: bool,
+    /// The devices that drive this camera. Defaults to all of them.
+    pub input_source: InputSource,
+    /// Scales cursor drags, in pixels. At the default of `1.0`, the grabbed point stays under the cursor.
+    pub mouse_translate: InputAxisConfig,
+    pub mouse_wheel_zoom_sensitivity: f32,
+    pub min_scale: f32,
+    pub max_scale: f32,
+    /// The minimum and maximum corners of the world-space rectangle that the view must stay inside.
+    pub bounds: Option<(Vec2, Vec2)>,
+}
+
+impl Default for PanCamController {
+    fn default() -> Self {
+        Self {
+            enabled: true,
+            input_source: InputSource::Any,
+            mouse_translate: InputAxisConfig::default(),
+            mouse_wheel_zoom_sensitivity: 0.15,
+            min_scale: 0.1,
+            max_scale: 10.0,
+            bounds: None,
+        }
+    }
+}
+
+impl ToggleableController for PanCamController {
+    fn enabled(&self) -> bool {
+        self.enabled
+    }
+
+    fn enabled_mut(&mut self) -> &mut bool {
+        &mut self.enabled
+    }
+}
+
+/// Control events for the PanCam controller.
+#[derive(Clone, Copy, Debug, Deserialize, PartialEq, Serialize)]
+pub enum PanCamControlEvent {
+    /// Drags the view by this many pixels, with Y pointing down the screen.
+    Pan(Entity, Vec2),
+    /// Scales the projection. Values less than `1.0` zoom in.
+    Zoom(Entity, f32),
+}
+
+impl PanCamControlEvent {
+    /// The camera entity that this event controls.
+    pub fn camera(&self) -> Entity {
+        match *self {
+            Self::Pan(camera, _) | Self::Zoom(camera, _) => camera,
+        }
+    }
+}
+
+/// The pan drags in progress for each pancam camera, kept by `map_pancam_input`.
+#[derive(Default)]
+pub struct PanCamDrags {
+    pan: DragState,
+}
+
+pub fn map_pancam_input(
+    controller_enabled: Res<ControllerEnabled>,
+    active_camera: Res<ActiveCameraEntity>,
+    input_capture: Res<InputCaptureState>,
+    mut events: EventWriter<PanCamControlEvent>,
+    mut accumulated_input: ResMut<AccumulatedInput>,
+    input_map: Res<CameraInputMap>,
+    keyboard: Res<Input<KeyCode>>,
+    mouse_buttons: Res<Input<MouseButton>>,
+    gamepads: Res<Gamepads>,
+    gamepad_axes: Res<Axis<GamepadAxis>>,
+    gamepad_buttons: Res<Input<GamepadButton>>,
+    windows: Res<Windows>,
+    mut drags: Local<PanCamDrags>,
+    controllers: Query<(
+        Entity,
+        &PanCamController,
+        Option<&ControllerViewport>,
+        Option<&CameraInputMap>,
+    )>,
+) {
+    // Take the input even when it's ignored, so it doesn't build up while the controllers are paused.
+    let pointer_delta = accumulated_input.take::<PanCamControlEvent>();
+    if !controller_enabled.0 {
+        return;
+    }
+
+    let input = BindingInput::new(
+        &keyboard,
+        &mouse_buttons,
+        &gamepads,
+        &gamepad_axes,
+        &gamepad_buttons,
+    );
+
+    // Forget the drags of cameras that were despawned or lost their controller.
+    drags.pan.retain(|camera| controllers.get(camera).is_ok());
+
+    for (entity, controller, viewport, camera_input_map) in controllers.iter() {
+        if !controller.enabled
+            || !active_camera.allows(entity)
+            || !controller.input_source.accepts_keyboard_mouse()
+        {
+            continue;
+        }
+
+        let (window, cursor_position) = viewport_cursor(&windows, viewport);
+        let pointer_free = !input_capture.pointer_captured && cursor_position.is_some();
+        let bindings = camera_input_map
+            .unwrap_or(&input_map)
+            .bindings(CameraAction::Grab);
+        let input = input.with_source(controller.input_source);
+        let panning =
+            drags.pan.update_bindings(entity, bindings, &input, window, pointer_free, 0.0);
+        // Follow the cursor rather than mouse motion, which pointer acceleration and DPI scaling pull away from it.
+        let cursor_delta =
+            panning.map_or(Vec2::ZERO, |button| drags.pan.cursor_delta(entity, button));
+        if cursor_delta != Vec2::ZERO {
+            // The cursor position has Y pointing up the screen, unlike the event.
+            let delta = Vec2::new(cursor_delta.x, -cursor_delta.y);
+            events.send(PanCamControlEvent::Pan(entity, controller.mouse_translate.apply(delta)));
+        }
+
+        if !pointer_free {
+            continue;
+        }
+
+        let scalar = (-pointer_delta.wheel() * controller.mouse_wheel_zoom_sensitivity).exp();
+        if scalar != 1.0 {
+            events.send(PanCamControlEvent::Zoom(entity, scalar));
+        }
+    }
+}
+
+pub fn control_pancam(
+    mut events: EventReader<PanCamControlEvent>,
+    mut cameras: Query<
+        (
+            Entity,
+            &PanCamController,
+            &mut LookTransform,
+            &mut OrthographicProjection,
+        ),
+        Without<LookTransformTween>,
+    >,
+) {
+    // Every camera only consumes the events that were routed to it.
+    let events: Vec<&PanCamControlEvent> = events.iter().collect();
+
+    for (entity, controller, mut look_transform, mut projection) in cameras.iter_mut() {
+        if !controller.enabled {
+            continue;
+        }
+
+        let mut transform = *look_transform;
+        let mut context = (*controller, projection.clone());
+        let camera_events: Vec<&PanCamControlEvent> =
+            events.iter().filter(|e| e.camera() == entity).cloned().collect();
+        PanCamControlEvent::apply_step(&mut transform, &mut context, &camera_events, 0.0);
+
+        let scale = context.1.scale;
+        if scale != projection.scale {
+            projection.scale = scale;
+        }
+        set_look_transform_if_changed(&mut look_transform, transform);
+    }
+}
+
+impl ReplayableControlEvent for PanCamControlEvent {
+    /// The controller's settings and the camera's `OrthographicProjection`, whose scale zooming changes.
+    type Context = (PanCamController, OrthographicProjection);
+
+    fn camera(&self) -> Entity {
+        PanCamControlEvent::camera(self)
+    }
+
+    /// PanCam events are in pixels and zoom factors rather than speeds, so the step's length doesn't matter.
+    fn apply_step(
+        transform: &mut LookTransform,
+        (controller, projection): &mut Self::Context,
+        events: &[&Self],
+        _: f32,
+    ) {
+        let mut translation = Vec2::ZERO;
+
+        for event in events {
+            match event {
+                PanCamControlEvent::Pan(_, delta) => {
+                    // One pixel covers `scale` world units, and the world moves with the cursor.
+                    translation += projection.scale * Vec2::new(-delta.x, delta.y);
+                }
+                PanCamControlEvent::Zoom(_, scalar) => {
+                    projection.scale = (projection.scale * scalar)
+                        .min(controller.max_scale)
+                        .max(controller.min_scale);
+                }
+            }
+        }
+
+        let mut center = transform.target.truncate() + translation;
+
+        if let Some((min, max)) = controller.bounds {
+            let half_extents = 0.5
+                * projection.scale
+                * Vec2::new(
+                    projection.right - projection.left,
+                    projection.top - projection.bottom,
+                );
+            center = clamp_view_center(center, half_extents, min, max);
+        }
+
+        let offset = (center - transform.target.truncate()).extend(0.0);
+        transform.eye += offset;
+        transform.target += offset;
+    }
+}
+
+/// Keeps a view with the given half extents inside the bounds, centering it on any axis where the bounds are too small.
+fn clamp_view_center(center: Vec2, half_extents: Vec2, min: Vec2, max: Vec2) -> Vec2 {
+    let clamp_axis = |c: f32, half: f32, lo: f32, hi: f32| {
+        if hi - lo < 2.0 * half {
+            0.5 * (lo + hi)
+        } else {
+            c.max(lo + half).min(hi - half)
+        }
+    };
+
+    Vec2::new(
+        clamp_axis(center.x, half_extents.x, min.x, max.x),
+        clamp_axis(center.y, half_extents.y, min.y, max.y),
+    )
+}
@@ -0,0 +1,452 @@
+use crate::{
+    camera_path::catmull_rom, set_look_transform_if_changed, viewport::pointer_in_viewport,
+    AccumulatedInput, ActiveCameraEntity, AddCameraController, BindingInput, CameraAction,
+    CameraInputMap, ControlTimestep, ControllerEnabled, ControllerViewport, InputCaptureState,
+    InputSource, LookTransform, LookTransformTween, PathInterpolation, ToggleableController,
+};
+
+use bevy::{
+    app::prelude::*,
+    core::Time,
+    ecs::{bundle::Bundle, prelude::*},
+    input::{
+        gamepad::{GamepadAxis, GamepadButton, Gamepads},
+        prelude::*,
+    },
+    math::prelude::*,
+    transform::components::{GlobalTransform, Transform},
+    window::Windows,
+};
+use serde::{Deserialize, Serialize};
+
+/// How many straight pieces each curved segment of a rail is split into when searching for the closest point.
+const CLOSEST_SEARCH_STEPS: usize = 16;
+
+#[derive(Default)]
+pub struct RailCameraPlugin {
+    /// Skips the built-in input mapping, so the camera is only driven by `RailControlEvent`s that you send.
+    pub override_input_system: bool,
+}
+
+impl Plugin for RailCameraPlugin {
+    fn build(&self, app: &mut App) {
+        app.add_camera_controller::<RailControlEvent, _, _>(
+            map_rail_input.system(),
+            control_rail_camera.system(),
+            self.override_input_system,
+        )
+        .add_control_toggle::<RailCameraController>()
+        .add_controller_state::<RailCameraController, RailCameraState>();
+    }
+}
+
+#[derive(Bundle)]
+pub struct RailCameraBundle {
+    controller: RailCameraController,
+    state: RailCameraState,
+}
+
+impl RailCameraBundle {
+    pub fn new(controller: RailCameraController) -> Self {
+        Self {
+            controller,
+            state: Default::default(),
+        }
+    }
+}
+
+/// A curve through `points`, in order, that a `RailCameraController` keeps its eye on. Positions along the rail are given
+/// by a parameter that's `0.0` at the first point, `1.0` at the second, and so on.
+#[derive(Clone, Debug, Default, Deserialize, PartialEq, Serialize)]
+pub struct CameraRail {
+    pub points: Vec<Vec3>,
+    pub interpolation: PathInterpolation,
+}
+
+impl CameraRail {
+    pub fn new(points: Vec<Vec3>, interpolation: PathInterpolation) -> Self {
+        Self {
+            points,
+            interpolation,
+        }
+    }
+
+    /// The parameter of the last point.
+    pub fn end(&self) -> f32 {
+        self.points.len().saturating_sub(1) as f32
+    }
+
+    /// The point at parameter `s`, which is clamped to the ends of the rail. Returns `None` if the rail has no points.
+    pub fn sample(&self, s: f32) -> Option<Vec3> {
+        let last = self.points.len().checked_sub(1)?;
+        if last == 0 {
+            return Some(self.points[0]);
+        }
+
+        let s = s.max(0.0).min(last as f32);
+        let i = (s.floor() as usize).min(last - 1);
+        let t = s - i as f32;
+        let (p1, p2) = (self.points[i], self.points[i + 1]);
+
+        Some(match self.interpolation {
+            PathInterpolation::Linear => p1.lerp(p2, t),
+            PathInterpolation::CatmullRom => {
+                // The ends of the rail are repeated so the spline still reaches them.
+                let p0 = self.points[i.saturating_sub(1)];
+                let p3 = self.points[(i + 2).min(last)];
+                catmull_rom(p0, p1, p2, p3, t)
+            }
+        })
+    }
+
+    /// The parameter of the point on the rail closest to `point`. Curved segments are searched as a chain of short
+    /// straight pieces, so the result is approximate.
+    pub fn closest(&self, point: Vec3) -> f32 {
+        self.closest_between(point, 0.0, self.end())
+    }
+
+    /// Like `closest`, but only searches the rail between the parameters `from` and `to`, e.g. around where the eye is now,
+    /// so the result doesn't jump to another stretch of a rail that folds back near `point`.
+    pub fn closest_between(&self, point: Vec3, from: f32, to: f32) -> f32 {
+        let steps = match self.interpolation {
+            PathInterpolation::Linear => 1,
+            PathInterpolation::CatmullRom => CLOSEST_SEARCH_STEPS,
+        };
+        let pieces = self.points.len().saturating_sub(1) * steps;
+        let from = from.max(0.0).min(self.end());
+        let to = to.max(from).min(self.end());
+        let first = ((from * steps as f32).floor() as usize).min(pieces);
+        let last = ((to * steps as f32).ceil() as usize).min(pieces);
+
+        let mut closest = (f32::INFINITY, from);
+        for piece in first..last {
+            let (s0, s1) = (
+                piece as f32 / steps as f32,
+                (piece + 1) as f32 / steps as f32,
+            );
+            let (a, b) = match (self.sample(s0), self.sample(s1)) {
+                (Some(a), Some(b)) => (a, b),
+                _ => break,
+            };
+
+            // Only the part of the piece between `from` and `to` counts.
+            let t_min = ((from - s0) / (s1 - s0)).max(0.0);
+            let t_max = ((to - s0) / (s1 - s0)).min(1.0);
+            let ab = b - a;
+            let t = if ab.length_squared() > 0.0 {
+                ((point - a).dot(ab) / ab.length_squared())
+                    .max(t_min)
+                    .min(t_max)
+            } else {
+                t_min
+            };
+            let distance = point.distance_squared(a + t * ab);
+            if distance < closest.0 {
+                closest = (distance, s0 + t * (s1 - s0));
+            }
+        }
+
+        closest.1
+    }
+}
+
+/// How a `RailCameraController` picks where its eye is on the rail.
+#[derive(Clone, Copy, Debug, Deserialize, Eq, PartialEq, Serialize)]
+pub enum RailMode {
+    /// The point on the rail closest to the target, like a platformer camera that tracks the player along a level.
+    Closest,
+    /// A point that's moved along the rail by `RailControlEvent`s, scrolling, and the move bindings, like a cinematic
+    /// dolly track.
+    Progress,
+}
+
+/// A rail camera, whose eye rides along a `CameraRail` while it keeps looking at another entity.
+///
+/// Put a `Smoother` on the camera to ease it along the rail instead of following the target exactly.
+#[derive(Clone, Debug)]
+pub struct RailCameraController {
+    pub enabled: bool,
+    /// The devices that drive this camera. Defaults to all of them.
+    pub input_source: InputSource,
+    /// The entity to look at. It must have a `GlobalTransform`.
+    pub followed: Entity,
+    /// Added to the followed entity's translation to get the target, e.g. to look at a character's head instead of its feet.
+    pub target_offset: Vec3,
+    pub rail: CameraRail,
+    pub mode: RailMode,
+    /// How far along the rail, in its parameter, each line scrolled moves the eye in `RailMode::Progress`.
+    pub wheel_advance_sensitivity: f32,
+    /// How far along the rail, in its parameter, the eye moves per second in `RailMode::Progress` while the
+    /// `CameraAction::MoveForward` or `MoveBack` bindings are held.
+    pub advance_speed: f32,
+    /// How far along the rail, in its parameter, `RailMode::Closest` searches on either side of the eye each step. The eye
+    /// slides along a rail that folds back near the target instead of jumping across to the other stretch.
+    pub closest_search_window: f32,
+}
+
+impl RailCameraController {
+    pub fn new(followed: Entity, rail: CameraRail) -> Self {
+        Self {
+            enabled: true,
+            input_source: InputSource::Any,
+            followed,
+            target_offset: Vec3::ZERO,
+            rail,
+            mode: RailMode::Closest,
+            wheel_advance_sensitivity: 0.1,
+            advance_speed: 0.5,
+            closest_search_window: 1.0,
+        }
+    }
+}
+
+impl ToggleableController for RailCameraController {
+    fn enabled(&self) -> bool {
+        self.enabled
+    }
+
+    fn enabled_mut(&mut self) -> &mut bool {
+        &mut self.enabled
+    }
+}
+
+/// Per-camera state that the rail controller carries between frames.
+#[derive(Clone, Copy, Debug, Default)]
+pub struct RailCameraState {
+    /// `None` until the eye is first placed, when `RailMode::Closest` searches the whole rail.
+    progress: Option<f32>,
+}
+
+impl RailCameraState {
+    /// Where the eye is on the rail, in the rail's parameter.
+    pub fn progress(&self) -> f32 {
+        self.progress.unwrap_or(0.0)
+    }
+}
+
+/// Control events for the rail controller, which are ignored in `RailMode::Closest`.
+#[derive(Clone, Copy, Debug, Deserialize, PartialEq, Serialize)]
+pub enum RailControlEvent {
+    /// Moves the eye along the rail by this much of the rail's parameter. Negative values move back toward the start.
+    Advance(Entity, f32),
+    /// Moves the eye to this parameter on the rail.
+    SetProgress(Entity, f32),
+}
+
+impl RailControlEvent {
+    /// The camera entity that this event controls.
+    pub fn camera(&self) -> Entity {
+        match *self {
+            Self::Advance(camera, _) | Self::SetProgress(camera, _) => camera,
+        }
+    }
+}
+
+pub fn map_rail_input(
+    controller_enabled: Res<ControllerEnabled>,
+    active_camera: Res<ActiveCameraEntity>,
+    input_capture: Res<InputCaptureState>,
+    time: Res<Time>,
+    timestep: Res<ControlTimestep>,
+    mut events: EventWriter<RailControlEvent>,
+    input_map: Res<CameraInputMap>,
+    keyboard: Res<Input<KeyCode>>,
+    mouse_buttons: Res<Input<MouseButton>>,
+    gamepads: Res<Gamepads>,
+    gamepad_axes: Res<Axis<GamepadAxis>>,
+    gamepad_buttons: Res<Input<GamepadButton>>,
+    mut accumulated_input: ResMut<AccumulatedInput>,
+    windows: Res<Windows>,
+    controllers: Query<
+        (
+            Entity,
+            &RailCameraController,
+            Option<&ControllerViewport>,
+            Option<&CameraInputMap>,
+        ),
+        With<Transform>,
+    >,
+) {
+    // Take the input even when it's ignored, so it doesn't build up while the controllers are paused.
+    let pointer_delta = accumulated_input.take::<RailControlEvent>();
+    if !controller_enabled.0 {
+        return;
+    }
+
+    let scroll = pointer_delta.wheel();
+    let dt = timestep.delta_seconds(&time);
+    let input = BindingInput::new(
+        &keyboard,
+        &mouse_buttons,
+        &gamepads,
+        &gamepad_axes,
+        &gamepad_buttons,
+    );
+
+    for (entity, controller, viewport, camera_input_map) in controllers.iter() {
+        if !controller.enabled
+            || controller.mode != RailMode::Progress
+            || !active_camera.allows(entity)
+        {
+            continue;
+        }
+
+        if scroll != 0.0
+            && !input_capture.pointer_captured
+            && controller.input_source.accepts_keyboard_mouse()
+            && pointer_in_viewport(&windows, viewport)
+        {
+            events.send(RailControlEvent::Advance(
+                entity,
+                controller.wheel_advance_sensitivity * scroll,
+            ));
+        }
+
+        if input_capture.keyboard_captured {
+            continue;
+        }
+
+        let input_map = camera_input_map.unwrap_or(&input_map);
+        let input = input.with_source(controller.input_source);
+        let advance = input_map.axis(CameraAction::MoveForward, CameraAction::MoveBack, &input);
+        if advance != 0.0 {
+            events.send(RailControlEvent::Advance(
+                entity,
+                controller.advance_speed * advance * dt,
+            ));
+        }
+    }
+}
+
+pub fn control_rail_camera(
+    mut events: EventReader<RailControlEvent>,
+    mut cameras: Query<
+        (
+            Entity,
+            &RailCameraController,
+            &mut RailCameraState,
+            &mut LookTransform,
+        ),
+        (With<Transform>, Without<LookTransformTween>),
+    >,
+    followed: Query<&GlobalTransform>,
+) {
+    // Every camera only consumes the events that were routed to it.
+    let events: Vec<&RailControlEvent> = events.iter().collect();
+
+    for (entity, controller, mut state, mut look_transform) in cameras.iter_mut() {
+        if !controller.enabled {
+            continue;
+        }
+
+        let mut transform = *look_transform;
+
+        let followed_transform = if let Ok(t) = followed.get(controller.followed) {
+            t
+        } else {
+            continue;
+        };
+        let target = followed_transform.translation + controller.target_offset;
+
+        let progress = match (controller.mode, state.progress) {
+            (RailMode::Closest, None) => controller.rail.closest(target),
+            (RailMode::Closest, Some(progress)) => controller.rail.closest_between(
+                target,
+                progress - controller.closest_search_window,
+                progress + controller.closest_search_window,
+            ),
+            (RailMode::Progress, _) => events.iter().filter(|e| e.camera() == entity).fold(
+                state.progress(),
+                |progress, event| match event {
+                    RailControlEvent::Advance(_, delta) => progress + delta,
+                    RailControlEvent::SetProgress(_, progress) => *progress,
+                },
+            ),
+        };
+        let progress = progress.max(0.0).min(controller.rail.end());
+        state.progress = Some(progress);
+
+        let eye = match controller.rail.sample(progress) {
+            Some(eye) => eye,
+            None => continue,
+        };
+        // There's no direction to look in while the target passes right through the eye.
+        if eye.distance_squared(target) < 1e-6 {
+            continue;
+        }
+
+        transform.eye = eye;
+        transform.target = target;
+        set_look_transform_if_changed(&mut look_transform, transform);
+    }
+}
+
+// ████████╗███████╗███████╗████████╗
+// ╚══██╔══╝██╔════╝██╔════╝╚══██╔══╝
+//    ██║   █████╗  ███████╗   ██║
+//    ██║   ██╔══╝  ╚════██║   ██║
+//    ██║   ███████╗███████║   ██║
+//    ╚═╝   ╚══════╝╚══════╝   ╚═╝
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    use approx::assert_relative_eq;
+
+    fn rail(interpolation: PathInterpolation) -> CameraRail {
+        CameraRail::new(
+            vec![
+                Vec3::new(0.0, 2.0, 5.0),
+                Vec3::new(10.0, 2.0, 5.0),
+                Vec3::new(20.0, 4.0, 5.0),
+            ],
+            interpolation,
+        )
+    }
+
+    #[test]
+    fn test_sample_passes_through_points_and_clamps() {
+        for interpolation in [PathInterpolation::Linear, PathInterpolation::CatmullRom].iter() {
+            let rail = rail(*interpolation);
+            for (i, point) in rail.points.iter().enumerate() {
+                assert!(rail.sample(i as f32).unwrap().abs_diff_eq(*point, 1e-5));
+            }
+            assert_eq!(rail.sample(-1.0), Some(rail.points[0]));
+            assert_eq!(rail.sample(5.0), Some(rail.points[2]));
+        }
+
+        assert_eq!(CameraRail::default().sample(0.0), None);
+    }
+
+    #[test]
+    fn test_closest_finds_the_nearest_point_on_the_rail() {
+        let rail = rail(PathInterpolation::Linear);
+        assert_relative_eq!(rail.closest(Vec3::new(5.0, 0.0, 0.0)), 0.5, epsilon = 1e-5);
+        assert_relative_eq!(rail.closest(Vec3::new(-5.0, 0.0, 0.0)), 0.0);
+        assert_relative_eq!(rail.closest(Vec3::new(30.0, 0.0, 0.0)), 2.0);
+
+        let rail = rail(PathInterpolation::CatmullRom);
+        let s = rail.closest(Vec3::new(15.0, 0.0, 5.0));
+        assert!(s > 1.0 && s < 2.0);
+    }
+
+    #[test]
+    fn test_closest_between_stays_on_its_stretch_of_a_folded_rail() {
+        let rail = CameraRail::new(
+            vec![
+                Vec3::ZERO,
+                Vec3::new(10.0, 0.0, 0.0),
+                Vec3::new(10.0, 0.0, 1.0),
+                Vec3::new(0.0, 0.0, 1.0),
+            ],
+            PathInterpolation::Linear,
+        );
+        let point = Vec3::new(2.0, 0.0, 0.6);
+
+        // The way back is closer, but not within reach of the first stretch.
+        assert_relative_eq!(rail.closest(point), 2.8, epsilon = 1e-5);
+        assert_relative_eq!(rail.closest_between(point, 0.0, 1.2), 0.2, epsilon = 1e-5);
+        assert_relative_eq!(rail.closest_between(point, 0.5, 1.2), 0.5, epsilon = 1e-5);
+    }
+}
@@ -0,0 +1,198 @@
+use crate::{
+    controllers::{
+        chase::{ChaseCameraController, ChaseCameraState},
+        follow::FollowCameraController,
+        fps::{FpsCameraController, FpsCameraState},
+        free_fly::FreeFlyCameraController,
+        isometric::{IsometricCameraController, IsometricCameraState},
+        orbit::{OrbitCameraController, OrbitCameraState},
+        pancam::PanCamController,
+        rts::RtsCameraController,
+    },
+    LookTransformSystem, LookTransformTween, ToggleableController,
+};
+
+use bevy::{
+    app::prelude::*,
+    ecs::{component::Component, prelude::*},
+};
+use serde::{Deserialize, Serialize};
+
+/// Switches cameras with a `CameraRig` between their controllers in response to `SwitchCameraController` events.
+pub struct CameraRigPlugin;
+
+impl Plugin for CameraRigPlugin {
+    fn build(&self, app: &mut App) {
+        app.add_event::<SwitchCameraController>()
+            .add_system(
+                switch_camera_controllers
+                    .system()
+                    .label(CameraRigSystem::Switch)
+                    .before(LookTransformSystem::InputMap),
+            )
+            .add_system(
+                enable_active_controllers
+                    .system()
+                    .after(CameraRigSystem::Switch)
+                    .before(LookTransformSystem::InputMap),
+            );
+    }
+}
+
+#[derive(Clone, Debug, Eq, Hash, PartialEq, SystemLabel)]
+pub enum CameraRigSystem {
+    Switch,
+}
+
+/// One of the built-in controllers.
+#[derive(Clone, Copy, Debug, Deserialize, Eq, PartialEq, Serialize)]
+pub enum RigController {
+    Chase,
+    Follow,
+    Fps,
+    FreeFly,
+    Isometric,
+    Orbit,
+    PanCam,
+    Rts,
+}
+
+/// Put this on a camera that has the components of several controllers, e.g. one spawned with an `FpsCameraBundle` that
+/// also has an `OrbitCameraController` and `OrbitCameraState`, to have only one of them enabled at a time. All of the
+/// controllers drive the same `LookTransform`, so the eye and target carry over when switching.
+#[derive(Clone, Copy, Debug, Deserialize, Serialize)]
+pub struct CameraRig {
+    active: RigController,
+}
+
+impl CameraRig {
+    pub fn new(active: RigController) -> Self {
+        Self { active }
+    }
+
+    /// The controller that's currently enabled.
+    pub fn active(&self) -> RigController {
+        self.active
+    }
+}
+
+/// Makes `controller` the active controller of the `CameraRig` on `camera`.
+///
+/// With a `transition`, the camera first flies to the tween's destination, e.g. from an orbit view down to a character's eyes,
+/// and the new controller takes over once it arrives.
+///
+/// ```rust
+/// events.send(SwitchCameraController {
+///     camera,
+///     controller: RigController::Fps,
+///     transition: Some(LookTransformTween::ease_to(
+///         LookTransform::new(head, head + forward),
+///         1.0,
+///         Easing::EaseInOut,
+///     )),
+/// });
+/// ```
+#[derive(Clone, Copy, Debug)]
+pub struct SwitchCameraController {
+    pub camera: Entity,
+    pub controller: RigController,
+    pub transition: Option<LookTransformTween>,
+}
+
+pub fn switch_camera_controllers(
+    mut commands: Commands,
+    mut events: EventReader<SwitchCameraController>,
+    mut rigs: Query<&mut CameraRig>,
+) {
+    for event in events.iter() {
+        let mut rig = if let Ok(rig) = rigs.get_mut(event.camera) {
+            rig
+        } else {
+            continue;
+        };
+
+        if rig.active != event.controller {
+            rig.active = event.controller;
+        }
+        if let Some(transition) = event.transition {
+            commands.entity(event.camera).insert(transition);
+        }
+    }
+}
+
+/// Enables the active controller of each rig and disables the others. Only controllers whose `enabled` flag changes are
+/// touched, and a controller that's switched on starts from fresh state, since the camera may have moved since it was last
+/// enabled.
+#[allow(clippy::type_complexity)]
+pub fn enable_active_controllers(
+    mut rigs: Query<
+        (
+            &CameraRig,
+            Option<&mut ChaseCameraController>,
+            Option<&mut FollowCameraController>,
+            Option<&mut FpsCameraController>,
+            Option<&mut FreeFlyCameraController>,
+            Option<&mut IsometricCameraController>,
+            Option<&mut OrbitCameraController>,
+            Option<&mut PanCamController>,
+            Option<&mut RtsCameraController>,
+            Option<&mut ChaseCameraState>,
+            Option<&mut FpsCameraState>,
+            Option<&mut IsometricCameraState>,
+            Option<&mut OrbitCameraState>,
+        ),
+        Changed<CameraRig>,
+    >,
+) {
+    for (
+        rig,
+        chase,
+        follow,
+        fps,
+        free_fly,
+        isometric,
+        orbit,
+        pancam,
+        rts,
+        chase_state,
+        fps_state,
+        isometric_state,
+        orbit_state,
+    ) in rigs.iter_mut()
+    {
+        let active = rig.active;
+        if set_enabled(chase, active == RigController::Chase) {
+            reset_state(chase_state);
+        }
+        set_enabled(follow, active == RigController::Follow);
+        if set_enabled(fps, active == RigController::Fps) {
+            reset_state(fps_state);
+        }
+        set_enabled(free_fly, active == RigController::FreeFly);
+        if set_enabled(isometric, active == RigController::Isometric) {
+            reset_state(isometric_state);
+        }
+        if set_enabled(orbit, active == RigController::Orbit) {
+            reset_state(orbit_state);
+        }
+        set_enabled(pancam, active == RigController::PanCam);
+        set_enabled(rts, active == RigController::Rts);
+    }
+}
+
+/// Sets the `enabled` flag of `controller` if it's there and different, returning whether it was just switched on.
+fn set_enabled<C: ToggleableController>(controller: Option<Mut<C>>, enabled: bool) -> bool {
+    match controller {
+        Some(mut controller) if controller.enabled() != enabled => {
+            *controller.enabled_mut() = enabled;
+            enabled
+        }
+        _ => false,
+    }
+}
+
+fn reset_state<S: Component + Default>(state: Option<Mut<S>>) {
+    if let Some(mut state) = state {
+        *state = S::default();
+    }
+}
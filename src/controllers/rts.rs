@@ -0,0 +1,312 @@
+use crate::{
+    set_look_transform_if_changed, viewport::{pointer_in_viewport, viewport_cursor},
+    AccumulatedInput, ActiveCameraEntity, AddCameraController, BindingInput, CameraAction,
+    CameraInputMap, ControlTimestep, ControllerEnabled, ControllerViewport, InputAxisConfig,
+    InputCaptureState, InputSource, LookAngles, LookTransform, LookTransformTween,
+    ReplayableControlEvent, ToggleableController,
+};
+
+use bevy::{
+    app::prelude::*,
+    core::Time,
+    ecs::{bundle::Bundle, prelude::*},
+    input::{
+        gamepad::{GamepadAxis, GamepadButton, Gamepads},
+        prelude::*,
+    },
+    math::prelude::*,
+    transform::components::Transform,
+    window::Windows,
+};
+use serde::{Deserialize, Serialize};
+use std::f32::consts::FRAC_PI_4;
+
+#[derive(Default)]
+pub struct RtsCameraPlugin {
+    /// Skips the built-in input mapping, so the camera is only driven by `RtsControlEvent`s that you send.
+    pub override_input_system: bool,
+}
+
+impl Plugin for RtsCameraPlugin {
+    fn build(&self, app: &mut App) {
+        app.add_camera_controller::<RtsControlEvent, _, _>(
+            map_rts_input.system(),
+            control_rts_camera.system(),
+            self.override_input_system,
+        )
+        .add_control_toggle::<RtsCameraController>();
+    }
+}
+
+#[derive(Bundle, Deserialize, Serialize)]
+pub struct RtsCameraBundle {
+    controller: RtsCameraController,
+}
+
+impl RtsCameraBundle {
+    pub fn new(controller: RtsCameraController) -> Self {
+        Self { controller }
+    }
+}
+
+/// A top-down strategy camera. The target slides over the ground while the eye hovers above it at a fixed pitch; zooming
+/// changes the eye's height and rotation spins the view around the vertical axis.
+#[derive(Clone, Copy, Debug, Deserialize, Serialize)]
+pub struct RtsCameraController {
+    pub enabled: bool,
+    /// The devices that drive this camera. Defaults to all of them.
+    pub input_source: InputSource,
+    /// The angle of the eye above the ground plane, in radians.
+    pub pitch: f32,
+    /// Panning starts when the cursor is within this many pixels of a window edge.
+    pub edge_pan_margin: f32,
+    /// Panning speed in units per second, from the window edges.
+    pub pan: InputAxisConfig,
+    /// Rotation speed in radians per second, from the keys. Only the X axis is used.
+    pub rotate: InputAxisConfig,
+    pub mouse_wheel_zoom_sensitivity: f32,
+    /// The lowest the eye can be above the target.
+    pub min_height: f32,
+    /// The highest the eye can be above the target.
+    pub max_height: f32,
+}
+
+impl Default for RtsCameraController {
+    fn default() -> Self {
+        Self {
+            enabled: true,
+            input_source: InputSource::Any,
+            pitch: FRAC_PI_4,
+            edge_pan_margin: 10.0,
+            pan: InputAxisConfig::new(Vec2::splat(10.0)),
+            rotate: InputAxisConfig::new(Vec2::splat(1.5)),
+            mouse_wheel_zoom_sensitivity: 0.15,
+            min_height: 2.0,
+            max_height: 100.0,
+        }
+    }
+}
+
+impl ToggleableController for RtsCameraController {
+    fn enabled(&self) -> bool {
+        self.enabled
+    }
+
+    fn enabled_mut(&mut self) -> &mut bool {
+        &mut self.enabled
+    }
+}
+
+/// Control events for the RTS controller.
+#[derive(Clone, Copy, Debug, Deserialize, PartialEq, Serialize)]
+pub enum RtsControlEvent {
+    /// Target velocity on the ground plane in units per second, where X is screen right and Y is screen forward.
+    Pan(Entity, Vec2),
+    /// Angular velocity around the vertical axis in radians per second.
+    Rotate(Entity, f32),
+    /// Scales the height of the eye above the target. Values less than `1.0` zoom in.
+    Zoom(Entity, f32),
+}
+
+impl RtsControlEvent {
+    /// The camera entity that this event controls.
+    pub fn camera(&self) -> Entity {
+        match *self {
+            Self::Pan(camera, _) | Self::Rotate(camera, _) | Self::Zoom(camera, _) => camera,
+        }
+    }
+
+    /// Whether this event moves the camera at all, unlike the zero pan that's sent while no key is held.
+    fn moves(&self) -> bool {
+        match *self {
+            Self::Pan(_, velocity) => velocity != Vec2::ZERO,
+            Self::Rotate(_, velocity) => velocity != 0.0,
+            Self::Zoom(_, scalar) => scalar != 1.0,
+        }
+    }
+}
+
+pub fn map_rts_input(
+    controller_enabled: Res<ControllerEnabled>,
+    active_camera: Res<ActiveCameraEntity>,
+    input_capture: Res<InputCaptureState>,
+    mut events: EventWriter<RtsControlEvent>,
+    windows: Res<Windows>,
+    input_map: Res<CameraInputMap>,
+    keyboard: Res<Input<KeyCode>>,
+    mouse_buttons: Res<Input<MouseButton>>,
+    gamepads: Res<Gamepads>,
+    gamepad_axes: Res<Axis<GamepadAxis>>,
+    gamepad_buttons: Res<Input<GamepadButton>>,
+    mut accumulated_input: ResMut<AccumulatedInput>,
+    controllers: Query<
+        (
+            Entity,
+            &RtsCameraController,
+            Option<&ControllerViewport>,
+            Option<&CameraInputMap>,
+        ),
+        With<Transform>,
+    >,
+) {
+    // Take the input even when it's ignored, so it doesn't build up while the controllers are paused.
+    let pointer_delta = accumulated_input.take::<RtsControlEvent>();
+    if !controller_enabled.0 {
+        return;
+    }
+
+    let input = BindingInput::new(
+        &keyboard,
+        &mouse_buttons,
+        &gamepads,
+        &gamepad_axes,
+        &gamepad_buttons,
+    );
+
+    for (entity, controller, viewport, camera_input_map) in controllers.iter() {
+        let RtsCameraController {
+            enabled,
+            edge_pan_margin,
+            pan,
+            rotate,
+            mouse_wheel_zoom_sensitivity,
+            ..
+        } = *controller;
+
+        if !enabled || !active_camera.allows(entity) {
+            continue;
+        }
+
+        let pointer_free =
+            !input_capture.pointer_captured && controller.input_source.accepts_keyboard_mouse();
+        let (window, cursor_position) = viewport_cursor(&windows, viewport);
+        // Don't pan while the app is in the background and the cursor happens to rest near an edge.
+        let window = window.filter(|w| w.is_focused());
+
+        if !pointer_free {
+            // Fall through to the keyboard and gamepad controls.
+        } else if let (Some(window), Some(cursor)) = (window, cursor_position) {
+            // The cursor origin is at the bottom-left of the window.
+            let (min, max) = viewport
+                .map(|v| v.bounds(window))
+                .unwrap_or_else(|| (Vec2::ZERO, Vec2::new(window.width(), window.height())));
+            let mut pan_dir = Vec2::ZERO;
+            if cursor.x < min.x + edge_pan_margin {
+                pan_dir.x -= 1.0;
+            }
+            if cursor.x > max.x - edge_pan_margin {
+                pan_dir.x += 1.0;
+            }
+            if cursor.y < min.y + edge_pan_margin {
+                pan_dir.y -= 1.0;
+            }
+            if cursor.y > max.y - edge_pan_margin {
+                pan_dir.y += 1.0;
+            }
+            if pan_dir != Vec2::ZERO {
+                events.send(RtsControlEvent::Pan(entity, pan.apply(pan_dir)));
+            }
+        }
+
+        let input_map = camera_input_map.unwrap_or(&input_map);
+        let input = input.with_source(controller.input_source);
+        for (action, dir) in [(CameraAction::RotateLeft, 1.0), (CameraAction::RotateRight, -1.0)]
+            .iter()
+            .cloned()
+        {
+            if !input_capture.keyboard_captured && input_map.pressed(action, &input) {
+                let velocity = rotate.apply(Vec2::new(dir, 0.0)).x;
+                events.send(RtsControlEvent::Rotate(entity, velocity));
+            }
+        }
+
+        if pointer_free && pointer_in_viewport(&windows, viewport) {
+            let scalar = (-pointer_delta.wheel() * mouse_wheel_zoom_sensitivity).exp();
+            events.send(RtsControlEvent::Zoom(entity, scalar));
+        }
+    }
+}
+
+pub fn control_rts_camera(
+    time: Res<Time>,
+    timestep: Res<ControlTimestep>,
+    mut events: EventReader<RtsControlEvent>,
+    mut cameras: Query<
+        (
+            Entity,
+            &RtsCameraController,
+            ChangeTrackers<RtsCameraController>,
+            &mut LookTransform,
+        ),
+        (With<Transform>, Without<LookTransformTween>),
+    >,
+) {
+    // Every camera only consumes the events that were routed to it.
+    let events: Vec<&RtsControlEvent> = events.iter().collect();
+    let dt = timestep.delta_seconds(&time);
+
+    for (entity, controller, controller_tracker, mut look_transform) in cameras.iter_mut() {
+        if !controller.enabled {
+            continue;
+        }
+
+        let camera_events: Vec<&RtsControlEvent> = events
+            .iter()
+            .filter(|e| e.camera() == entity && e.moves())
+            .cloned()
+            .collect();
+        // Rebuilding the eye from angles rounds a little differently every step, which would count as a change to the
+        // `LookTransform`, so a camera is left alone unless something moves it or its settings change.
+        if camera_events.is_empty() && !controller_tracker.is_changed() {
+            continue;
+        }
+
+        let mut transform = *look_transform;
+        let mut controller = *controller;
+        RtsControlEvent::apply_step(&mut transform, &mut controller, &camera_events, dt);
+        set_look_transform_if_changed(&mut look_transform, transform);
+    }
+}
+
+impl ReplayableControlEvent for RtsControlEvent {
+    /// The controller's settings, which the events don't change.
+    type Context = RtsCameraController;
+
+    fn camera(&self) -> Entity {
+        RtsControlEvent::camera(self)
+    }
+
+    fn apply_step(
+        transform: &mut LookTransform,
+        controller: &mut RtsCameraController,
+        events: &[&Self],
+        delta_seconds: f32,
+    ) {
+        let mut look_angles = LookAngles::from_vector(-transform.look_direction());
+        look_angles.set_pitch(controller.pitch);
+
+        let mut height = transform.eye.y - transform.target.y;
+
+        for event in events {
+            match event {
+                RtsControlEvent::Pan(_, velocity) => {
+                    let forward = -look_angles.unit_vector() * Vec3::new(1.0, 0.0, 1.0);
+                    let forward = forward.normalize();
+                    let right = forward.cross(Vec3::Y);
+                    transform.target += delta_seconds * (velocity.x * right + velocity.y * forward);
+                }
+                RtsControlEvent::Rotate(_, velocity) => {
+                    look_angles.add_yaw(delta_seconds * velocity);
+                }
+                RtsControlEvent::Zoom(_, scalar) => {
+                    height *= scalar;
+                }
+            }
+        }
+
+        let height = height.min(controller.max_height).max(controller.min_height);
+        let radius = height / look_angles.get_pitch().sin();
+
+        transform.eye = transform.target + radius * look_angles.unit_vector();
+    }
+}
@@ -0,0 +1,167 @@
+use crate::{
+    control_replay::record_control_events_system, control_timestep::control_system_set,
+    control_toggle::control_toggle_system, scripted_events::scripted_control_events_system,
+    ControlEventRecorder, ControlTimestep, LookTransformSystem, ScriptedControlEvents,
+    ToggleableController,
+};
+
+use bevy::{
+    app::prelude::*,
+    ecs::{component::Component, prelude::*, schedule::SystemDescriptor},
+};
+
+/// Wires up a controller the same way as the built-in ones, for controllers written outside this crate.
+///
+/// The input-mapping system turns raw input into control events of type `E`, and the control system reads them and moves
+/// each camera's `LookTransform`. They're labelled `LookTransformSystem::InputMap` and `LookTransformSystem::Control`, so
+/// they run before the `Smoother`, and both follow the app's `ControlTimestep`. With `ControlTimestep::Fixed`, events of
+/// type `E` are kept until the next step rather than for two frames.
+///
+/// ```rust
+/// impl Plugin for MyCameraPlugin {
+///     fn build(&self, app: &mut App) {
+///         app.add_camera_controller::<MyControlEvent, _, _>(
+///             map_my_input.system(),
+///             control_my_camera.system(),
+///             self.override_input_system,
+///         );
+///     }
+/// }
+/// ```
+pub trait AddCameraController {
+    /// Adds the event type `E`, the `control` system, and, unless `override_input_system` is set, the `input_map` system.
+    fn add_camera_controller<E, M, C>(
+        &mut self,
+        input_map: M,
+        control: C,
+        override_input_system: bool,
+    ) -> &mut Self
+    where
+        E: Component,
+        M: Into<SystemDescriptor>,
+        C: ParallelSystemDescriptorCoercion;
+
+    /// Adds a `ScriptedControlEvents<E>` resource and a system that sends its events on their steps, in place of or
+    /// alongside the input-mapping system. Call this after the controller for `E` has been added.
+    fn add_scripted_control_events<E>(&mut self) -> &mut Self
+    where
+        E: Component;
+
+    /// Adds a `ControlEventRecorder<E>` resource and a system that stamps and records every event of type `E` on its way to
+    /// the control systems. Call this after the controller for `E` has been added.
+    fn add_control_event_recorder<E>(&mut self) -> &mut Self
+    where
+        E: Component + Clone;
+
+    /// Adds a system that flips the `enabled` field of `C` on cameras with a `ControlToggle` when its key is pressed. It
+    /// runs every frame, before the input-mapping systems, so presses aren't missed with a `ControlTimestep`.
+    fn add_control_toggle<C>(&mut self) -> &mut Self
+    where
+        C: ToggleableController;
+
+    /// Adds a system that inserts a default `S` on cameras with a controller `C` but no `S`, so cameras that were spawned
+    /// with only the controller component, rather than its bundle, still get the state their control system needs.
+    fn add_controller_state<C, S>(&mut self) -> &mut Self
+    where
+        C: Component,
+        S: Component + Default;
+}
+
+impl AddCameraController for App {
+    fn add_camera_controller<E, M, C>(
+        &mut self,
+        input_map: M,
+        control: C,
+        override_input_system: bool,
+    ) -> &mut Self
+    where
+        E: Component,
+        M: Into<SystemDescriptor>,
+        C: ParallelSystemDescriptorCoercion,
+    {
+        let control = control_system_set(self).with_system(
+            control
+                .label(LookTransformSystem::Control)
+                .after(LookTransformSystem::InputMap),
+        );
+        self.add_system_set(control);
+
+        if let Some(ControlTimestep::Fixed(_)) = self.world.get_resource::<ControlTimestep>() {
+            // Bevy drops events after two frames, which can pass without a fixed step. Events sent from anywhere, like a
+            // gameplay `SetView`, are instead kept until a step has run the control systems.
+            let update = control_system_set(self).with_system(
+                Events::<E>::update_system
+                    .system()
+                    .after(LookTransformSystem::Control),
+            );
+            self.init_resource::<Events<E>>().add_system_set(update);
+        } else {
+            self.add_event::<E>();
+        }
+
+        if !override_input_system {
+            let input_map = control_system_set(self)
+                .label(LookTransformSystem::InputMap)
+                .with_system(input_map);
+            self.add_system_set(input_map);
+        }
+
+        self
+    }
+
+    fn add_scripted_control_events<E>(&mut self) -> &mut Self
+    where
+        E: Component,
+    {
+        let script = control_system_set(self)
+            .label(LookTransformSystem::InputMap)
+            .with_system(scripted_control_events_system::<E>.system());
+        self.init_resource::<ScriptedControlEvents<E>>()
+            .add_system_set(script)
+    }
+
+    fn add_control_event_recorder<E>(&mut self) -> &mut Self
+    where
+        E: Component + Clone,
+    {
+        let recorder = control_system_set(self).with_system(
+            record_control_events_system::<E>
+                .system()
+                .after(LookTransformSystem::InputMap)
+                .before(LookTransformSystem::Control),
+        );
+        self.init_resource::<ControlEventRecorder<E>>()
+            .add_system_set(recorder)
+    }
+
+    fn add_control_toggle<C>(&mut self) -> &mut Self
+    where
+        C: ToggleableController,
+    {
+        self.add_system(
+            control_toggle_system::<C>
+                .system()
+                .before(LookTransformSystem::InputMap),
+        )
+    }
+
+    fn add_controller_state<C, S>(&mut self) -> &mut Self
+    where
+        C: Component,
+        S: Component + Default,
+    {
+        self.add_system_to_stage(
+            CoreStage::PreUpdate,
+            insert_controller_state_system::<C, S>.system(),
+        )
+    }
+}
+
+fn insert_controller_state_system<C: Component, S: Component + Default>(
+    mut commands: Commands,
+    cameras: Query<Entity, (With<C>, Without<S>)>,
+) {
+    for camera in cameras.iter() {
+        commands.entity(camera).insert(S::default());
+    }
+}
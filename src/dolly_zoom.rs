@@ -0,0 +1,113 @@
+use crate::{ControlTimestep, Easing, LookTransform, ReducedMotion};
+
+use bevy::{core::Time, ecs::prelude::*, render::camera::PerspectiveProjection};
+
+/// The radius that keeps the view of the target plane the same size when a perspective camera's vertical field of view
+/// changes from `fov` to `new_fov`, in radians.
+pub fn dolly_zoom_radius(radius: f32, fov: f32, new_fov: f32) -> f32 {
+    radius * (0.5 * fov).tan() / (0.5 * new_fov).tan()
+}
+
+/// The "vertigo" effect: eases a camera's `PerspectiveProjection::fov` to `target_fov` over `duration` seconds, while moving
+/// the eye along the look direction so the plane through the target keeps the same size on screen. The background appears to
+/// stretch or compress around a subject that stays still. The component removes itself once the effect is finished. While
+/// `ReducedMotion` is enabled, the camera cuts straight to the end of the effect.
+///
+/// ```rust
+/// commands.entity(camera).insert(DollyZoom::new(30f32.to_radians(), 2.0));
+/// ```
+#[derive(Clone, Copy, Debug)]
+pub struct DollyZoom {
+    pub target_fov: f32,
+    pub duration: f32,
+    pub easing: Easing,
+    /// The field of view and radius when the effect started.
+    start: Option<(f32, f32)>,
+    elapsed: f32,
+}
+
+impl DollyZoom {
+    pub fn new(target_fov: f32, duration: f32) -> Self {
+        Self {
+            target_fov,
+            duration,
+            easing: Easing::EaseInOut,
+            start: None,
+            elapsed: 0.0,
+        }
+    }
+
+    pub fn with_easing(mut self, easing: Easing) -> Self {
+        self.easing = easing;
+        self
+    }
+
+    /// Progress through the effect in `[0.0, 1.0]`, before easing.
+    pub fn progress(&self) -> f32 {
+        if self.duration > 0.0 {
+            (self.elapsed / self.duration).min(1.0)
+        } else {
+            1.0
+        }
+    }
+}
+
+pub(crate) fn dolly_zoom_system(
+    mut commands: Commands,
+    time: Res<Time>,
+    timestep: Res<ControlTimestep>,
+    reduced_motion: Res<ReducedMotion>,
+    mut cameras: Query<(
+        Entity,
+        &mut DollyZoom,
+        &mut LookTransform,
+        &mut PerspectiveProjection,
+    )>,
+) {
+    for (entity, mut dolly_zoom, mut transform, mut projection) in cameras.iter_mut() {
+        // The effect starts from wherever the camera is on the first frame it's seen.
+        let (start_fov, start_radius) =
+            *dolly_zoom.start.get_or_insert((projection.fov, transform.radius()));
+        dolly_zoom.elapsed += timestep.frame_delta_seconds(&time);
+        if !reduced_motion.allows_inertia() {
+            dolly_zoom.elapsed = dolly_zoom.elapsed.max(dolly_zoom.duration);
+        }
+
+        let s = dolly_zoom.easing.apply(dolly_zoom.progress());
+        let fov = start_fov + s * (dolly_zoom.target_fov - start_fov);
+        projection.fov = fov;
+        transform.set_radius(dolly_zoom_radius(start_radius, start_fov, fov));
+
+        if dolly_zoom.progress() >= 1.0 {
+            commands.entity(entity).remove::<DollyZoom>();
+        }
+    }
+}
+
+// ████████╗███████╗███████╗████████╗
+// ╚══██╔══╝██╔════╝██╔════╝╚══██╔══╝
+//    ██║   █████╗  ███████╗   ██║
+//    ██║   ██╔══╝  ╚════██║   ██║
+//    ██║   ███████╗███████║   ██║
+//    ╚═╝   ╚══════╝╚══════╝   ╚═╝
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    use approx::assert_relative_eq;
+
+    #[test]
+    fn test_dolly_zoom_keeps_target_plane_size() {
+        let (radius, fov) = (10.0, 1.0);
+        let new_fov = 0.5;
+        let new_radius = dolly_zoom_radius(radius, fov, new_fov);
+
+        assert!(new_radius > radius);
+        assert_relative_eq!(
+            new_radius * (0.5 * new_fov).tan(),
+            radius * (0.5 * fov).tan(),
+            epsilon = 1e-5
+        );
+    }
+}
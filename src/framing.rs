@@ -0,0 +1,95 @@
+use bevy::{math::prelude::*, render::camera::OrthographicProjection};
+
+/// Returns the center and radius of a sphere that encloses the box between the `min` and `max` corners.
+pub fn bounding_sphere(min: Vec3, max: Vec3) -> (Vec3, f32) {
+    (0.5 * (min + max), 0.5 * (max - min).length())
+}
+
+/// Returns how far a perspective camera must be from the center of a sphere for the whole sphere to be in view, given the
+/// camera's projection matrix.
+pub fn perspective_framing_distance(sphere_radius: f32, projection_matrix: &Mat4) -> f32 {
+    // The diagonal of a perspective matrix holds the cotangent of the half field of view on each axis, and the sphere has to fit
+    // inside the narrower one.
+    let tan_half_fov = 1.0 / projection_matrix.x_axis.x.max(projection_matrix.y_axis.y);
+    let sin_half_fov = tan_half_fov / (1.0 + tan_half_fov * tan_half_fov).sqrt();
+
+    sphere_radius / sin_half_fov
+}
+
+/// Returns how many world units one pixel covers at `distance` in front of a camera, given the camera's projection matrix
+/// and the height of its viewport in pixels. For an orthographic camera, the distance doesn't matter.
+pub fn world_units_per_pixel(projection_matrix: &Mat4, distance: f32, viewport_height: f32) -> f32 {
+    // Clip space is two units tall. A perspective matrix divides by depth, so the view widens with distance, while an
+    // orthographic matrix, whose last column is (0, 0, 0, 1), doesn't.
+    let view_height = 2.0 / projection_matrix.y_axis.y;
+    let view_height = if projection_matrix.w_axis.w == 0.0 {
+        view_height * distance
+    } else {
+        view_height
+    };
+
+    view_height / viewport_height
+}
+
+/// Returns the `scale` that an `OrthographicProjection` needs for a sphere to fit in view.
+pub fn orthographic_framing_scale(sphere_radius: f32, projection: &OrthographicProjection) -> f32 {
+    let half_extent = 0.5 * (projection.right - projection.left).min(projection.top - projection.bottom);
+
+    sphere_radius / half_extent
+}
+
+// ████████╗███████╗███████╗████████╗
+// ╚══██╔══╝██╔════╝██╔════╝╚══██╔══╝
+//    ██║   █████╗  ███████╗   ██║
+//    ██║   ██╔══╝  ╚════██║   ██║
+//    ██║   ███████╗███████║   ██║
+//    ╚═╝   ╚══════╝╚══════╝   ╚═╝
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    use approx::assert_relative_eq;
+    use std::f32::consts::FRAC_PI_2;
+
+    #[test]
+    fn test_perspective_framing_distance() {
+        let (center, radius) = bounding_sphere(-Vec3::ONE, Vec3::ONE);
+        assert_eq!(center, Vec3::ZERO);
+        assert_relative_eq!(radius, 3.0f32.sqrt());
+
+        // With a 90 degree field of view, the sphere touches the view at 45 degrees from the look direction.
+        let projection_matrix = Mat4::perspective_rh(FRAC_PI_2, 2.0, 0.1, 100.0);
+        assert_relative_eq!(
+            perspective_framing_distance(1.0, &projection_matrix),
+            2.0f32.sqrt(),
+            epsilon = 1e-5
+        );
+    }
+
+    #[test]
+    fn test_orthographic_framing_scale() {
+        let projection = OrthographicProjection {
+            left: -200.0,
+            right: 200.0,
+            bottom: -100.0,
+            top: 100.0,
+            ..Default::default()
+        };
+
+        // The shorter side is 200 units at a scale of one, so a sphere with a radius of 50 fits at half that.
+        assert_relative_eq!(orthographic_framing_scale(50.0, &projection), 0.5);
+        assert_relative_eq!(orthographic_framing_scale(100.0, &projection), 1.0);
+    }
+
+    #[test]
+    fn test_world_units_per_pixel() {
+        // A 90 degree field of view is twice the distance tall.
+        let perspective = Mat4::perspective_rh(FRAC_PI_2, 2.0, 0.1, 100.0);
+        assert_relative_eq!(world_units_per_pixel(&perspective, 5.0, 500.0), 0.02, epsilon = 1e-6);
+
+        let orthographic = Mat4::orthographic_rh(-200.0, 200.0, -100.0, 100.0, 0.0, 1000.0);
+        assert_relative_eq!(world_units_per_pixel(&orthographic, 5.0, 200.0), 1.0, epsilon = 1e-6);
+        assert_relative_eq!(world_units_per_pixel(&orthographic, 50.0, 400.0), 0.5, epsilon = 1e-6);
+    }
+}
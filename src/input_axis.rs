@@ -0,0 +1,95 @@
+use bevy::{math::prelude::*, reflect::Reflect};
+use serde::{Deserialize, Serialize};
+
+/// Settings for one two-axis input of a controller, like mouse motion, a gamepad stick, a touch drag, or panning with the
+/// keys. Most controller inputs use this type, so sensitivity, inversion, and dead zones work the same everywhere and can
+/// be saved with the rest of a player's settings.
+#[derive(Clone, Copy, Debug, Deserialize, PartialEq, Reflect, Serialize)]
+#[reflect_value(PartialEq, Serialize, Deserialize)]
+pub struct InputAxisConfig {
+    /// Scales the horizontal (X) and vertical (Y) axes.
+    pub sensitivity: Vec2,
+    /// Flips the horizontal axis.
+    pub invert_x: bool,
+    /// Flips the vertical axis.
+    pub invert_y: bool,
+    /// Inputs shorter than this are ignored, and longer ones are rescaled so the output still ramps up from zero. This is
+    /// meant for sticks, whose input is at most `1.0` long; `0.0` turns it off.
+    pub deadzone: f32,
+}
+
+impl Default for InputAxisConfig {
+    fn default() -> Self {
+        Self::new(Vec2::ONE)
+    }
+}
+
+impl InputAxisConfig {
+    pub fn new(sensitivity: Vec2) -> Self {
+        Self {
+            sensitivity,
+            invert_x: false,
+            invert_y: false,
+            deadzone: 0.0,
+        }
+    }
+
+    pub fn with_deadzone(mut self, deadzone: f32) -> Self {
+        self.deadzone = deadzone;
+        self
+    }
+
+    /// Applies the dead zone, then inversion, then sensitivity to `input`.
+    pub fn apply(&self, input: Vec2) -> Vec2 {
+        self.sensitivity * self.invert(apply_deadzone(input, self.deadzone))
+    }
+
+    /// Applies only the inversion to `input`, for inputs that must keep their scale, like pixel-perfect panning.
+    pub fn invert(&self, input: Vec2) -> Vec2 {
+        Vec2::new(
+            if self.invert_x { -input.x } else { input.x },
+            if self.invert_y { -input.y } else { input.y },
+        )
+    }
+}
+
+/// Zeroes deflections inside the deadzone and rescales the rest so the output still ramps up from zero.
+fn apply_deadzone(stick: Vec2, deadzone: f32) -> Vec2 {
+    if deadzone <= 0.0 {
+        return stick;
+    }
+
+    let length = stick.length();
+    if length <= deadzone {
+        return Vec2::ZERO;
+    }
+    let scaled_length = ((length - deadzone) / (1.0 - deadzone)).min(1.0);
+
+    stick * (scaled_length / length)
+}
+
+// ████████╗███████╗███████╗████████╗
+// ╚══██╔══╝██╔════╝██╔════╝╚══██╔══╝
+//    ██║   █████╗  ███████╗   ██║
+//    ██║   ██╔══╝  ╚════██║   ██║
+//    ██║   ███████╗███████║   ██║
+//    ╚═╝   ╚══════╝╚══════╝   ╚═╝
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_apply_deadzone_inversion_and_sensitivity() {
+        let mut config = InputAxisConfig::new(Vec2::new(2.0, 3.0)).with_deadzone(0.2);
+        config.invert_y = true;
+
+        assert_eq!(config.apply(Vec2::new(0.1, 0.1)), Vec2::ZERO);
+        assert!(config.apply(Vec2::new(1.0, 0.0)).abs_diff_eq(Vec2::new(2.0, 0.0), 1e-5));
+        assert!(config.apply(Vec2::new(0.0, 0.6)).abs_diff_eq(Vec2::new(0.0, -1.5), 1e-5));
+
+        // Without a dead zone, mouse deltas longer than `1.0` pass through.
+        let mouse = InputAxisConfig::new(Vec2::splat(0.5));
+        assert_eq!(mouse.apply(Vec2::new(10.0, -4.0)), Vec2::new(5.0, -2.0));
+    }
+}
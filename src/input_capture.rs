@@ -0,0 +1,19 @@
+/// A resource that tells the input-mapping systems when some other part of the app, like a UI, is using the mouse or keyboard.
+/// While input is captured, the controllers won't react to it, so e.g. scrolling a UI panel doesn't also zoom the camera.
+///
+/// Set these flags from your UI code each frame, before `LookTransformSystem::InputMap`. With egui, that's:
+///
+/// ```rust
+/// fn capture_egui_input(egui_context: Res<EguiContext>, mut input_capture: ResMut<InputCaptureState>) {
+///     let ctx = egui_context.ctx();
+///     input_capture.pointer_captured = ctx.wants_pointer_input() || ctx.is_pointer_over_area();
+///     input_capture.keyboard_captured = ctx.wants_keyboard_input();
+/// }
+/// ```
+#[derive(Clone, Copy, Debug, Default)]
+pub struct InputCaptureState {
+    /// Mouse motion, buttons, scrolling, and touches are being used by something else.
+    pub pointer_captured: bool,
+    /// Keyboard input is being used by something else.
+    pub keyboard_captured: bool,
+}
@@ -0,0 +1,164 @@
+use bevy::{ecs::entity::Entity, math::prelude::*, reflect::Reflect};
+use serde::{Deserialize, Serialize};
+use std::{
+    collections::{HashMap, VecDeque},
+    f32::consts::PI,
+};
+
+/// Filters out jitter in mouse motion before a controller maps it, e.g. from high polling rate mice. This is separate from
+/// the `Smoother`, which smooths the camera's pose after the controller has moved it.
+#[derive(Clone, Copy, Debug, Deserialize, PartialEq, Reflect, Serialize)]
+#[reflect_value(PartialEq, Serialize, Deserialize)]
+pub enum InputFilter {
+    /// Passes motion through untouched.
+    None,
+    /// Averages the motion of the last `window` input-mapping steps. Every step's motion is spread over the following
+    /// steps, so the total is preserved, but input lags by about half the window.
+    MovingAverage { window: usize },
+    /// The 1€ filter, which smooths heavily while the mouse moves slowly and hardly at all while it moves quickly. It
+    /// filters the pointer's position, summed from its motion, so the speed it responds to is in pixels per second.
+    OneEuro {
+        /// The cutoff frequency in hertz at low speeds. Lower values remove more jitter.
+        min_cutoff: f32,
+        /// How much the cutoff frequency rises, in hertz, per pixel per second of speed. Higher values reduce lag on quick
+        /// motions.
+        beta: f32,
+        /// The cutoff frequency in hertz for estimating speed.
+        derivative_cutoff: f32,
+    },
+}
+
+impl Default for InputFilter {
+    fn default() -> Self {
+        Self::None
+    }
+}
+
+impl InputFilter {
+    /// A 1€ filter with reasonable settings for mouse motion.
+    pub fn one_euro() -> Self {
+        Self::OneEuro {
+            min_cutoff: 1.0,
+            beta: 0.01,
+            derivative_cutoff: 1.0,
+        }
+    }
+}
+
+/// The history that each camera's `InputFilter` needs, kept by the input-mapping systems.
+#[derive(Default)]
+pub struct InputFilterState {
+    cameras: HashMap<Entity, FilterHistory>,
+}
+
+#[derive(Default)]
+struct FilterHistory {
+    samples: VecDeque<Vec2>,
+    /// How far the filtered position trails the unfiltered one, once the filter has started.
+    lag: Option<Vec2>,
+    derivative: Vec2,
+}
+
+impl InputFilterState {
+    /// Drops the history of every camera that `keep` returns false for, e.g. ones that were despawned.
+    pub(crate) fn retain(&mut self, keep: impl Fn(Entity) -> bool) {
+        self.cameras.retain(|&camera, _| keep(camera));
+    }
+
+    /// Filters `delta`, the motion since the last step. Call this every step for each enabled camera, even when there's no
+    /// motion, so the filter settles once the mouse stops.
+    pub fn apply(&mut self, camera: Entity, filter: InputFilter, delta: Vec2, dt: f32) -> Vec2 {
+        let history = self.cameras.entry(camera).or_default();
+        match filter {
+            InputFilter::None => delta,
+            InputFilter::MovingAverage { window } => {
+                let window = window.max(1);
+                history.samples.push_back(delta);
+                while history.samples.len() > window {
+                    history.samples.pop_front();
+                }
+
+                history.samples.iter().fold(Vec2::ZERO, |sum, &d| sum + d) / window as f32
+            }
+            InputFilter::OneEuro {
+                min_cutoff,
+                beta,
+                derivative_cutoff,
+            } => {
+                // The filter is meant for positions, so it runs on the summed motion and returns how far its output moved.
+                // Only the distance between the filtered and unfiltered positions is kept, so nothing grows without bound.
+                let lag = match history.lag {
+                    Some(lag) if dt > 0.0 => lag + delta,
+                    _ => {
+                        history.lag = Some(Vec2::ZERO);
+                        return delta;
+                    }
+                };
+
+                let derivative = lag / dt;
+                history.derivative = history
+                    .derivative
+                    .lerp(derivative, smoothing_factor(derivative_cutoff, dt));
+                let cutoff = min_cutoff + beta * history.derivative.length();
+                let moved = lag * smoothing_factor(cutoff, dt);
+                history.lag = Some(lag - moved);
+
+                moved
+            }
+        }
+    }
+}
+
+/// The weight of a new sample in an exponential low-pass filter with the given cutoff frequency.
+fn smoothing_factor(cutoff: f32, dt: f32) -> f32 {
+    let tau = 1.0 / (2.0 * PI * cutoff.max(f32::EPSILON));
+
+    1.0 / (1.0 + tau / dt)
+}
+
+// ████████╗███████╗███████╗████████╗
+// ╚══██╔══╝██╔════╝██╔════╝╚══██╔══╝
+//    ██║   █████╗  ███████╗   ██║
+//    ██║   ██╔══╝  ╚════██║   ██║
+//    ██║   ███████╗███████║   ██║
+//    ╚═╝   ╚══════╝╚══════╝   ╚═╝
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_filters_smooth_jitter() {
+        let camera = Entity::new(0);
+        let dt = 1.0 / 60.0;
+        let jitter = |i: usize| Vec2::new(if i % 2 == 0 { 2.0 } else { 0.0 }, 1.0);
+
+        let mut state = InputFilterState::default();
+        let average = InputFilter::MovingAverage { window: 2 };
+        state.apply(camera, average, jitter(0), dt);
+        for i in 1..10 {
+            assert_eq!(state.apply(camera, average, jitter(i), dt), Vec2::new(1.0, 1.0));
+        }
+
+        // The 1€ filter keeps up with the average motion, and damps the jitter on top of it.
+        let mut state = InputFilterState::default();
+        let mut last = [Vec2::ZERO; 2];
+        for i in 0..120 {
+            last[i % 2] = state.apply(camera, InputFilter::one_euro(), jitter(i), dt);
+        }
+        assert!(((last[0] + last[1]) / 2.0).abs_diff_eq(Vec2::ONE, 1e-3));
+        assert!((last[0].x - last[1].x).abs() < 0.5);
+    }
+
+    #[test]
+    fn test_removed_cameras_are_forgotten() {
+        let (kept, removed) = (Entity::new(0), Entity::new(1));
+        let mut state = InputFilterState::default();
+        state.apply(kept, InputFilter::one_euro(), Vec2::ONE, 1.0 / 60.0);
+        state.apply(removed, InputFilter::one_euro(), Vec2::ONE, 1.0 / 60.0);
+
+        state.retain(|camera| camera == kept);
+        assert!(state.cameras.contains_key(&kept));
+        assert!(!state.cameras.contains_key(&removed));
+    }
+}
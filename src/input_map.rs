@@ -0,0 +1,557 @@
+use crate::{InputSource, Presses};
+use bevy::input::{
+    gamepad::{GamepadAxis, GamepadAxisType, GamepadButton, GamepadButtonType, Gamepads},
+    keyboard::KeyCode,
+    mouse::MouseButton,
+    Axis, Input,
+};
+use serde::{Deserialize, Serialize};
+use std::collections::HashMap;
+
+/// How far a gamepad axis has to be pushed for its binding to count as pressed.
+const GAMEPAD_AXIS_PRESS_THRESHOLD: f32 = 0.5;
+
+/// Something a camera can be told to do by a key, button, or stick, independent of which one it's bound to.
+#[derive(Clone, Copy, Debug, Deserialize, Eq, Hash, PartialEq, Serialize)]
+pub enum CameraAction {
+    MoveForward,
+    MoveBack,
+    MoveLeft,
+    MoveRight,
+    MoveUp,
+    MoveDown,
+    PanLeft,
+    PanRight,
+    PanUp,
+    PanDown,
+    RotateLeft,
+    RotateRight,
+    RollLeft,
+    RollRight,
+    /// Turns the FPS camera, at a speed given by how far the binding is pushed.
+    LookLeft,
+    LookRight,
+    LookUp,
+    LookDown,
+    /// Multiplies the FPS camera's speed by its `sprint_multiplier` while held.
+    Sprint,
+    /// Divides the FPS camera's speed by its `slow_divisor` while held.
+    Slow,
+    /// Looks around with the mouse while held, when the FPS camera can't lock the cursor.
+    DragLook,
+    /// Narrows the FPS camera's field of view while held, if it has an `FpsZoom`.
+    Aim,
+    /// Grabs or releases the cursor of an FPS camera with `grab_cursor` set.
+    ToggleCursorGrab,
+    /// Orbits the orbit camera by dragging with a mouse binding.
+    Orbit,
+    /// Pans the orbit camera by dragging with a mouse binding.
+    Pan,
+    /// Zooms the orbit camera by dragging vertically with a mouse binding.
+    Zoom,
+    /// Pans a PanCam by dragging with a mouse binding, so the grabbed point stays under the cursor.
+    Grab,
+    /// Steps the orbit camera around by its `keyboard_orbit_step` on each press.
+    OrbitLeft,
+    OrbitRight,
+    OrbitUp,
+    OrbitDown,
+    /// Snaps the orbit camera to `StandardView::Front`.
+    FrontView,
+    /// Snaps the orbit camera to `StandardView::Right`.
+    RightView,
+    /// Snaps the orbit camera to `StandardView::Top`.
+    TopView,
+    /// Zooms the orbit camera in while held.
+    ZoomIn,
+    /// Zooms the orbit camera out while held.
+    ZoomOut,
+    /// Makes the orbit camera's vertical wheel pan sideways instead of zooming while held, if it has
+    /// `horizontal_wheel_enabled`.
+    WheelPan,
+}
+
+/// Modifier keys that must be held for a binding to trigger. Either the left or the right key counts.
+#[derive(Clone, Copy, Debug, Default, Deserialize, Eq, Hash, PartialEq, Serialize)]
+pub struct ModifierKeys {
+    pub shift: bool,
+    pub ctrl: bool,
+    pub alt: bool,
+}
+
+impl ModifierKeys {
+    pub const NONE: Self = Self {
+        shift: false,
+        ctrl: false,
+        alt: false,
+    };
+    pub const SHIFT: Self = Self {
+        shift: true,
+        ctrl: false,
+        alt: false,
+    };
+    pub const CTRL: Self = Self {
+        shift: false,
+        ctrl: true,
+        alt: false,
+    };
+    pub const ALT: Self = Self {
+        shift: false,
+        ctrl: false,
+        alt: true,
+    };
+
+    /// The modifier keys that are currently held.
+    pub fn pressed(keyboard: &Input<KeyCode>) -> Self {
+        Self {
+            shift: keyboard.pressed(KeyCode::LShift) || keyboard.pressed(KeyCode::RShift),
+            ctrl: keyboard.pressed(KeyCode::LControl) || keyboard.pressed(KeyCode::RControl),
+            alt: keyboard.pressed(KeyCode::LAlt) || keyboard.pressed(KeyCode::RAlt),
+        }
+    }
+}
+
+/// Which way a gamepad axis has to be pushed for its binding to trigger.
+#[derive(Clone, Copy, Debug, Deserialize, Eq, Hash, PartialEq, Serialize)]
+pub enum AxisDirection {
+    Positive,
+    Negative,
+}
+
+/// A key, mouse button, or gamepad input that triggers a `CameraAction`.
+#[derive(Clone, Copy, Debug, Deserialize, Eq, PartialEq, Serialize)]
+pub enum InputBinding {
+    /// A key, whatever modifier keys are held with it.
+    Key(KeyCode),
+    /// A mouse button, whatever modifier keys are held with it.
+    Mouse(MouseButton),
+    /// A key held with exactly these modifier keys, and no others.
+    ModifiedKey(KeyCode, ModifierKeys),
+    /// A mouse button held with exactly these modifier keys, and no others, e.g. Shift + middle drag. For drags, the
+    /// modifiers are only checked when the button is pressed, so letting go of them mid-drag doesn't switch actions.
+    ModifiedMouse(MouseButton, ModifierKeys),
+    /// A button of any gamepad that the camera's `InputSource` accepts.
+    GamepadButton(GamepadButtonType),
+    /// A stick or trigger axis of any gamepad that the camera's `InputSource` accepts, pushed in this direction. Held
+    /// actions follow how far it's pushed; actions that trigger on a press ignore it.
+    GamepadAxis(GamepadAxisType, AxisDirection),
+}
+
+/// The devices that `InputBinding`s are read from, and the `InputSource` of the camera that reads them.
+#[derive(Clone, Copy)]
+pub struct BindingInput<'a> {
+    pub keyboard: &'a Input<KeyCode>,
+    pub mouse_buttons: &'a Input<MouseButton>,
+    pub gamepads: &'a Gamepads,
+    pub gamepad_axes: &'a Axis<GamepadAxis>,
+    pub gamepad_buttons: &'a Input<GamepadButton>,
+    pub source: InputSource,
+}
+
+impl<'a> BindingInput<'a> {
+    /// Reads every device, like `InputSource::Any`.
+    pub fn new(
+        keyboard: &'a Input<KeyCode>,
+        mouse_buttons: &'a Input<MouseButton>,
+        gamepads: &'a Gamepads,
+        gamepad_axes: &'a Axis<GamepadAxis>,
+        gamepad_buttons: &'a Input<GamepadButton>,
+    ) -> Self {
+        Self {
+            keyboard,
+            mouse_buttons,
+            gamepads,
+            gamepad_axes,
+            gamepad_buttons,
+            source: InputSource::Any,
+        }
+    }
+
+    /// Only reads the devices that `source` accepts.
+    pub fn with_source(self, source: InputSource) -> Self {
+        Self { source, ..self }
+    }
+
+    pub(crate) fn modifiers_match(&self, modifiers: ModifierKeys) -> bool {
+        ModifierKeys::pressed(self.keyboard) == modifiers
+    }
+}
+
+impl InputBinding {
+    /// How far the binding is pushed, between `0.0` and `1.0`. Keys and buttons are either all the way or not at all.
+    pub fn value(&self, input: &BindingInput) -> f32 {
+        let keyboard_mouse = input.source.accepts_keyboard_mouse();
+        let held = |down: bool| if down { 1.0 } else { 0.0 };
+        match *self {
+            Self::Key(key) => held(keyboard_mouse && input.keyboard.pressed(key)),
+            Self::Mouse(button) => held(keyboard_mouse && input.mouse_buttons.pressed(button)),
+            Self::ModifiedKey(key, modifiers) => held(
+                keyboard_mouse && input.keyboard.pressed(key) && input.modifiers_match(modifiers),
+            ),
+            Self::ModifiedMouse(button, modifiers) => held(
+                keyboard_mouse
+                    && input.mouse_buttons.pressed(button)
+                    && input.modifiers_match(modifiers),
+            ),
+            Self::GamepadButton(button_type) => held(
+                input
+                    .gamepads
+                    .iter()
+                    .filter(|&&gamepad| input.source.accepts_gamepad(gamepad))
+                    .any(|&gamepad| {
+                        input
+                            .gamepad_buttons
+                            .pressed(GamepadButton(gamepad, button_type))
+                    }),
+            ),
+            Self::GamepadAxis(axis_type, direction) => {
+                let sign = match direction {
+                    AxisDirection::Positive => 1.0,
+                    AxisDirection::Negative => -1.0,
+                };
+                input
+                    .gamepads
+                    .iter()
+                    .filter(|&&gamepad| input.source.accepts_gamepad(gamepad))
+                    .filter_map(|&gamepad| input.gamepad_axes.get(GamepadAxis(gamepad, axis_type)))
+                    .map(|value| (sign * value).max(0.0).min(1.0))
+                    .fold(0.0, f32::max)
+            }
+        }
+    }
+
+    pub fn pressed(&self, input: &BindingInput) -> bool {
+        match self {
+            Self::GamepadAxis(..) => self.value(input) >= GAMEPAD_AXIS_PRESS_THRESHOLD,
+            _ => self.value(input) > 0.0,
+        }
+    }
+
+    /// Whether the binding was pressed since `presses` were last read. Gamepad buttons only count this frame's presses,
+    /// and gamepad axes never trigger a press.
+    pub fn just_pressed(&self, presses: &Presses, input: &BindingInput) -> bool {
+        let keyboard_mouse = input.source.accepts_keyboard_mouse();
+        match *self {
+            Self::Key(key) => keyboard_mouse && presses.just_pressed(key),
+            Self::Mouse(button) => keyboard_mouse && presses.just_pressed_mouse(button),
+            Self::ModifiedKey(key, modifiers) => {
+                keyboard_mouse && presses.just_pressed(key) && input.modifiers_match(modifiers)
+            }
+            Self::ModifiedMouse(button, modifiers) => {
+                keyboard_mouse
+                    && presses.just_pressed_mouse(button)
+                    && input.modifiers_match(modifiers)
+            }
+            Self::GamepadButton(button_type) => input
+                .gamepads
+                .iter()
+                .filter(|&&gamepad| input.source.accepts_gamepad(gamepad))
+                .any(|&gamepad| {
+                    input
+                        .gamepad_buttons
+                        .just_pressed(GamepadButton(gamepad, button_type))
+                }),
+            Self::GamepadAxis(..) => false,
+        }
+    }
+
+    /// The mouse button of a mouse binding, and the modifier keys it needs, if any, for starting drags.
+    pub fn mouse_button(&self) -> Option<(MouseButton, Option<ModifierKeys>)> {
+        match *self {
+            Self::Mouse(button) => Some((button, None)),
+            Self::ModifiedMouse(button, modifiers) => Some((button, Some(modifiers))),
+            _ => None,
+        }
+    }
+}
+
+/// The bindings of the built-in controllers, so they can be rebound at runtime, e.g. from a settings menu. An action can
+/// have any number of bindings, and triggers while any of them is held.
+///
+/// As a resource, it holds the bindings of every camera. Add one to a camera as a component to give that camera its own
+/// bindings instead, e.g. so local players can each use their own keys.
+///
+/// The free-fly controller moves with the `Move` actions and rolls with the `Roll` ones, the isometric controller pans
+/// with the `Move` actions, and the RTS controller turns with the `Rotate` ones. The FPS controller moves with the `Move`
+/// actions, turns with the `Look` ones, and uses `Sprint`, `Slow`, `DragLook`, `Aim`, and `ToggleCursorGrab`. The orbit
+/// controller drags with `Orbit`, `Pan`, and `Zoom`, pans with the `Pan` direction actions, and uses the `Orbit` direction
+/// actions, the views, and `ZoomIn` and `ZoomOut` when its keyboard controls are on. PanCam drags with `Grab`. The chase,
+/// follow, and rail controllers only take mouse motion, so they have no bindings.
+///
+/// ```rust
+/// fn use_arrow_keys(mut input_map: ResMut<CameraInputMap>) {
+///     input_map.rebind(CameraAction::MoveForward, InputBinding::Key(KeyCode::Up));
+/// }
+/// ```
+#[derive(Clone, Debug, Deserialize, PartialEq, Serialize)]
+pub struct CameraInputMap {
+    bindings: HashMap<CameraAction, Vec<InputBinding>>,
+}
+
+impl Default for CameraInputMap {
+    fn default() -> Self {
+        let mut input_map = Self::empty();
+        for (action, key) in [
+            (CameraAction::MoveForward, KeyCode::W),
+            (CameraAction::MoveBack, KeyCode::S),
+            (CameraAction::MoveLeft, KeyCode::A),
+            (CameraAction::MoveRight, KeyCode::D),
+            (CameraAction::MoveUp, KeyCode::Space),
+            (CameraAction::MoveDown, KeyCode::LShift),
+            (CameraAction::PanLeft, KeyCode::Left),
+            (CameraAction::PanRight, KeyCode::Right),
+            (CameraAction::PanUp, KeyCode::Up),
+            (CameraAction::PanDown, KeyCode::Down),
+            (CameraAction::RotateLeft, KeyCode::Q),
+            (CameraAction::RotateRight, KeyCode::E),
+            (CameraAction::RollLeft, KeyCode::Q),
+            (CameraAction::RollRight, KeyCode::E),
+            (CameraAction::Sprint, KeyCode::LControl),
+            (CameraAction::Slow, KeyCode::C),
+            (CameraAction::ToggleCursorGrab, KeyCode::Escape),
+            // The numpad, like Blender.
+            (CameraAction::OrbitLeft, KeyCode::Numpad4),
+            (CameraAction::OrbitRight, KeyCode::Numpad6),
+            (CameraAction::OrbitUp, KeyCode::Numpad8),
+            (CameraAction::OrbitDown, KeyCode::Numpad2),
+            (CameraAction::FrontView, KeyCode::Numpad1),
+            (CameraAction::RightView, KeyCode::Numpad3),
+            (CameraAction::TopView, KeyCode::Numpad7),
+            (CameraAction::ZoomIn, KeyCode::NumpadAdd),
+            (CameraAction::ZoomOut, KeyCode::NumpadSubtract),
+            (CameraAction::WheelPan, KeyCode::LShift),
+            (CameraAction::WheelPan, KeyCode::RShift),
+        ]
+        .iter()
+        .cloned()
+        {
+            input_map.bind(action, InputBinding::Key(key));
+        }
+
+        for (action, button) in [
+            (CameraAction::DragLook, MouseButton::Left),
+            (CameraAction::Aim, MouseButton::Right),
+            (CameraAction::Orbit, MouseButton::Left),
+            (CameraAction::Pan, MouseButton::Middle),
+            (CameraAction::Grab, MouseButton::Left),
+        ]
+        .iter()
+        .cloned()
+        {
+            input_map.bind(action, InputBinding::Mouse(button));
+        }
+
+        // The left stick moves and the right stick looks around.
+        for (action, axis_type, direction) in [
+            (CameraAction::MoveForward, GamepadAxisType::LeftStickY, AxisDirection::Positive),
+            (CameraAction::MoveBack, GamepadAxisType::LeftStickY, AxisDirection::Negative),
+            (CameraAction::MoveLeft, GamepadAxisType::LeftStickX, AxisDirection::Negative),
+            (CameraAction::MoveRight, GamepadAxisType::LeftStickX, AxisDirection::Positive),
+            (CameraAction::LookLeft, GamepadAxisType::RightStickX, AxisDirection::Negative),
+            (CameraAction::LookRight, GamepadAxisType::RightStickX, AxisDirection::Positive),
+            (CameraAction::LookUp, GamepadAxisType::RightStickY, AxisDirection::Positive),
+            (CameraAction::LookDown, GamepadAxisType::RightStickY, AxisDirection::Negative),
+        ]
+        .iter()
+        .cloned()
+        {
+            input_map.bind(action, InputBinding::GamepadAxis(axis_type, direction));
+        }
+
+        for (action, button_type) in [
+            (CameraAction::MoveUp, GamepadButtonType::RightTrigger),
+            (CameraAction::MoveUp, GamepadButtonType::RightTrigger2),
+            (CameraAction::MoveDown, GamepadButtonType::LeftTrigger),
+            (CameraAction::MoveDown, GamepadButtonType::LeftTrigger2),
+        ]
+        .iter()
+        .cloned()
+        {
+            input_map.bind(action, InputBinding::GamepadButton(button_type));
+        }
+
+        input_map
+    }
+}
+
+impl CameraInputMap {
+    /// A map without any bindings.
+    pub fn empty() -> Self {
+        Self {
+            bindings: HashMap::new(),
+        }
+    }
+
+    /// Like Blender: middle drag orbits, Shift + middle drag pans, and Ctrl + middle drag zooms.
+    pub fn blender() -> Self {
+        let mut input_map = Self::default();
+        for (action, modifiers) in [
+            (CameraAction::Orbit, ModifierKeys::NONE),
+            (CameraAction::Pan, ModifierKeys::SHIFT),
+            (CameraAction::Zoom, ModifierKeys::CTRL),
+        ]
+        .iter()
+        .cloned()
+        {
+            input_map.rebind(action, InputBinding::ModifiedMouse(MouseButton::Middle, modifiers));
+        }
+
+        input_map
+    }
+
+    /// The bindings of `action`, in the order they were added.
+    pub fn bindings(&self, action: CameraAction) -> &[InputBinding] {
+        self.bindings.get(&action).map(Vec::as_slice).unwrap_or(&[])
+    }
+
+    /// Adds `binding` to those of `action`.
+    pub fn bind(&mut self, action: CameraAction, binding: InputBinding) {
+        let bindings = self.bindings.entry(action).or_default();
+        if !bindings.contains(&binding) {
+            bindings.push(binding);
+        }
+    }
+
+    /// Replaces the bindings of `action` with `binding`.
+    pub fn rebind(&mut self, action: CameraAction, binding: InputBinding) {
+        self.bindings.insert(action, vec![binding]);
+    }
+
+    /// Removes every binding of `action`, so it can't be triggered.
+    pub fn unbind(&mut self, action: CameraAction) {
+        self.bindings.remove(&action);
+    }
+
+    /// Whether any binding of `action` is held.
+    pub fn pressed(&self, action: CameraAction, input: &BindingInput) -> bool {
+        self.bindings(action)
+            .iter()
+            .any(|binding| binding.pressed(input))
+    }
+
+    /// How far the bindings of `action` are pushed, between `0.0` and `1.0`, taking the furthest of them.
+    pub fn value(&self, action: CameraAction, input: &BindingInput) -> f32 {
+        self.bindings(action)
+            .iter()
+            .map(|binding| binding.value(input))
+            .fold(0.0, f32::max)
+    }
+
+    /// The value of `positive` minus that of `negative`, e.g. how far a stick is pushed to the right when given
+    /// `LookRight` and `LookLeft`.
+    pub fn axis(
+        &self,
+        positive: CameraAction,
+        negative: CameraAction,
+        input: &BindingInput,
+    ) -> f32 {
+        self.value(positive, input) - self.value(negative, input)
+    }
+
+    /// Whether any binding of `action` was pressed since `presses` were last read.
+    pub fn just_pressed(
+        &self,
+        action: CameraAction,
+        presses: &Presses,
+        input: &BindingInput,
+    ) -> bool {
+        self.bindings(action)
+            .iter()
+            .any(|binding| binding.just_pressed(presses, input))
+    }
+}
+
+// ████████╗███████╗███████╗████████╗
+// ╚══██╔══╝██╔════╝██╔════╝╚══██╔══╝
+//    ██║   █████╗  ███████╗   ██║
+//    ██║   ██╔══╝  ╚════██║   ██║
+//    ██║   ███████╗███████║   ██║
+//    ╚═╝   ╚══════╝╚══════╝   ╚═╝
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use bevy::input::gamepad::Gamepad;
+
+    #[test]
+    fn test_rebinding_replaces_the_default_keys() {
+        let mut keyboard = Input::default();
+        let (mouse_buttons, gamepads, gamepad_axes, gamepad_buttons) =
+            (Input::default(), Gamepads::default(), Axis::default(), Input::default());
+        let mut input_map = CameraInputMap::default();
+
+        keyboard.press(KeyCode::W);
+        let input = BindingInput::new(
+            &keyboard,
+            &mouse_buttons,
+            &gamepads,
+            &gamepad_axes,
+            &gamepad_buttons,
+        );
+        assert!(input_map.pressed(CameraAction::MoveForward, &input));
+
+        input_map.rebind(CameraAction::MoveForward, InputBinding::Key(KeyCode::Up));
+        assert!(!input_map.pressed(CameraAction::MoveForward, &input));
+
+        input_map.bind(CameraAction::MoveForward, InputBinding::Key(KeyCode::W));
+        assert!(input_map.pressed(CameraAction::MoveForward, &input));
+        assert_eq!(input_map.bindings(CameraAction::MoveForward).len(), 2);
+
+        input_map.unbind(CameraAction::MoveForward);
+        assert!(!input_map.pressed(CameraAction::MoveForward, &input));
+    }
+
+    #[test]
+    fn test_mouse_buttons_trigger_actions() {
+        let keyboard = Input::default();
+        let mut mouse_buttons = Input::default();
+        let (gamepads, gamepad_axes, gamepad_buttons) =
+            (Gamepads::default(), Axis::default(), Input::default());
+        let mut input_map = CameraInputMap::empty();
+        input_map.bind(CameraAction::MoveForward, InputBinding::Mouse(MouseButton::Right));
+
+        mouse_buttons.press(MouseButton::Right);
+        let input = BindingInput::new(
+            &keyboard,
+            &mouse_buttons,
+            &gamepads,
+            &gamepad_axes,
+            &gamepad_buttons,
+        );
+        assert!(input_map.pressed(CameraAction::MoveForward, &input));
+        assert!(!input_map.pressed(CameraAction::MoveBack, &input));
+
+        // Keyboard and mouse bindings are ignored for a camera that only takes a gamepad.
+        let gamepad_only = input.with_source(InputSource::Gamepad(Gamepad(0)));
+        assert!(!input_map.pressed(CameraAction::MoveForward, &gamepad_only));
+    }
+
+    #[test]
+    fn test_modified_bindings_need_exactly_their_modifiers() {
+        let mut keyboard = Input::default();
+        let mut mouse_buttons = Input::default();
+        let (gamepads, gamepad_axes, gamepad_buttons) =
+            (Gamepads::default(), Axis::default(), Input::default());
+        let input_map = CameraInputMap::blender();
+
+        mouse_buttons.press(MouseButton::Middle);
+        keyboard.press(KeyCode::RShift);
+        let input = BindingInput::new(
+            &keyboard,
+            &mouse_buttons,
+            &gamepads,
+            &gamepad_axes,
+            &gamepad_buttons,
+        );
+        assert!(input_map.pressed(CameraAction::Pan, &input));
+        assert!(!input_map.pressed(CameraAction::Orbit, &input));
+        assert!(!input_map.pressed(CameraAction::Zoom, &input));
+
+        keyboard.press(KeyCode::LControl);
+        let input = BindingInput {
+            keyboard: &keyboard,
+            ..input
+        };
+        assert!(!input_map.pressed(CameraAction::Pan, &input));
+    }
+}
@@ -0,0 +1,40 @@
+use bevy::{input::gamepad::Gamepad, reflect::Reflect};
+use serde::{Deserialize, Serialize};
+
+/// The devices that a controller takes input from. For local multiplayer, e.g. split-screen, give each player's camera its
+/// own source so one can be driven by the keyboard and mouse and another by a specific gamepad.
+///
+/// Every controller reads its gamepad controls through the `CameraInputMap`, so a camera set to a gamepad only follows that
+/// gamepad's bindings and ignores the keyboard, mouse, and touch screen.
+#[derive(Clone, Copy, Debug, Deserialize, Eq, PartialEq, Reflect, Serialize)]
+#[reflect_value(PartialEq, Serialize, Deserialize)]
+pub enum InputSource {
+    /// The keyboard, mouse, touch screen, and every gamepad.
+    Any,
+    /// The keyboard, mouse, and touch screen, but no gamepads.
+    KeyboardMouse,
+    /// Only this gamepad.
+    Gamepad(Gamepad),
+}
+
+impl Default for InputSource {
+    fn default() -> Self {
+        Self::Any
+    }
+}
+
+impl InputSource {
+    /// Whether keyboard, mouse, and touch input should drive the controller.
+    pub fn accepts_keyboard_mouse(self) -> bool {
+        matches!(self, Self::Any | Self::KeyboardMouse)
+    }
+
+    /// Whether input from `gamepad` should drive the controller.
+    pub fn accepts_gamepad(self, gamepad: Gamepad) -> bool {
+        match self {
+            Self::Any => true,
+            Self::KeyboardMouse => false,
+            Self::Gamepad(source) => source == gamepad,
+        }
+    }
+}
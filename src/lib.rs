@@ -7,6 +7,13 @@
 //!
 //! Any entities with `{Transform, LookTransform, Smoother}` components will automatically have their `Transform` smoothed.
 //! Smoothing will have no effect on the `LookTransform`, only the final `Transform` in the scene graph.
+//! For orbiting cameras, `Smoother::with_mode(SmoothingMode::Orbital)` smooths the angles and radius around the target instead of
+//! the eye position, so fast orbits don't cut inside the sphere.
+//! For smoothing that keeps its momentum between frames, use a critically damped spring with `Smoother::spring(half_life)`.
+//! A `Smoother` added to a camera that's already in place starts from where the camera is, so it never flies in from the origin.
+//! Lag weights are the fraction of the distance that's left after a sixtieth of a second, so smoothing looks the same at any
+//! frame rate. The same easing is available for your own fields, like a field of view or an orthographic scale, with
+//! `smooth(current, target, lag_weight, dt)`, which works on `f32`, `Vec2`, `Vec3`, and `LookTransform`.
 //!
 //! ```rust
 //! // Enables the system that synchronizes your `Transform`s and `LookTransform`s.
@@ -16,7 +23,7 @@
 //!
 //! commands
 //!     .spawn_bundle(LookTransformBundle {
-//!         transform: LookTransform { eye, target },
+//!         transform: LookTransform::new(eye, target),
 //!         smoother: Smoother::new(0.9), // Value between 0.0 and 1.0, higher is smoother.
 //!     })
 //!     .insert(PerspectiveCameraBundle::default())
@@ -29,6 +36,29 @@
 //! }
 //! ```
 //!
+//! Each frame, controlled cameras go through the `LookTransformSystem::InputMap`, `LookTransformSystem::Control`, and
+//! `LookTransformSystem::Smooth` system labels, in that order. Order your own camera systems relative to these labels to avoid
+//! one-frame jitter:
+//!
+//! ```rust
+//! app.add_system(
+//!     move_camera_system
+//!         .system()
+//!         .after(LookTransformSystem::Control)
+//!         .before(LookTransformSystem::Smooth),
+//! );
+//! ```
+//!
+//! To read where a camera actually is on screen, after smoothing, add a `SmoothedLookTransform` component. Its `eye`,
+//! `target`, `look_direction`, `right`, and `up` are updated each frame in `LookTransformSystem::Smooth`.
+//! For effects that should only move the view, like head bob or camera shake, add to a `LookTransformOffset` component.
+//! It shifts the eye and target in the `Transform` after smoothing, and never shows up in the `LookTransform`.
+//!
+//! A camera that's a child of another entity, e.g. one attached to a vehicle, has its `LookTransform` in the parent's space,
+//! like its `Transform`. To give it a world-space `LookTransform` instead, add `LookTransformSpace::World`, and its
+//! `Transform` is worked out from the parent's `GlobalTransform`. That's last frame's, so keep world space for parents that
+//! don't move, and leave a camera riding a vehicle in the parent's space.
+//!
 //! # Look Angles
 //!
 //! When implementing a camera controller, it's often useful to work directly with the angles (pitch and yaw) of your look
@@ -38,32 +68,394 @@
 //! let mut angles = LookAngles::from_vector(transform.look_direction());
 //! angles.add_pitch(delta.y);
 //! angles.add_yaw(delta.x);
-//! transform.target = transform.target + transform.radius() * angles.unit_vector();
+//! transform.set_look_direction(angles.unit_vector());
+//! ```
+//!
+//! This is how the built-in controllers implement rotation controls. `LookAngles::new(yaw, pitch)` builds angles directly, and
+//! `to_quat` and `from_quat` convert to and from a rotation, e.g. a `Transform`'s. The yaw is wrapped to `[-PI, PI)` and the
+//! pitch stays just short of straight up or down.
+//!
+//! The world is assumed to be +Y up, but Z-up worlds, like CAD models or geographic data, work too. Create the camera with
+//! `LookTransform::look_at(eye, target, Vec3::Z)`, use `LookAngles::from_vector_with_up` and `unit_vector_with_up`, and set the
+//! `up` field of the FPS and orbit controllers. `LookTransform::from_transform` converts back from a scene `Transform`. The
+//! `up` vector also carries roll: `LookTransform::roll(angle)` tilts the camera for a dutch angle, and the free-fly controller
+//! rolls with Q/E. Smoothing eases the up vector along with the eye and target.
+//!
+//! # Custom Controllers
+//!
+//! To write your own controller, follow the pattern of the built-in ones: an input-mapping system that sends control events
+//! tagged with the camera entity, and a control system that reads them and moves the `LookTransform`. The
+//! `AddCameraController` extension trait adds both with the right labels and ordering, and respects the `ControlTimestep`:
+//!
+//! ```rust
+//! impl Plugin for MyCameraPlugin {
+//!     fn build(&self, app: &mut App) {
+//!         app.add_camera_controller::<MyControlEvent, _, _>(
+//!             map_my_input.system(),
+//!             control_my_camera.system(),
+//!             self.override_input_system,
+//!         );
+//!     }
+//! }
+//! ```
+//!
+//! # Scripted Camera Moves
+//!
+//! To fly a camera to a new pose, like in a cut-scene, insert a `LookTransformTween`. Controllers ignore input for the camera
+//! until the move is finished.
+//!
+//! ```rust
+//! commands
+//!     .entity(camera)
+//!     .insert(LookTransformTween::ease_to(LookTransform::new(eye, target), 2.0, Easing::EaseInOut));
+//! ```
+//!
+//! To teleport a camera instead, e.g. on respawn, send a `CameraJumpEvent`. It sets the `LookTransform` and resets the
+//! `Smoother` in the same frame, so the camera doesn't ease over from where it was:
+//!
+//! ```rust
+//! events.send(CameraJumpEvent::new(camera, eye, target));
+//! ```
+//!
+//! For a tracking shot, insert `LookAtEntity(entity)` on a camera. Its target then follows the entity's `GlobalTransform`,
+//! while the eye stays under the control of the camera's controller.
+//!
+//! For the "vertigo" effect, insert `DollyZoom::new(target_fov, duration)` on a perspective camera. It changes the field of
+//! view while moving the eye so the target stays the same size on screen.
+//!
+//! # Recording and Replaying Paths
+//!
+//! Add a `CameraPathRecorder` to a camera to record its `LookTransform` into a `CameraPath`, every frame or at a fixed
+//! interval. Insert a `CameraPathPlayer` to play a path back, e.g. for trailers or automated screenshot tours, with linear or
+//! Catmull-Rom interpolation between keyframes. Paths implement serde's traits, so they can be saved as RON or JSON.
+//!
+//! ```rust
+//! commands.entity(camera).insert(CameraPathRecorder::new(Some(0.25)));
+//!
+//! ...
+//!
+//! commands
+//!     .entity(camera)
+//!     .insert(CameraPathPlayer::new(recorder.path.clone(), PathInterpolation::CatmullRom));
+//! ```
+//!
+//! # Cinematic Tracks
+//!
+//! For cut-scenes with more than one move, insert a `CameraTrack` of keyframes, each with its own `Easing`. Tracks can play
+//! once, loop, or ping-pong, and send a `CameraTrackEvent` as each keyframe is reached. A track that plays once hands control
+//! back to the camera's controller over its `blend_out` time and then removes itself.
+//!
+//! ```rust
+//! commands.entity(camera).insert(
+//!     CameraTrack::new(
+//!         vec![
+//!             TrackKeyframe { time: 0.0, transform: start, easing: Easing::Linear },
+//!             TrackKeyframe { time: 4.0, transform: end, easing: Easing::EaseInOut },
+//!         ],
+//!         TrackPlayback::Once,
+//!     )
+//!     .with_blend_out(1.0),
+//! );
+//! ```
+//!
+//! # Bounds
+//!
+//! Add a `LookTransformBounds` component to keep a camera's eye or target inside a box, like the edges of a map. It works with
+//! any controller, since it's applied to the `LookTransform` just before smoothing.
+//!
+//! # Collision
+//!
+//! Add a `CameraCollision` component and a `CameraRaycaster` resource to keep the eye from clipping through geometry between
+//! it and the target. The raycaster is a callback, so you can back it with whichever physics engine you use.
+//!
+//! # Framing
+//!
+//! To focus an orbit camera on an object, send an `OrbitControlEvent::Frame` with the object's bounding box. The target moves to
+//! the center of the box and the camera zooms until the whole box is in view. The underlying math is also available through
+//! `bounding_sphere`, `perspective_framing_distance`, and `orthographic_framing_scale`.
+//!
+//! ```rust
+//! events.send(OrbitControlEvent::Frame(camera, aabb_min, aabb_max));
+//! ```
+//!
+//! Similarly, a view cube widget can move the camera to a `StandardView` with `OrbitControlEvent::SetView`, either easing through
+//! the `Smoother` or jumping straight there. Zoom controls can send `OrbitControlEvent::ZoomTo` to zoom to a given
+//! radius, or `OrbitControlEvent::ZoomBy` to zoom by an exact factor.
+//!
+//! # Saving and Loading
+//!
+//! `LookTransform`, `Smoother`, and the controller components implement `Serialize` and `Deserialize`, except for
+//! `FollowCameraController`, `ChaseCameraController`, and `RailCameraController`, which refer to an entity. To save and
+//! restore exactly where a camera is, including any smoothing in progress, use a `LookTransformSnapshot`:
+//!
+//! ```rust
+//! let snapshot = LookTransformSnapshot::capture(&look_transform, Some(&smoother));
+//! ...
+//! snapshot.restore(&mut look_transform, Some(&mut smoother));
 //! ```
 //!
-//! This is how the built-in controllers implement rotation controls.
+//! `LookTransform`, `Smoother`, `OrbitCameraController`, and `FpsCameraController` also implement `Reflect` and are registered by
+//! their plugins, so they show up in reflection-based tools like `bevy-inspector-egui` and can be edited live.
 //!
 //! # Built-In Controllers
 //!
-//! These plugins depend on the `LookTransformPlugin`:
+//! These plugins depend on the `LookTransformPlugin`.
 //!
+//! - `ChaseCameraPlugin + ChaseCameraBundle`
+//!   - Eye trails behind the chased entity and swings after it on an angular spring when it turns
+//!   - Target leads the chased entity by its velocity
+//!   - Mouse or right stick: Glance around the chased entity
+//! - `FollowCameraPlugin + FollowCameraBundle`
+//!   - Target tracks the followed entity
+//!   - Mouse or right stick: Orbit camera around the followed entity
 //! - `FpsCameraPlugin + FpsCameraBundle`
-//!   - WASD: Translate on the XZ plane
+//!   - WASD: Translate on the XZ plane, or along the look direction with `FpsMovementMode::Fly`
 //!   - Shift/Space: Translate along the Y axis
+//!   - Ctrl/C: Speed up/slow down translation
 //!   - Mouse: Rotate camera
+//!   - Escape: Grab/release the cursor, if `grab_cursor` is enabled
+//!   - Click: Grab the cursor in web builds with the `web_pointer_lock` feature, or drag to look if the browser refuses
+//!   - Gamepad: Left stick to translate, right stick to rotate, shoulder buttons to translate along the Y axis
+//!   - Head bob: Bobs the eye while translating, if `head_bob` is set
+//!   - Gravity: Walks on the ground or a `GroundHeight` function, if `gravity` is set, easing up stairs with `step_lag_weight`
+//!   - Right mouse: Zoom in by narrowing the field of view, if `zoom` is set
+//!   - Target lock: Keeps aiming at an entity while translating, if `lock_target` is set
+//! - `FreeFlyCameraPlugin + FreeFlyCameraBundle`
+//!   - WASD: Translate in the camera's local XZ plane
+//!   - Shift/Space: Translate along the camera's local Y axis
+//!   - Q/E: Roll camera
+//!   - Mouse: Rotate camera
+//! - `IsometricCameraPlugin + IsometricCameraBundle` (requires an `OrthographicProjection`)
+//!   - WASD: Pan camera on the ground plane
+//!   - Cursor at window edge: Pan camera, if `edge_pan_enabled` is set
+//!   - Mouse wheel: Zoom
+//!   - Grid: Snaps the target to a grid, if `grid_size` is set
 //! - `OrbitCameraPlugin + OrbitCameraBundle`
-//!   - CTRL + mouse drag: Rotate camera
-//!   - Right mouse drag: Pan camera
+//!   - Left mouse drag: Rotate camera, turntable style by default or freely with `OrbitMode::Trackball`
+//!   - Pitch limits: Stretch and spring back like a rubber band, if `elastic_pitch` is set
+//!   - Middle mouse drag: Pan camera, keeping the grabbed point under the cursor unless `pixel_perfect_pan` is off
+//!   - Mouse bindings: Rebind each drag to a button and modifier keys, e.g. `CameraInputMap::blender()`
+//!   - Actions: Turn off rotating, panning, or zooming separately with `rotate_enabled`, `pan_enabled`, and `zoom_enabled`
+//!   - Arrow keys: Pan camera, if `keyboard_pan_enabled` is set
+//!   - Orthographic cameras: Pan speed follows the projection's scale, so panning feels the same at every zoom level
+//!   - Screen edges: Pan camera, if `edge_pan_enabled` is set
+//!   - Numpad 4/6/8/2: Rotate camera in steps, and numpad 1/3/7: Front/right/top views, if `keyboard_orbit_enabled` is set
+//!   - Mouse wheel: Zoom, skipping the `Smoother` if `zoom_bypasses_smoothing` is set
+//!   - Numpad +/-: Zoom while held, if `keyboard_zoom_enabled` is set
+//!   - Zoom speed: Proportional to the radius, or a fixed distance per step with `OrbitZoomMode::Linear`
+//!   - Horizontal mouse wheel: Yaw, and Shift + mouse wheel: Pan sideways, if `horizontal_wheel_enabled` is set
+//!   - Touch: One-finger drag to rotate, two-finger drag to pan, pinch to zoom, twist to turn
+//!   - Momentum: Keeps spinning after a rotate drag is released, if `momentum_enabled` is set
+//!   - Idle: Spins around the target after `idle_delay` seconds without input, if `idle_rotation_speed` is set
+//! - `PanCamPlugin + PanCamBundle` (2D, requires an `OrthographicProjection`)
+//!   - Left mouse drag: Pan camera
+//!   - Mouse wheel: Zoom
+//! - `RailCameraPlugin + RailCameraBundle`
+//!   - Eye rides along a `CameraRail` through a list of points, while the target stays on the followed entity
+//!   - `RailMode::Closest`: Eye stays at the point on the rail closest to the target
+//!   - `RailMode::Progress`: Mouse wheel, W/S, or `RailControlEvent`s move the eye along the rail
+//! - `RtsCameraPlugin + RtsCameraBundle`
+//!   - Cursor at window edge: Pan camera
+//!   - Q/E: Rotate camera around the vertical axis
 //!   - Mouse wheel: Zoom
 //! - `UnrealCameraPlugin + UnrealCameraBundle`
 //!   - Left mouse drag: Locomotion
 //!   - Right mouse drag: Rotate camera
 //!   - Left and Right mouse drag: Pan camera
+//!
+//! The `OrbitCameraBundle` and `FpsCameraBundle` include a `LookTransformBundle` and a `PerspectiveCameraBundle`, so a
+//! camera spawns in one go, with its `Transform`, `LookTransform`, and `Smoother` agreeing from the start:
+//!
+//! ```rust
+//! commands.spawn_bundle(OrbitCameraBundle::new(
+//!     OrbitCameraController::default(),
+//!     PerspectiveCameraBundle::default(),
+//!     eye,
+//!     target,
+//! ));
+//! ```
+//!
+//! To switch one camera between controllers, like from an orbit view to first person, give it the components of both
+//! controllers and a `CameraRig`, and add the `CameraRigPlugin`. Sending a `SwitchCameraController` event enables one
+//! controller and disables the rest, optionally flying the camera to a new pose with a `LookTransformTween` first:
+//!
+//! ```rust
+//! events.send(SwitchCameraController {
+//!     camera,
+//!     controller: RigController::Fps,
+//!     transition: Some(LookTransformTween::ease_to(first_person_pose, 1.0, Easing::EaseInOut)),
+//! });
+//! ```
+//!
+//! # Routing Input
+//!
+//! To pause input for all controllers at once, e.g. while a menu is open, set the `ControllerEnabled` resource to
+//! `ControllerEnabled(false)`. To have only one of several cameras take input, call `set_active(camera)` on the
+//! `ActiveCameraEntity` resource. To switch a camera between UI mode and camera mode with a key, add a `ControlToggle`
+//! component, which flips its controller's `enabled` field and can grab the cursor too. To stop the controllers from
+//! reacting to input that a UI is using, set the `InputCaptureState` resource from your UI code, e.g. from egui's
+//! `wants_pointer_input`.
+//!
+//! In apps with several windows or split-screen views, add a `ControllerViewport` component to a camera so it only
+//! takes pointer input from its own window or region. Mouse drags that rotate or pan a camera only start when the
+//! button is pressed over its viewport, and they pause while the cursor is outside the window. So clicks for object
+//! picking don't nudge the orbit controller, its drags wait until the cursor moves its `drag_threshold` of 3 logical
+//! pixels. Read the `ConsumedDrags` resource on release to tell clicks apart from camera drags. To pick what's under
+//! the cursor, `pointer_ray` turns a cursor position into a world-space `PointerRay` from a camera's `GlobalTransform`,
+//! perspective or orthographic projection, and viewport size.
+//!
+//! On devices with both a touch screen and a mouse, the `PointerArbitration` resource lets only one of them drive each
+//! gesture, preferring its `priority` when both start at once, so a touch that's also reported as a mouse drag doesn't
+//! rotate the orbit controller twice. For local multiplayer, set each controller's `input_source` to
+//! `InputSource::KeyboardMouse` or `InputSource::Gamepad(gamepad)` so every player drives only their own camera.
+//!
+//! # Bindings and Sensitivity
+//!
+//! Every controller reads its keys, mouse buttons, and gamepad buttons and sticks through the `CameraAction`s of the
+//! `CameraInputMap` resource, so they can be rebound at runtime, e.g. from a settings menu. A binding can require
+//! modifier keys, like Shift + middle drag, and a camera with its own `CameraInputMap` component uses that instead,
+//! e.g. for a second player.
+//!
+//! Each mouse, stick, and touch input of a controller has an `InputAxisConfig` with its sensitivity, inversion, and
+//! dead zone. Mouse sensitivities are per physical pixel by default; set the `PointerNormalization` resource to
+//! `PointerNormalization::LogicalPixels` or `PointerNormalization::WindowHeight` to make them independent of DPI or
+//! window size. For mouse acceleration, set the `rotate_response_curve` of the FPS or orbit controller to a
+//! `ResponseCurve::Power`. To steady jittery mice, set a controller's `mouse_filter` to an `InputFilter::MovingAverage`
+//! or an `InputFilter::OneEuro`.
+//!
+//! # Reduced Motion
+//!
+//! To honor the operating system's reduced-motion preference, set the `ReducedMotion` resource's `enabled` field. It
+//! caps the lag of every `Smoother` and controller easing at `max_lag_weight`, turns off the FPS head bob and the orbit
+//! momentum, idle rotation, and elastic pitch, keeps the chase camera from swinging past its vehicle, cuts straight to
+//! the end of tweens and dolly zooms, plays camera paths as cuts between keyframes, and scales `LookTransformOffset`s,
+//! like camera shake, by `shake_scale`.
+//!
+//! # Control Events
+//!
+//! Each controller is split into an input-mapping system, which turns raw input into control events like
+//! `OrbitControlEvent`, and a control system, which only reads those events. To drive a camera from gameplay code, a
+//! replay, or the network, send the events yourself; add the plugin with `override_input_system: true` to turn off the
+//! built-in input mapping entirely. The control systems only write a camera's `LookTransform` when it actually moves,
+//! so `Changed<LookTransform>` filters can react to real camera motion. Every event names the camera it controls, so
+//! several controlled cameras can coexist, and the name is available through its `camera()` method. Events implement
+//! `Clone`, `Debug`, `PartialEq`, `Serialize`, and `Deserialize`, so they can be logged, recorded, and sent again
+//! later:
+//!
+//! ```rust
+//! app.add_plugin(OrbitCameraPlugin {
+//!     override_input_system: true,
+//! });
+//! ```
+//!
+//! To run the controllers in a fixed timestep, e.g. to keep them in step with physics, insert a `ControlTimestep`
+//! resource before adding their plugins. The input-mapping and control systems then run at that rate and step the
+//! camera by the fixed step, while the `Smoother` still runs every frame and eases toward the latest step. Mouse
+//! motion, scrolling, and key and button presses are collected in the `AccumulatedInput` resource until each
+//! input-mapping system takes them, and control events are kept until the next step, so none are lost between steps.
+//! Custom input mapping can read from `AccumulatedInput` too:
+//!
+//! ```rust
+//! App::new()
+//!     .insert_resource(ControlTimestep::Fixed(1.0 / 60.0))
+//!     .add_plugin(LookTransformPlugin)
+//!     .add_plugin(OrbitCameraPlugin::default());
+//! ```
+//!
+//! For headless tests and replays, insert `ControlTimestep::Stepped(1.0 / 60.0)` so the controllers and the `Smoother`
+//! step by the same amount every frame, and queue the control events in a `ScriptedControlEvents` resource, keyed by
+//! the step on which each should be sent. The cameras then move the same way on every run:
+//!
+//! ```rust
+//! App::new()
+//!     .add_plugins(MinimalPlugins)
+//!     .insert_resource(ControlTimestep::Stepped(1.0 / 60.0))
+//!     .add_plugin(LookTransformPlugin)
+//!     .add_plugin(OrbitCameraPlugin {
+//!         override_input_system: true,
+//!     })
+//!     .add_scripted_control_events::<OrbitControlEvent>()
+//!     .insert_resource(ScriptedControlEvents::new(vec![
+//!         (0, OrbitControlEvent::Orbit(camera, Vec2::new(0.5, 0.0))),
+//!         (30, OrbitControlEvent::Zoom(camera, 0.5)),
+//!     ]));
+//! ```
+//!
+//! To record the events that reach a controller, e.g. for a replay file or a spectator's camera on another machine, add
+//! a `ControlEventRecorder` with `add_control_event_recorder`. It stamps each event with its step and the step's
+//! length, and the stamped events can be queued in a `ScriptedControlEvents` elsewhere. The events of the free-fly,
+//! RTS, isometric, and PanCam controllers only depend on the camera's `LookTransform` and the controller's settings, so
+//! they can also be played back without an `App`, one camera at a time:
+//!
+//! ```rust
+//! let recording = app
+//!     .world
+//!     .get_resource_mut::<ControlEventRecorder<FreeFlyControlEvent>>()
+//!     .unwrap()
+//!     .take();
+//! apply_events(player_camera, &mut spectator_look_transform, &mut (), &recording);
+//! ```
 
 pub mod controllers;
 
+mod accumulated_input;
+mod bounds;
+mod camera_jump;
+mod camera_path;
+mod camera_track;
+mod collision;
+mod control_replay;
+mod control_timestep;
+mod control_toggle;
+mod custom_controller;
+mod dolly_zoom;
+mod framing;
+mod input_axis;
+mod input_capture;
+mod input_filter;
+mod input_map;
+mod input_source;
 mod look_angles;
+mod look_at_entity;
 mod look_transform;
+mod pointer_arbitration;
+mod pointer_normalization;
+mod pointer_ray;
+mod reduced_motion;
+mod response_curve;
+mod scripted_events;
+mod smoothing;
+mod touch;
+mod tween;
+mod viewport;
 
+pub use accumulated_input::*;
+pub use bounds::*;
+pub use camera_jump::*;
+pub use camera_path::*;
+pub use camera_track::*;
+pub use collision::*;
+pub use control_replay::*;
+pub use control_timestep::*;
+pub use control_toggle::*;
+pub use custom_controller::*;
+pub use dolly_zoom::*;
+pub use framing::*;
+pub use input_axis::*;
+pub use input_capture::*;
+pub use input_filter::*;
+pub use input_map::*;
+pub use input_source::*;
 pub use look_angles::*;
+pub use look_at_entity::*;
 pub use look_transform::*;
+pub use pointer_arbitration::*;
+pub use pointer_normalization::*;
+pub use pointer_ray::*;
+pub use reduced_motion::*;
+pub use response_curve::*;
+pub use scripted_events::*;
+pub use smoothing::*;
+pub use touch::*;
+pub use tween::*;
+pub use viewport::*;
@@ -1,10 +1,13 @@
-use approx::relative_eq;
 use bevy::math::prelude::*;
 
 const PI: f32 = std::f32::consts::PI;
 
-/// A (yaw, pitch) pair representing a direction.
-#[derive(Clone, Copy, Debug, Default)]
+/// A (yaw, pitch) pair representing a direction, for building rotation controls in custom controllers.
+///
+/// The yaw turns around the +Y axis, starting from +Z and turning toward +X, and is wrapped to `[-PI, PI)`. The pitch tilts
+/// toward +Y and is clamped to slightly less than `PI / 2` either way, so the direction is never straight up or down.
+/// Angles that aren't finite, e.g. after a huge sensitivity or time step, are replaced with zero.
+#[derive(Clone, Copy, Debug, Default, PartialEq)]
 pub struct LookAngles {
     // The fields are protected to keep them in an allowable range for the camera transform.
     yaw: f32,
@@ -12,6 +15,14 @@ pub struct LookAngles {
 }
 
 impl LookAngles {
+    pub fn new(yaw: f32, pitch: f32) -> Self {
+        let mut p = Self::default();
+        p.set_yaw(yaw);
+        p.set_pitch(pitch);
+
+        p
+    }
+
     pub fn from_vector(v: Vec3) -> Self {
         let mut p = Self::default();
         p.set_direction(v);
@@ -19,10 +30,31 @@ impl LookAngles {
         p
     }
 
+    /// Like `from_vector`, but with yaw around `up` and pitch toward it, e.g. `Vec3::Z` for a Z-up world.
+    pub fn from_vector_with_up(v: Vec3, up: Vec3) -> Self {
+        Self::from_vector(y_up_rotation(up).inverse() * v)
+    }
+
     pub fn unit_vector(self) -> Vec3 {
         unit_vector_from_yaw_and_pitch(self.yaw, self.pitch)
     }
 
+    /// The inverse of `from_vector_with_up`.
+    pub fn unit_vector_with_up(self, up: Vec3) -> Vec3 {
+        y_up_rotation(up) * self.unit_vector()
+    }
+
+    /// The angles of the direction that `rotation` turns +Z to. Any roll is lost.
+    pub fn from_quat(rotation: Quat) -> Self {
+        Self::from_vector(rotation * Vec3::Z)
+    }
+
+    /// The rotation without roll that turns +Z to `unit_vector`. When the unit vector points from a camera's target to its
+    /// eye, like in the built-in controllers, this is the camera's rotation.
+    pub fn to_quat(self) -> Quat {
+        Quat::from_rotation_y(self.yaw) * Quat::from_rotation_x(-self.pitch)
+    }
+
     pub fn set_direction(&mut self, v: Vec3) {
         let (yaw, pitch) = yaw_and_pitch_from_vector(v);
         self.set_yaw(yaw);
@@ -30,46 +62,72 @@ impl LookAngles {
     }
 
     pub fn set_yaw(&mut self, yaw: f32) {
-        self.yaw = yaw % (2.0 * PI);
+        self.yaw = yaw;
+        self.sanitize();
     }
 
     pub fn get_yaw(&self) -> f32 {
         self.yaw
     }
 
+    /// Turns the yaw by `delta`. A delta that isn't finite is ignored.
     pub fn add_yaw(&mut self, delta: f32) {
-        self.set_yaw(self.get_yaw() + delta);
+        if delta.is_finite() {
+            self.set_yaw(self.get_yaw() + delta);
+        }
     }
 
     pub fn set_pitch(&mut self, pitch: f32) {
-        // Things can get weird if we are parallel to the UP vector.
-        let up_eps = 0.01;
-        self.pitch = pitch.min(PI / 2.0 - up_eps).max(-PI / 2.0 + up_eps);
+        self.pitch = pitch;
+        self.sanitize();
     }
 
     pub fn get_pitch(&self) -> f32 {
         self.pitch
     }
 
+    /// Tilts the pitch by `delta`. A delta that isn't finite is ignored.
     pub fn add_pitch(&mut self, delta: f32) {
-        self.set_pitch(self.get_pitch() + delta);
+        if delta.is_finite() {
+            self.set_pitch(self.get_pitch() + delta);
+        }
     }
 
-    pub fn assert_not_looking_up(&self) {
-        let is_looking_up = relative_eq!(self.unit_vector().dot(Vec3::Y).abs(), 1.0);
+    /// Restricts the pitch to `[min, max]`. The pitch is always kept slightly away from straight up or down, regardless of
+    /// these limits.
+    pub fn clamp_pitch(&mut self, min: f32, max: f32) {
+        self.set_pitch(self.get_pitch().min(max).max(min));
+    }
+
+    /// Brings the angles back into range: wraps the yaw to `[-PI, PI)`, keeps the pitch slightly away from straight up or
+    /// down, and replaces angles that aren't finite with zero. The setters already do this, so custom controllers only need
+    /// it after building up angles some other way.
+    pub fn sanitize(&mut self) {
+        if !self.yaw.is_finite() {
+            self.yaw = 0.0;
+        }
+        if !self.pitch.is_finite() {
+            self.pitch = 0.0;
+        }
 
-        assert!(
-            !is_looking_up,
-            "Your camera transform is fucked up. Your look direction {} is probably bad.",
-            self.unit_vector(),
-        );
+        // The remainder can round up to exactly `PI`.
+        self.yaw = (self.yaw + PI).rem_euclid(2.0 * PI) - PI;
+        if self.yaw >= PI {
+            self.yaw = -PI;
+        }
+        // Things can get weird if we are parallel to the UP vector.
+        let up_eps = 0.01;
+        self.pitch = self.pitch.min(PI / 2.0 - up_eps).max(-PI / 2.0 + up_eps);
     }
 }
 
 /// Returns pitch and yaw angles that rotates z unit vector to v. The yaw is applied first to z about the y axis to get z'. Then
 /// the pitch is applied about some axis orthogonal to z' in the XZ plane to get v.
 fn yaw_and_pitch_from_vector(v: Vec3) -> (f32, f32) {
-    debug_assert_ne!(v, Vec3::ZERO);
+    // There's no direction to take the angles from, so face +Z rather than produce NaNs.
+    if v == Vec3::ZERO || !(v.x.is_finite() && v.y.is_finite() && v.z.is_finite()) {
+        return (0.0, 0.0);
+    }
 
     let y = Vec3::Y;
     let z = Vec3::Z;
@@ -97,6 +155,28 @@ fn yaw_and_pitch_from_vector(v: Vec3) -> (f32, f32) {
     (yaw, pitch)
 }
 
+/// The rotation from a Y-up world to one where `up` is up. For a Z-up world, this takes +Z to -Y, so a yaw of zero faces the
+/// eye toward -Y.
+pub(crate) fn y_up_rotation(up: Vec3) -> Quat {
+    Quat::from_rotation_arc(Vec3::Y, normalize_or(up, Vec3::Y))
+}
+
+/// `v` scaled to unit length, or `fallback` if `v` is too short to have a direction.
+pub(crate) fn normalize_or(v: Vec3, fallback: Vec3) -> Vec3 {
+    let length = v.length();
+    if length > 1e-6 && length.is_finite() {
+        v / length
+    } else {
+        fallback
+    }
+}
+
+/// Some unit vector perpendicular to `v`, for when a cross product with `v` degenerates.
+pub(crate) fn any_orthogonal(v: Vec3) -> Vec3 {
+    let axis = if v.x.abs() < 0.9 { Vec3::X } else { Vec3::Y };
+    normalize_or(v.cross(axis), Vec3::Z)
+}
+
 fn unit_vector_from_yaw_and_pitch(yaw: f32, pitch: f32) -> Vec3 {
     let ray = Mat3::from_rotation_y(yaw) * Vec3::Z;
     let pitch_axis = ray.cross(Vec3::Y);
@@ -161,4 +241,73 @@ mod tests {
         assert_relative_eq!(yaw, -PI / 4.0);
         assert_relative_eq!(pitch, -PI / 4.0);
     }
+
+    #[test]
+    fn test_clamp_pitch() {
+        let mut angles = LookAngles::default();
+
+        angles.set_pitch(1.0);
+        angles.clamp_pitch(-0.5, 0.5);
+        assert_relative_eq!(angles.get_pitch(), 0.5);
+
+        angles.set_pitch(-1.0);
+        angles.clamp_pitch(-0.5, 0.5);
+        assert_relative_eq!(angles.get_pitch(), -0.5);
+
+        // Even with unrestricted limits, the pitch never reaches straight up.
+        angles.set_pitch(PI);
+        angles.clamp_pitch(-PI, PI);
+        assert!(angles.get_pitch() < PI / 2.0);
+    }
+
+    #[test]
+    fn test_new_wraps_yaw_and_clamps_pitch() {
+        let angles = LookAngles::new(1.5 * PI, PI);
+        assert_relative_eq!(angles.get_yaw(), -0.5 * PI, epsilon = 1e-5);
+        assert!(angles.get_pitch() < PI / 2.0);
+
+        let mut angles = LookAngles::new(0.9 * PI, 0.0);
+        angles.add_yaw(0.2 * PI);
+        assert_relative_eq!(angles.get_yaw(), -0.9 * PI, epsilon = 1e-5);
+    }
+
+    #[test]
+    fn test_quat_round_trip() {
+        let angles = LookAngles::new(PI / 3.0, -PI / 5.0);
+        let rotation = angles.to_quat();
+        assert!((rotation * Vec3::Z).abs_diff_eq(angles.unit_vector(), 1e-5));
+
+        let round_trip = LookAngles::from_quat(rotation);
+        assert_relative_eq!(round_trip.get_yaw(), angles.get_yaw(), epsilon = 1e-5);
+        assert_relative_eq!(round_trip.get_pitch(), angles.get_pitch(), epsilon = 1e-5);
+    }
+
+    #[test]
+    fn test_custom_up() {
+        let v = Vec3::new(1.0, 0.0, 1.0).normalize();
+        let angles = LookAngles::from_vector_with_up(v, Vec3::Z);
+        assert_relative_eq!(angles.get_pitch(), PI / 4.0, epsilon = 1e-5);
+        assert!(angles.unit_vector_with_up(Vec3::Z).abs_diff_eq(v, 1e-5));
+    }
+
+    #[test]
+    fn test_bad_angles_and_vectors_are_sanitized() {
+        let mut angles = LookAngles::new(f32::NAN, f32::INFINITY);
+        assert_eq!(angles, LookAngles::default());
+
+        angles.set_pitch(0.5);
+        angles.add_pitch(f32::NAN);
+        angles.add_yaw(f32::INFINITY);
+        assert_relative_eq!(angles.get_pitch(), 0.5);
+        assert_relative_eq!(angles.get_yaw(), 0.0);
+
+        angles.set_yaw(-1e-8 - PI);
+        assert!(angles.get_yaw() >= -PI && angles.get_yaw() < PI);
+
+        assert_eq!(LookAngles::from_vector(Vec3::ZERO), LookAngles::default());
+        let v = LookAngles::from_vector(Vec3::new(f32::NAN, 0.0, 1.0)).unit_vector();
+        assert!(v.abs_diff_eq(Vec3::Z, 1e-5));
+
+        assert_eq!(y_up_rotation(Vec3::ZERO) * Vec3::Z, Vec3::Z);
+    }
 }
@@ -0,0 +1,22 @@
+use crate::LookTransform;
+
+use bevy::{ecs::prelude::*, transform::components::GlobalTransform};
+
+/// Keeps a camera's target on another entity, which must have a `GlobalTransform`. Every frame, after the controllers and
+/// before smoothing, the entity's translation is copied into `LookTransform::target`, so inserting this turns any camera into
+/// a tracking shot. The eye is left to the camera's controller.
+#[derive(Clone, Copy, Debug)]
+pub struct LookAtEntity(pub Entity);
+
+pub(crate) fn look_at_entity_system(
+    mut cameras: Query<(&LookAtEntity, &mut LookTransform)>,
+    targets: Query<&GlobalTransform>,
+) {
+    for (look_at, mut transform) in cameras.iter_mut() {
+        if let Ok(target) = targets.get(look_at.0) {
+            if transform.target != target.translation {
+                transform.target = target.translation;
+            }
+        }
+    }
+}
@@ -1,20 +1,158 @@
+use crate::{
+    accumulate_input_system, camera_jump_system, camera_path_player_system,
+    camera_path_recorder_system, camera_track_system, dolly_zoom_system,
+    look_angles::{any_orthogonal, normalize_or}, look_at_entity_system, look_transform_tween_system,
+    pointer_arbitration_system, smooth, smoothing::{clamp_lag_weight, damped_spring},
+    viewport::consumed_drags_system, AccumulatedInput, CameraCollision, CameraInputMap,
+    CameraJumpEvent, CameraRaycaster, CameraTrackEvent, ConsumedDrags, ControlTimestep,
+    InputAxisConfig, InputCaptureState, InputFilter, InputSource, LookAngles, LookTransformBounds,
+    PointerArbitration, PointerNormalization, ReducedMotion, ResponseCurve,
+};
+
 use bevy::{
     app::prelude::*,
-    ecs::{bundle::Bundle, prelude::*},
+    core::Time,
+    ecs::{bundle::Bundle, prelude::*, reflect::ReflectComponent},
+    input::InputSystem,
     math::prelude::*,
+    reflect::Reflect,
     render::prelude::*,
-    transform::components::Transform,
+    transform::components::{GlobalTransform, Parent, Transform},
 };
+use serde::{Deserialize, Serialize};
+use std::f32::consts::PI;
 
 pub struct LookTransformPlugin;
 
 impl Plugin for LookTransformPlugin {
     fn build(&self, app: &mut App) {
-        app.add_system(look_transform_system.system());
+        app.init_resource::<ControllerEnabled>()
+            .init_resource::<ActiveCameraEntity>()
+            .init_resource::<InputCaptureState>()
+            .init_resource::<CameraInputMap>()
+            .init_resource::<PointerNormalization>()
+            .init_resource::<PointerArbitration>()
+            .init_resource::<ConsumedDrags>()
+            .init_resource::<AccumulatedInput>()
+            .init_resource::<ControlTimestep>()
+            .init_resource::<ReducedMotion>()
+            .register_type::<LookTransform>()
+            .register_type::<Smoother>()
+            .register_type::<SmoothingMode>()
+            .register_type::<SmoothingMethod>()
+            .register_type::<InputSource>()
+            .register_type::<InputAxisConfig>()
+            .register_type::<InputFilter>()
+            .register_type::<ResponseCurve>()
+            .add_system_to_stage(
+                CoreStage::PreUpdate,
+                pointer_arbitration_system
+                    .system()
+                    .label(PointerInputSystem::Arbitrate)
+                    .after(InputSystem),
+            )
+            .add_system_to_stage(
+                CoreStage::PreUpdate,
+                accumulate_input_system
+                    .system()
+                    .after(InputSystem)
+                    .after(PointerInputSystem::Arbitrate),
+            )
+            .add_system_to_stage(
+                CoreStage::PreUpdate,
+                consumed_drags_system.system().after(InputSystem),
+            )
+            .add_system(
+                look_transform_tween_system
+                    .system()
+                    .label(LookTransformSystem::Control)
+                    .after(LookTransformSystem::InputMap),
+            )
+            .add_event::<CameraJumpEvent>()
+            .add_system(
+                camera_jump_system
+                    .system()
+                    .label(CinematicSystem::Jump)
+                    .after(LookTransformSystem::Control)
+                    .before(LookTransformSystem::Smooth),
+            )
+            .add_system(
+                camera_path_player_system
+                    .system()
+                    .label(CinematicSystem::Path)
+                    .after(CinematicSystem::Jump)
+                    .before(LookTransformSystem::Smooth),
+            )
+            .add_event::<CameraTrackEvent>()
+            .add_system(
+                camera_track_system
+                    .system()
+                    .label(CinematicSystem::Track)
+                    .after(CinematicSystem::Path)
+                    .before(LookTransformSystem::Smooth),
+            )
+            .add_system(
+                look_at_entity_system
+                    .system()
+                    .label(CinematicSystem::LookAt)
+                    .after(CinematicSystem::Track)
+                    .before(LookTransformSystem::Smooth),
+            )
+            .add_system(
+                dolly_zoom_system
+                    .system()
+                    .label(CinematicSystem::DollyZoom)
+                    .after(CinematicSystem::LookAt)
+                    .before(LookTransformSystem::Smooth),
+            )
+            .add_system(
+                camera_path_recorder_system
+                    .system()
+                    .after(CinematicSystem::DollyZoom)
+                    .before(LookTransformSystem::Smooth),
+            )
+            .add_system(
+                look_transform_system
+                    .system()
+                    .label(LookTransformSystem::Smooth)
+                    .after(LookTransformSystem::Control),
+            );
     }
 }
 
-#[derive(Bundle)]
+/// Labels for the stages that every controlled camera goes through each frame, in order. Order your own systems relative to
+/// these to avoid racing the controllers, e.g. run a system `.after(LookTransformSystem::Control)` to adjust the
+/// `LookTransform` that a controller just produced, before it's smoothed.
+#[derive(Clone, Debug, Eq, Hash, PartialEq, SystemLabel)]
+pub enum LookTransformSystem {
+    /// Turns raw input into control events.
+    InputMap,
+    /// Applies control events and tweens to each `LookTransform`.
+    Control,
+    /// Applies bounds, smoothing, and collision, and writes the final `Transform`.
+    Smooth,
+}
+
+/// The systems that gather pointer input in `CoreStage::PreUpdate`. The primary pointer is settled before the frame's
+/// input is accumulated, so both agree on which gesture is in progress.
+#[derive(Clone, Debug, Eq, Hash, PartialEq, SystemLabel)]
+enum PointerInputSystem {
+    Arbitrate,
+}
+
+/// The systems that override a controller between `LookTransformSystem::Control` and `LookTransformSystem::Smooth`, in
+/// order. A jump lands first, so paths and tracks can take over from it, then `LookAtEntity` aims whatever they produced,
+/// the dolly zoom moves the eye along that aim, and the recorder sees the end result.
+#[derive(Clone, Debug, Eq, Hash, PartialEq, SystemLabel)]
+enum CinematicSystem {
+    Jump,
+    Path,
+    Track,
+    LookAt,
+    DollyZoom,
+}
+
+#[derive(Bundle, Deserialize, Serialize)]
 pub struct LookTransformBundle {
     pub transform: LookTransform,
     pub smoother: Smoother,
@@ -26,94 +164,932 @@ impl LookTransformBundle {
         target: Vec3,
         smoothing_weight: f32,
     ) -> (Self, Transform) {
+        Self::look_at(eye, target, Vec3::Y, smoothing_weight)
+    }
+
+    /// Like `new`, but with a custom up direction, e.g. `Vec3::Z` for a Z-up world.
+    pub fn look_at(
+        eye: Vec3,
+        target: Vec3,
+        up: Vec3,
+        smoothing_weight: f32,
+    ) -> (Self, Transform) {
+        let transform = LookTransform::look_at(eye, target, up);
 
         (Self {
-            transform: LookTransform { eye, target },
+            transform,
             smoother: Smoother::new(smoothing_weight),
         },
         // Make sure the transform is consistent with the controller to start.
-        Transform::from_translation(eye).looking_at(target, Vec3::Y))
+        transform.into())
     }
 }
 /// An eye and the target it's looking at. As a component, this can be modified in place of bevy's `Transform`, and the two will
 /// stay in sync.
-#[derive(Clone, Copy, Debug)]
+///
+/// The `up` vector determines the roll of the camera about the look direction. It doesn't need to be orthogonal to the look
+/// direction, but it must not be parallel to it.
+#[derive(Clone, Copy, Debug, Deserialize, PartialEq, Reflect, Serialize)]
+#[reflect(Component)]
 pub struct LookTransform {
     pub eye: Vec3,
     pub target: Vec3,
+    pub up: Vec3,
+}
+
+impl Default for LookTransform {
+    /// Matches the default `Transform`, looking down the negative Z axis from the origin.
+    fn default() -> Self {
+        Self::new(Vec3::ZERO, -Vec3::Z)
+    }
 }
 
 impl From<LookTransform> for Transform {
     fn from(t: LookTransform) -> Self {
-        eye_look_at_target_transform(t.eye, t.target)
+        eye_look_at_target_transform(t.eye, t.target, t.up)
     }
 }
 
 impl LookTransform {
+    /// Looks from `eye` to `target` with `Vec3::Y` as the up direction.
+    pub fn new(eye: Vec3, target: Vec3) -> Self {
+        Self::look_at(eye, target, Vec3::Y)
+    }
+
+    pub fn look_at(eye: Vec3, target: Vec3, up: Vec3) -> Self {
+        Self { eye, target, up }
+    }
+
+    /// Interpolates the eye, target, and up vector toward `other`, where `s` is `0.0` at `self` and `1.0` at `other`.
+    pub fn lerp(&self, other: &LookTransform, s: f32) -> LookTransform {
+        LookTransform {
+            eye: self.eye.lerp(other.eye, s),
+            target: self.target.lerp(other.target, s),
+            // Opposite up vectors pass through zero halfway.
+            up: normalize_or(self.up.lerp(other.up, s), other.up),
+        }
+    }
+
+    /// The inverse of converting into a `Transform`: the eye is at the translation, the target is `radius` in front of it,
+    /// and the up vector is the transform's local Y axis.
+    pub fn from_transform(transform: &Transform, radius: f32) -> Self {
+        let eye = transform.translation;
+
+        Self {
+            eye,
+            target: eye + radius * (transform.rotation * -Vec3::Z),
+            up: transform.rotation * Vec3::Y,
+        }
+    }
+
     pub fn radius(&self) -> f32 {
         (self.target - self.eye).length()
     }
 
+    /// Moves the eye toward or away from the target, preserving the look direction.
+    pub fn set_radius(&mut self, radius: f32) {
+        self.eye = self.target - radius * self.look_direction();
+    }
+
     pub fn look_direction(&self) -> Vec3 {
         (self.target - self.eye).normalize()
     }
+
+    /// Moves the target so the eye looks along `direction`, preserving the radius.
+    pub fn set_look_direction(&mut self, direction: Vec3) {
+        self.target = self.eye + self.radius() * direction.normalize();
+    }
+
+    /// Rolls the camera about its look direction by `angle` radians, counterclockwise as seen from the eye, e.g. for a
+    /// dutch angle. The eye and target don't move.
+    pub fn roll(&mut self, angle: f32) {
+        self.up = Quat::from_axis_angle(self.look_direction(), -angle) * self.up;
+    }
+
+    /// Whether the eye, target, and up vector of `other` are all within `max_abs_diff` of these.
+    pub fn abs_diff_eq(&self, other: LookTransform, max_abs_diff: f32) -> bool {
+        self.eye.abs_diff_eq(other.eye, max_abs_diff)
+            && self.target.abs_diff_eq(other.target, max_abs_diff)
+            && self.up.abs_diff_eq(other.up, max_abs_diff)
+    }
+}
+
+/// Writes `new` into a camera's `LookTransform` only if it differs, so `Changed<LookTransform>` picks up real camera
+/// motion instead of every frame that a control system runs. The comparison is exact, so steps of any size add up; control
+/// systems leave the transform as it is while idle rather than rebuild it with fresh rounding.
+pub(crate) fn set_look_transform_if_changed(
+    look_transform: &mut Mut<LookTransform>,
+    new: LookTransform,
+) {
+    if **look_transform != new {
+        **look_transform = new;
+    }
+}
+
+/// The space that a child camera's `LookTransform` is in. Without this component, it's in the parent's space, like its
+/// `Transform`, so a camera attached to a vehicle rides along with it.
+///
+/// With `LookTransformSpace::World`, the `LookTransform` is in world space, e.g. so controllers that look at other
+/// entities' `GlobalTransform`s work on a parented camera, and the `Transform` is worked out from the parent's
+/// `GlobalTransform`. That's last frame's, since transforms are propagated after the `LookTransformSystem::Smooth` systems
+/// run, so this is meant for parents that stay put, like a turntable or a level's root. A camera on a moving parent would
+/// trail it by a frame, so give a camera riding a vehicle a `LookTransform` in the parent's space instead.
+#[derive(Clone, Copy, Debug, Deserialize, Eq, PartialEq, Serialize)]
+pub enum LookTransformSpace {
+    Parent,
+    World,
 }
 
-pub struct ControllerEnabled {}
+impl Default for LookTransformSpace {
+    fn default() -> Self {
+        Self::Parent
+    }
+}
+
+/// Where a camera is on screen, in the space of its `LookTransform`: its `GlobalTransform` with
+/// `LookTransformSpace::World`, and otherwise its own `Transform`.
+pub(crate) fn look_space_transform(
+    transform: &Transform,
+    global_transform: Option<&GlobalTransform>,
+    space: Option<&LookTransformSpace>,
+) -> Transform {
+    match (space, global_transform) {
+        (Some(LookTransformSpace::World), Some(global_transform)) => {
+            Transform::from_matrix(global_transform.compute_matrix())
+        }
+        _ => *transform,
+    }
+}
+
+/// Where a camera actually is on screen: the `LookTransform` that its `Transform` was last set from, after any bounds,
+/// smoothing, and collision. Add it to a camera to orient billboards or move a character relative to the view without working
+/// backward from the `Transform`. It's updated by the `LookTransformSystem::Smooth` systems, so read it after that label.
+#[derive(Clone, Copy, Debug, Default, PartialEq)]
+pub struct SmoothedLookTransform(LookTransform);
+
+/// Moves a camera's eye and target by this much in its scene `Transform` only, after bounds, smoothing, and collision, for
+/// effects like head bob or camera shake. The `LookTransform` and `SmoothedLookTransform` never include the offset, so
+/// bounds, snapshots, and recordings only see where the camera really is. Add to it rather than overwriting it, so several
+/// effects can share it. While `ReducedMotion` is enabled, the offset is scaled by its `shake_scale`.
+#[derive(Clone, Copy, Debug, Default, PartialEq)]
+pub struct LookTransformOffset(pub Vec3);
+
+impl SmoothedLookTransform {
+    pub fn look_transform(&self) -> LookTransform {
+        self.0
+    }
+
+    pub fn eye(&self) -> Vec3 {
+        self.0.eye
+    }
+
+    pub fn target(&self) -> Vec3 {
+        self.0.target
+    }
 
-fn eye_look_at_target_transform(eye: Vec3, target: Vec3) -> Transform {
+    pub fn look_direction(&self) -> Vec3 {
+        self.0.look_direction()
+    }
+
+    /// The unit direction of screen right.
+    pub fn right(&self) -> Vec3 {
+        let look_direction = self.look_direction();
+        normalize_or(look_direction.cross(self.0.up), any_orthogonal(look_direction))
+    }
+
+    /// The unit direction of screen up, which is the `up` vector made orthogonal to the look direction.
+    pub fn up(&self) -> Vec3 {
+        self.right().cross(self.look_direction())
+    }
+}
+
+/// A resource that enables or disables input for all of the built-in controllers at once, e.g. to pause camera control while a
+/// menu is open. Each controller's own `enabled` flag still applies when this is `true`.
+///
+/// ```rust
+/// fn open_menu(mut controller_enabled: ResMut<ControllerEnabled>) {
+///     controller_enabled.0 = false;
+/// }
+/// ```
+#[derive(Clone, Copy, Debug)]
+pub struct ControllerEnabled(pub bool);
+
+impl Default for ControllerEnabled {
+    fn default() -> Self {
+        Self(true)
+    }
+}
+
+/// The camera that the built-in controllers map input for. By default no camera is singled out, and every enabled
+/// controller takes input. Once a camera is set active, the others ignore input until it's cleared, though they still
+/// respond to control events that you send them.
+///
+/// ```rust
+/// fn switch_camera(mut active_camera: ResMut<ActiveCameraEntity>, cameras: Res<MyCameras>) {
+///     active_camera.set_active(cameras.overview);
+/// }
+/// ```
+#[derive(Clone, Copy, Debug, Default, PartialEq)]
+pub struct ActiveCameraEntity(Option<Entity>);
+
+impl ActiveCameraEntity {
+    pub fn set_active(&mut self, camera: Entity) {
+        self.0 = Some(camera);
+    }
+
+    /// Lets every camera take input again.
+    pub fn clear(&mut self) {
+        self.0 = None;
+    }
+
+    pub fn get(&self) -> Option<Entity> {
+        self.0
+    }
+
+    /// Whether `camera` should take input.
+    pub fn allows(&self, camera: Entity) -> bool {
+        self.0.map_or(true, |active| active == camera)
+    }
+}
+
+fn eye_look_at_target_transform(eye: Vec3, target: Vec3, up: Vec3) -> Transform {
     // If eye and target are very close, we avoid imprecision issues by keeping the look vector a unit vector.
     let look_vector = (target - eye).normalize();
     let look_at = eye + look_vector;
 
-    Transform::from_translation(eye).looking_at(look_at, Vec3::Y)
+    Transform::from_translation(eye).looking_at(look_at, up)
 }
 
 /// Preforms exponential smoothing on a `LookTransform`. Set the `lag_weight` between `0.0` and `1.0`, where higher is smoother.
+/// Weights outside that range are clamped into it.
+///
+/// The eye and target can be given different lag weights, e.g. to have the target track a player tightly while the eye trails
+/// behind.
+///
+/// The smoothed state is part of the component, so serializing a `Smoother` captures exactly where the camera is mid-motion.
+#[derive(Clone, Copy, Debug, Deserialize, Reflect, Serialize)]
+#[reflect(Component)]
 pub struct Smoother {
-    lag_weight: f32,
+    eye_lag_weight: f32,
+    target_lag_weight: f32,
     lerp_tfm: Option<LookTransform>,
+    enabled: bool,
+    mode: SmoothingMode,
+    method: SmoothingMethod,
+    // Only used by `SmoothingMethod::Spring`.
+    eye_velocity: Vec3,
+    target_velocity: Vec3,
+    up_velocity: Vec3,
+}
+
+/// What a `Smoother` interpolates.
+#[derive(Clone, Copy, Debug, Deserialize, Eq, PartialEq, Reflect, Serialize)]
+#[reflect_value(PartialEq, Serialize, Deserialize)]
+pub enum SmoothingMode {
+    /// Interpolates the eye and target positions. When orbiting quickly, the eye cuts across the inside of the orbit, so the
+    /// camera briefly moves closer to the target.
+    Position,
+    /// Interpolates the target position, and the yaw, pitch, and radius of the eye around it, so the eye stays on the orbit
+    /// sphere. Like `LookAngles`, this keeps the eye slightly away from straight above or below the target.
+    Orbital,
+}
+
+/// How a `Smoother` eases toward the latest transform.
+#[derive(Clone, Copy, Debug, Deserialize, PartialEq, Reflect, Serialize)]
+#[reflect_value(PartialEq, Serialize, Deserialize)]
+pub enum SmoothingMethod {
+    /// Closes a fixed fraction of the remaining distance over time, like `smooth`. The lag weights are the fraction that's
+    /// left after a sixtieth of a second, whatever the frame rate. This never overshoots, but the camera's velocity jumps
+    /// whenever the input changes.
+    Exponential,
+    /// A critically damped spring, which also never overshoots but keeps its velocity between frames, so alternating input
+    /// feels smoother. Each half-life is roughly the number of seconds it takes to close half the distance to a new position.
+    Spring {
+        eye_half_life: f32,
+        target_half_life: f32,
+    },
+}
+
+impl Default for Smoother {
+    fn default() -> Self {
+        Self::new(0.0)
+    }
 }
 
 impl Smoother {
     pub fn new(lag_weight: f32) -> Self {
+        Self::with_lag_weights(lag_weight, lag_weight)
+    }
+
+    pub fn with_lag_weights(eye_lag_weight: f32, target_lag_weight: f32) -> Self {
         Self {
-            lag_weight,
+            eye_lag_weight,
+            target_lag_weight,
             lerp_tfm: None,
+            enabled: true,
+            mode: SmoothingMode::Position,
+            method: SmoothingMethod::Exponential,
+            eye_velocity: Vec3::ZERO,
+            target_velocity: Vec3::ZERO,
+            up_velocity: Vec3::ZERO,
+        }
+    }
+
+    /// A critically damped spring smoother with the same half-life, in seconds, for the eye and target.
+    pub fn spring(half_life: f32) -> Self {
+        Self::new(0.0).with_method(SmoothingMethod::Spring {
+            eye_half_life: half_life,
+            target_half_life: half_life,
+        })
+    }
+
+    pub fn with_method(mut self, method: SmoothingMethod) -> Self {
+        self.set_method(method);
+        self
+    }
+
+    pub fn set_method(&mut self, method: SmoothingMethod) {
+        self.method = method;
+        self.reset_velocities();
+    }
+
+    pub fn method(&self) -> SmoothingMethod {
+        self.method
+    }
+
+    pub fn with_mode(mut self, mode: SmoothingMode) -> Self {
+        self.mode = mode;
+        self
+    }
+
+    pub fn set_mode(&mut self, mode: SmoothingMode) {
+        self.mode = mode;
+        // The eye velocity means something different in each mode.
+        self.reset_velocities();
+    }
+
+    pub fn mode(&self) -> SmoothingMode {
+        self.mode
+    }
+
+    /// While disabled, the smoother passes transforms through unchanged. Smoothing resumes from the latest transform when
+    /// re-enabled.
+    pub fn set_enabled(&mut self, enabled: bool) {
+        self.enabled = enabled;
+    }
+
+    pub fn is_enabled(&self) -> bool {
+        self.enabled
+    }
+
+    /// Forgets the smoothed state, so the next transform is used as-is instead of easing from the stale position. Use this
+    /// when teleporting the camera.
+    pub fn reset(&mut self) {
+        self.lerp_tfm = None;
+        self.reset_velocities();
+    }
+
+    /// Starts smoothing from `transform`, e.g. where the camera already is, if the smoother hasn't smoothed anything yet.
+    /// Otherwise the first transform it's given is used as-is.
+    pub fn warm_start(&mut self, transform: LookTransform) {
+        if self.lerp_tfm.is_none() {
+            self.lerp_tfm = Some(transform);
+        }
+    }
+
+    /// Scales the radius of the smoothed transform, keeping its look direction, so a zoom takes effect right away while
+    /// other motion is still smoothed.
+    pub fn scale_radius(&mut self, scalar: f32) {
+        if let Some(lerp_tfm) = self.lerp_tfm.as_mut() {
+            lerp_tfm.set_radius(scalar * lerp_tfm.radius());
         }
     }
 
+    fn reset_velocities(&mut self) {
+        self.eye_velocity = Vec3::ZERO;
+        self.target_velocity = Vec3::ZERO;
+        self.up_velocity = Vec3::ZERO;
+    }
+
+    /// Sets the lag weight of both the eye and target.
     pub fn set_lag_weight(&mut self, lag_weight: f32) {
-        self.lag_weight = lag_weight;
+        self.eye_lag_weight = lag_weight;
+        self.target_lag_weight = lag_weight;
+    }
+
+    pub fn set_eye_lag_weight(&mut self, lag_weight: f32) {
+        self.eye_lag_weight = lag_weight;
+    }
+
+    pub fn set_target_lag_weight(&mut self, lag_weight: f32) {
+        self.target_lag_weight = lag_weight;
     }
 
+    /// Smooths with an assumed frame time of 1/60th of a second, which only matters for `SmoothingMethod::Spring`.
     pub fn smooth_transform(&mut self, new_tfm: &LookTransform) -> LookTransform {
-        debug_assert!(0.0 <= self.lag_weight);
-        debug_assert!(self.lag_weight < 1.0);
+        self.smooth_transform_with_dt(new_tfm, 1.0 / 60.0)
+    }
+
+    /// Smooths the transform over a frame that took `dt` seconds.
+    pub fn smooth_transform_with_dt(&mut self, new_tfm: &LookTransform, dt: f32) -> LookTransform {
+        self.smooth_transform_with_motion(new_tfm, dt, &ReducedMotion::default())
+    }
+
+    /// Smooths the transform over a frame that took `dt` seconds, no slower than `reduced_motion` allows.
+    pub(crate) fn smooth_transform_with_motion(
+        &mut self,
+        new_tfm: &LookTransform,
+        dt: f32,
+        reduced_motion: &ReducedMotion,
+    ) -> LookTransform {
+        if !self.enabled {
+            self.lerp_tfm = Some(*new_tfm);
+            self.reset_velocities();
+            return *new_tfm;
+        }
 
         let old_lerp_tfm = self.lerp_tfm.unwrap_or_else(|| *new_tfm);
+        let method = reduced_motion.smoothing_method(self.method);
+        // The weights can be set to anything, e.g. through reflection, so keep them to ones that ease.
+        let eye_lag_weight = reduced_motion.lag_weight(clamp_lag_weight(self.eye_lag_weight));
+        let target_lag_weight = reduced_motion.lag_weight(clamp_lag_weight(self.target_lag_weight));
 
-        let lead_weight = 1.0 - self.lag_weight;
-        let lerp_tfm = LookTransform {
-            eye: old_lerp_tfm.eye * self.lag_weight + new_tfm.eye * lead_weight,
-            target: old_lerp_tfm.target * self.lag_weight + new_tfm.target * lead_weight,
+        let (eye_half_life, target_half_life) = match method {
+            SmoothingMethod::Exponential => (0.0, 0.0),
+            SmoothingMethod::Spring {
+                eye_half_life,
+                target_half_life,
+            } => (eye_half_life, target_half_life),
         };
 
+        let (target, target_velocity) = smoothing_step(
+            method,
+            target_lag_weight,
+            target_half_life,
+            old_lerp_tfm.target,
+            new_tfm.target,
+            self.target_velocity,
+            dt,
+        );
+        self.target_velocity = target_velocity;
+
+        let eye = match self.mode {
+            SmoothingMode::Position => {
+                let (eye, eye_velocity) = smoothing_step(
+                    method,
+                    eye_lag_weight,
+                    eye_half_life,
+                    old_lerp_tfm.eye,
+                    new_tfm.eye,
+                    self.eye_velocity,
+                    dt,
+                );
+                self.eye_velocity = eye_velocity;
+
+                eye
+            }
+            SmoothingMode::Orbital => {
+                // Measure both sets of angles around the same up vector.
+                let up = new_tfm.up;
+                let old_angles =
+                    LookAngles::from_vector_with_up(old_lerp_tfm.eye - old_lerp_tfm.target, up);
+                let new_angles = LookAngles::from_vector_with_up(new_tfm.eye - new_tfm.target, up);
+
+                // Go the short way around.
+                let mut yaw_delta = (new_angles.get_yaw() - old_angles.get_yaw()) % (2.0 * PI);
+                if yaw_delta > PI {
+                    yaw_delta -= 2.0 * PI;
+                } else if yaw_delta < -PI {
+                    yaw_delta += 2.0 * PI;
+                }
+
+                // Smooth the (yaw, pitch, radius) of the eye around the target as if it were a position.
+                let (orbit, orbit_velocity) = smoothing_step(
+                    method,
+                    eye_lag_weight,
+                    eye_half_life,
+                    Vec3::new(old_angles.get_yaw(), old_angles.get_pitch(), old_lerp_tfm.radius()),
+                    Vec3::new(
+                        old_angles.get_yaw() + yaw_delta,
+                        new_angles.get_pitch(),
+                        new_tfm.radius(),
+                    ),
+                    self.eye_velocity,
+                    dt,
+                );
+                self.eye_velocity = orbit_velocity;
+
+                let mut angles = old_angles;
+                angles.set_yaw(orbit.x);
+                angles.set_pitch(orbit.y);
+
+                target + orbit.z * angles.unit_vector_with_up(up)
+            }
+        };
+
+        // The up vector belongs to the eye's orientation, so it lags along with the eye.
+        let (up, up_velocity) = smoothing_step(
+            method,
+            eye_lag_weight,
+            eye_half_life,
+            old_lerp_tfm.up,
+            new_tfm.up,
+            self.up_velocity,
+            dt,
+        );
+        self.up_velocity = up_velocity;
+        let up = normalize_or(up, new_tfm.up);
+
+        let lerp_tfm = LookTransform { eye, target, up };
+
         self.lerp_tfm = Some(lerp_tfm);
 
         lerp_tfm
     }
 }
 
+/// Moves `current` toward `goal` by one frame of `method`, returning the new value and velocity.
+fn smoothing_step(
+    method: SmoothingMethod,
+    lag_weight: f32,
+    half_life: f32,
+    current: Vec3,
+    goal: Vec3,
+    velocity: Vec3,
+    dt: f32,
+) -> (Vec3, Vec3) {
+    match method {
+        SmoothingMethod::Exponential => (smooth(current, goal, lag_weight, dt), Vec3::ZERO),
+        SmoothingMethod::Spring { .. } => {
+            // The exact solution of a critically damped spring over `dt`, which is stable for any frame time.
+            let half_damping = 2.0 * std::f32::consts::LN_2 / half_life.max(1e-5);
+            let (offset, velocity) = damped_spring(
+                current - goal,
+                velocity,
+                half_damping * half_damping,
+                2.0 * half_damping,
+                dt,
+            );
+
+            (goal + offset, velocity)
+        }
+    }
+}
+
+/// Everything needed to put a camera back exactly where it was, e.g. when saving and loading a game. The controller components
+/// are plain config and can be serialized on their own.
+///
+/// ```rust
+/// fn save_camera(cameras: Query<(&LookTransform, Option<&Smoother>)>) {
+///     for (transform, smoother) in cameras.iter() {
+///         let snapshot = LookTransformSnapshot::capture(transform, smoother);
+///         // Serialize `snapshot` with your format of choice.
+///     }
+/// }
+/// ```
+#[derive(Clone, Copy, Debug, Deserialize, Serialize)]
+pub struct LookTransformSnapshot {
+    pub transform: LookTransform,
+    pub smoother: Option<Smoother>,
+}
+
+impl LookTransformSnapshot {
+    pub fn capture(transform: &LookTransform, smoother: Option<&Smoother>) -> Self {
+        Self {
+            transform: *transform,
+            smoother: smoother.copied(),
+        }
+    }
+
+    /// Restores the captured components. If the camera has a `Smoother` but the snapshot doesn't, the smoother is reset so the
+    /// camera doesn't ease in from wherever it was before.
+    pub fn restore(&self, transform: &mut LookTransform, smoother: Option<&mut Smoother>) {
+        *transform = self.transform;
+        if let Some(smoother) = smoother {
+            match self.smoother {
+                Some(captured) => *smoother = captured,
+                None => smoother.reset(),
+            }
+        }
+    }
+}
+
 fn look_transform_system(
-    mut cameras: Query<(&LookTransform, &mut Transform, Option<&mut Smoother>)>,
+    time: Res<Time>,
+    timestep: Res<ControlTimestep>,
+    reduced_motion: Res<ReducedMotion>,
+    raycaster: Option<Res<CameraRaycaster>>,
+    mut cameras: Query<(
+        &mut LookTransform,
+        &mut Transform,
+        ChangeTrackers<Transform>,
+        Option<&LookTransformBounds>,
+        Option<(&mut Smoother, ChangeTrackers<Smoother>)>,
+        Option<&mut CameraCollision>,
+        Option<&mut SmoothedLookTransform>,
+        Option<(&LookTransformSpace, &Parent)>,
+        Option<&LookTransformOffset>,
+    )>,
+    parents: Query<&GlobalTransform>,
 ) {
-    for (look_transform, mut scene_transform, smoother) in cameras.iter_mut() {
-        let effective_look_transform = if let Some(mut smoother) = smoother {
-            smoother.smooth_transform(look_transform)
+    let dt = timestep.frame_delta_seconds(&time);
+
+    for (
+        mut look_transform,
+        mut scene_transform,
+        scene_transform_tracker,
+        bounds,
+        smoother,
+        collision,
+        smoothed,
+        space,
+        offset,
+    ) in cameras.iter_mut()
+    {
+        // The matrix from the parent's space to world space, for cameras whose `LookTransform` is in world space.
+        let parent_matrix = match space {
+            Some((LookTransformSpace::World, parent)) => {
+                parents.get(parent.0).ok().map(|p| p.compute_matrix())
+            }
+            _ => None,
+        };
+        let to_parent_space = |transform: Transform| match parent_matrix {
+            Some(matrix) => Transform::from_matrix(matrix.inverse() * transform.compute_matrix()),
+            None => transform,
+        };
+        let to_look_space = |transform: Transform| match parent_matrix {
+            Some(matrix) => Transform::from_matrix(matrix * transform.compute_matrix()),
+            None => transform,
+        };
+
+        if let Some(bounds) = bounds {
+            // Only write when clamping is needed, so change detection still means the camera moved.
+            let clamped = bounds.clamp(*look_transform);
+            if clamped != *look_transform {
+                *look_transform = clamped;
+            }
+        }
+
+        let mut effective_look_transform = if let Some((mut smoother, smoother_tracker)) = smoother {
+            // A smoother added to a camera that was already placed eases from where the camera is on screen. A camera
+            // spawned with its smoother starts at its `LookTransform`, whatever its `Transform` was.
+            if smoother_tracker.is_added() && !scene_transform_tracker.is_added() {
+                smoother.warm_start(LookTransform {
+                    up: look_transform.up,
+                    ..LookTransform::from_transform(
+                        &to_look_space(*scene_transform),
+                        look_transform.radius(),
+                    )
+                });
+            }
+            smoother.smooth_transform_with_motion(&look_transform, dt, &reduced_motion)
         } else {
             look_transform.clone()
         };
-        *scene_transform = effective_look_transform.into();
+        if let (Some(mut collision), Some(raycaster)) = (collision, raycaster.as_ref()) {
+            effective_look_transform = collision.resolve(effective_look_transform, raycaster, dt);
+        }
+        let mut shown_look_transform = effective_look_transform;
+        if let Some(offset) = offset {
+            let offset = offset.0 * reduced_motion.shake(1.0);
+            shown_look_transform.eye += offset;
+            shown_look_transform.target += offset;
+        }
+        *scene_transform = to_parent_space(shown_look_transform.into());
+        if let Some(mut smoothed) = smoothed {
+            smoothed.0 = effective_look_transform;
+        }
+    }
+}
+
+// ████████╗███████╗███████╗████████╗
+// ╚══██╔══╝██╔════╝██╔════╝╚══██╔══╝
+//    ██║   █████╗  ███████╗   ██║
+//    ██║   ██╔══╝  ╚════██║   ██║
+//    ██║   ███████╗███████║   ██║
+//    ╚═╝   ╚══════╝╚══════╝   ╚═╝
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    use approx::assert_relative_eq;
+
+    #[test]
+    fn test_set_radius_preserves_direction() {
+        let mut t = LookTransform::new(Vec3::new(0.0, 0.0, -2.0), Vec3::new(0.0, 0.0, 2.0));
+        t.set_radius(1.0);
+
+        assert_relative_eq!(t.radius(), 1.0);
+        assert_relative_eq!(t.look_direction().dot(Vec3::Z), 1.0);
+        assert_eq!(t.target, Vec3::new(0.0, 0.0, 2.0));
+    }
+
+    #[test]
+    fn test_roll_turns_up_about_look_direction() {
+        let mut t = LookTransform::new(Vec3::ZERO, -Vec3::Z);
+        t.roll(std::f32::consts::FRAC_PI_2);
+
+        // Rolling counterclockwise tips the top of the view to screen left.
+        assert!(t.up.abs_diff_eq(-Vec3::X, 1e-5));
+        assert_eq!(t.target, -Vec3::Z);
+    }
+
+    #[test]
+    fn test_set_look_direction_preserves_radius() {
+        let mut t = LookTransform::new(Vec3::ZERO, Vec3::new(0.0, 0.0, 3.0));
+        t.set_look_direction(Vec3::new(2.0, 0.0, 0.0));
+
+        assert_relative_eq!(t.radius(), 3.0);
+        assert_relative_eq!(t.look_direction().dot(Vec3::X), 1.0);
+        assert_eq!(t.eye, Vec3::ZERO);
+    }
+
+    #[test]
+    fn test_transform_round_trip_with_z_up() {
+        let t = LookTransform::look_at(Vec3::new(1.0, 2.0, 3.0), Vec3::new(4.0, -2.0, 3.0), Vec3::Z);
+        let round_trip = LookTransform::from_transform(&t.into(), t.radius());
+
+        assert!(round_trip.eye.abs_diff_eq(t.eye, 1e-5));
+        assert!(round_trip.target.abs_diff_eq(t.target, 1e-5));
+        assert!(round_trip.up.abs_diff_eq(Vec3::Z, 1e-5));
+    }
+
+    #[test]
+    fn test_degenerate_up_vectors_stay_finite() {
+        let t = LookTransform::look_at(Vec3::ZERO, -Vec3::Z, Vec3::Y);
+        let flipped = LookTransform::look_at(Vec3::ZERO, -Vec3::Z, -Vec3::Y);
+        assert_eq!(t.lerp(&flipped, 0.5).up, -Vec3::Y);
+
+        // Looking along the up vector.
+        let smoothed = SmoothedLookTransform(LookTransform::look_at(Vec3::ZERO, Vec3::Y, Vec3::Y));
+        assert!(smoothed.right().is_finite());
+        assert_relative_eq!(smoothed.right().length(), 1.0, epsilon = 1e-5);
+    }
+
+    #[test]
+    fn test_smoothed_basis_is_orthonormal() {
+        let smoothed = SmoothedLookTransform(LookTransform::look_at(
+            Vec3::new(0.0, 1.0, 5.0),
+            Vec3::ZERO,
+            Vec3::Y,
+        ));
+
+        assert!(smoothed.right().abs_diff_eq(Vec3::X, 1e-5));
+        assert_relative_eq!(smoothed.up().dot(smoothed.look_direction()), 0.0, epsilon = 1e-5);
+        assert_relative_eq!(smoothed.up().length(), 1.0, epsilon = 1e-5);
+        assert!(smoothed.up().y > 0.0);
+    }
+
+    #[test]
+    fn test_smoother_separate_eye_and_target_lag() {
+        let mut smoother = Smoother::with_lag_weights(0.5, 0.0);
+        smoother.smooth_transform(&LookTransform::new(Vec3::ZERO, Vec3::Z));
+
+        let smoothed =
+            smoother.smooth_transform(&LookTransform::new(Vec3::new(2.0, 0.0, 0.0), Vec3::X));
+
+        assert_relative_eq!(smoothed.eye.x, 1.0);
+        assert_eq!(smoothed.target, Vec3::X);
+    }
+
+    #[test]
+    fn test_smoother_reset_snaps_to_new_transform() {
+        let mut smoother = Smoother::new(0.9);
+        smoother.smooth_transform(&LookTransform::new(Vec3::ZERO, Vec3::Z));
+
+        smoother.reset();
+        let teleported = LookTransform::new(Vec3::new(10.0, 0.0, 0.0), Vec3::new(10.0, 0.0, 1.0));
+        let smoothed = smoother.smooth_transform(&teleported);
+
+        assert_eq!(smoothed.eye, teleported.eye);
+        assert_eq!(smoothed.target, teleported.target);
+    }
+
+    #[test]
+    fn test_disabled_smoother_passes_through() {
+        let mut smoother = Smoother::new(0.9);
+        smoother.smooth_transform(&LookTransform::new(Vec3::ZERO, Vec3::Z));
+
+        smoother.set_enabled(false);
+        let moved = LookTransform::new(Vec3::X, Vec3::new(1.0, 0.0, 1.0));
+        assert_eq!(smoother.smooth_transform(&moved).eye, moved.eye);
+
+        // Smoothing resumes from where the camera is now, not where it was before being disabled.
+        smoother.set_enabled(true);
+        assert_eq!(smoother.smooth_transform(&moved).eye, moved.eye);
+    }
+
+    #[test]
+    fn test_snapshot_restores_smoothed_state() {
+        let start = LookTransform::new(Vec3::ZERO, Vec3::Z);
+        let moved = LookTransform::new(Vec3::X, Vec3::Z);
+
+        let mut smoother = Smoother::new(0.5);
+        smoother.smooth_transform(&start);
+        let snapshot = LookTransformSnapshot::capture(&moved, Some(&smoother));
+
+        let mut restored_transform = start;
+        let mut restored_smoother = Smoother::new(0.5);
+        snapshot.restore(&mut restored_transform, Some(&mut restored_smoother));
+
+        assert_eq!(restored_transform, moved);
+        assert_eq!(
+            restored_smoother.smooth_transform(&moved),
+            smoother.smooth_transform(&moved)
+        );
+    }
+
+    #[test]
+    fn test_scale_radius_skips_smoothing() {
+        let mut smoother = Smoother::new(0.9);
+        smoother.smooth_transform(&LookTransform::new(Vec3::new(0.0, 0.0, 4.0), Vec3::ZERO));
+
+        smoother.scale_radius(0.5);
+        let smoothed =
+            smoother.smooth_transform(&LookTransform::new(Vec3::new(0.0, 0.0, 2.0), Vec3::ZERO));
+
+        assert!(smoothed.eye.abs_diff_eq(Vec3::new(0.0, 0.0, 2.0), 1e-5));
+    }
+
+    #[test]
+    fn test_warm_start_only_applies_before_smoothing() {
+        let placed = LookTransform::new(Vec3::new(0.0, 0.0, 4.0), Vec3::ZERO);
+        let moved = LookTransform::new(Vec3::new(0.0, 0.0, 2.0), Vec3::ZERO);
+
+        let mut smoother = Smoother::new(0.5);
+        smoother.warm_start(placed);
+        let smoothed = smoother.smooth_transform(&moved);
+        assert!(smoothed.eye.abs_diff_eq(Vec3::new(0.0, 0.0, 3.0), 1e-5));
+
+        smoother.warm_start(placed);
+        let smoothed = smoother.smooth_transform(&moved);
+        assert!(smoothed.eye.abs_diff_eq(Vec3::new(0.0, 0.0, 2.5), 1e-5));
+    }
+
+    #[test]
+    fn test_orbital_smoothing_stays_on_sphere() {
+        let mut smoother = Smoother::new(0.5).with_mode(SmoothingMode::Orbital);
+        smoother.smooth_transform(&LookTransform::new(Vec3::new(0.0, 0.0, 2.0), Vec3::ZERO));
+
+        let smoothed =
+            smoother.smooth_transform(&LookTransform::new(Vec3::new(2.0, 0.0, 0.0), Vec3::ZERO));
+
+        assert_relative_eq!(smoothed.radius(), 2.0, epsilon = 1e-5);
+        assert_relative_eq!(smoothed.eye.x, smoothed.eye.z, epsilon = 1e-5);
+    }
+
+    #[test]
+    fn test_spring_smoothing_converges_without_overshoot() {
+        let mut smoother = Smoother::spring(0.1);
+        smoother.smooth_transform_with_dt(&LookTransform::new(Vec3::ZERO, Vec3::Z), 0.0);
+
+        let goal = LookTransform::new(Vec3::X, Vec3::new(1.0, 0.0, 1.0));
+        let mut previous_x = 0.0;
+        for _ in 0..100 {
+            let smoothed = smoother.smooth_transform_with_dt(&goal, 1.0 / 60.0);
+            assert!(smoothed.eye.x >= previous_x);
+            assert!(smoothed.eye.x <= 1.0 + 1e-5);
+            previous_x = smoothed.eye.x;
+        }
+
+        assert_relative_eq!(previous_x, 1.0, epsilon = 1e-3);
+    }
+
+    #[test]
+    fn test_active_camera_gates_other_cameras() {
+        let (first, second) = (Entity::new(0), Entity::new(1));
+        let mut active_camera = ActiveCameraEntity::default();
+        assert!(active_camera.allows(first) && active_camera.allows(second));
+
+        active_camera.set_active(second);
+        assert!(!active_camera.allows(first) && active_camera.allows(second));
+
+        active_camera.clear();
+        assert!(active_camera.allows(first));
+    }
+
+    #[test]
+    fn test_smoothing_step_closes_in_on_the_goal() {
+        let spring = SmoothingMethod::Spring {
+            eye_half_life: 0.1,
+            target_half_life: 0.1,
+        };
+        for &method in [SmoothingMethod::Exponential, spring].iter() {
+            let goal = Vec3::new(1.0, -2.0, 3.0);
+            let at_rest = smoothing_step(method, 0.8, 0.1, goal, goal, Vec3::ZERO, 0.1);
+            assert_eq!(at_rest, (goal, Vec3::ZERO));
+
+            // Starting from rest, every step ends closer to the goal, whatever the frame time.
+            let (mut current, mut velocity) = (Vec3::ZERO, Vec3::ZERO);
+            for i in 0..100 {
+                let dt = 0.001 + 0.05 * (i as f32 * 0.9).sin().abs();
+                let (next, next_velocity) =
+                    smoothing_step(method, 0.8, 0.1, current, goal, velocity, dt);
+                assert!(next.distance(goal) <= current.distance(goal) + 1e-6);
+                current = next;
+                velocity = next_velocity;
+            }
+        }
     }
 }
@@ -0,0 +1,131 @@
+use bevy::{
+    ecs::prelude::*,
+    input::{mouse::MouseButton, touch::Touches, Input},
+};
+use serde::{Deserialize, Serialize};
+
+/// A device that moves the pointer.
+#[derive(Clone, Copy, Debug, Deserialize, Eq, PartialEq, Serialize)]
+pub enum PointerSource {
+    Mouse,
+    Touch,
+}
+
+/// A resource that picks one primary pointer source for every gesture, so devices with both a touch screen and a mouse,
+/// like convertible laptops, don't apply the same motion twice. Many systems turn touches into emulated mouse events as
+/// well, so a one-finger drag would otherwise rotate the orbit controller once as a touch and once as a mouse drag.
+///
+/// A gesture starts when a mouse button or a touch goes down, and that source stays primary until it's released, while the
+/// other one is ignored. Moving the mouse or scrolling without a button held never starts one, so hovering doesn't lock out
+/// the touch screen. If both start on the same frame, `priority` wins. Only the orbit controller reads touches, so it's
+/// the only one that takes this into account.
+#[derive(Clone, Copy, Debug)]
+pub struct PointerArbitration {
+    /// The source that wins when both start a gesture on the same frame.
+    pub priority: PointerSource,
+    primary: Option<PointerSource>,
+}
+
+impl Default for PointerArbitration {
+    fn default() -> Self {
+        Self {
+            priority: PointerSource::Touch,
+            primary: None,
+        }
+    }
+}
+
+impl PointerArbitration {
+    pub fn new(priority: PointerSource) -> Self {
+        Self {
+            priority,
+            ..Default::default()
+        }
+    }
+
+    /// The source of the current gesture, if there is one.
+    pub fn primary(&self) -> Option<PointerSource> {
+        self.primary
+    }
+
+    /// Whether mouse motion, buttons, and scrolling should drive the controllers.
+    pub fn accepts_mouse(&self) -> bool {
+        self.primary != Some(PointerSource::Touch)
+    }
+
+    /// Whether touches should drive the controllers.
+    pub fn accepts_touch(&self) -> bool {
+        self.primary != Some(PointerSource::Mouse)
+    }
+
+    /// Keeps the primary source while it's pressed, or picks a new one from the sources that are pressed now.
+    fn update(&mut self, mouse_pressed: bool, touch_pressed: bool) {
+        let active = |source| match source {
+            PointerSource::Mouse => mouse_pressed,
+            PointerSource::Touch => touch_pressed,
+        };
+        if self.primary.map_or(false, active) {
+            return;
+        }
+
+        let other = match self.priority {
+            PointerSource::Mouse => PointerSource::Touch,
+            PointerSource::Touch => PointerSource::Mouse,
+        };
+        self.primary = [self.priority, other].iter().cloned().find(|&s| active(s));
+    }
+}
+
+pub(crate) fn pointer_arbitration_system(
+    mut arbitration: ResMut<PointerArbitration>,
+    mouse_buttons: Res<Input<MouseButton>>,
+    touches: Res<Touches>,
+) {
+    let mouse_pressed = mouse_buttons.get_pressed().next().is_some();
+    let touch_pressed = touches.iter().next().is_some();
+
+    arbitration.update(mouse_pressed, touch_pressed);
+}
+
+// ████████╗███████╗███████╗████████╗
+// ╚══██╔══╝██╔════╝██╔════╝╚══██╔══╝
+//    ██║   █████╗  ███████╗   ██║
+//    ██║   ██╔══╝  ╚════██║   ██║
+//    ██║   ███████╗███████║   ██║
+//    ╚═╝   ╚══════╝╚══════╝   ╚═╝
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_first_source_keeps_the_gesture() {
+        let mut arbitration = PointerArbitration::default();
+        arbitration.update(true, false);
+        assert_eq!(arbitration.primary(), Some(PointerSource::Mouse));
+
+        // The touch screen joins in, but the mouse started the gesture.
+        arbitration.update(true, true);
+        assert!(arbitration.accepts_mouse());
+        assert!(!arbitration.accepts_touch());
+
+        // Once the mouse is released, the touch takes over.
+        arbitration.update(false, true);
+        assert_eq!(arbitration.primary(), Some(PointerSource::Touch));
+
+        arbitration.update(false, false);
+        assert_eq!(arbitration.primary(), None);
+        assert!(arbitration.accepts_mouse() && arbitration.accepts_touch());
+    }
+
+    #[test]
+    fn test_priority_breaks_ties() {
+        let mut arbitration = PointerArbitration::new(PointerSource::Mouse);
+        arbitration.update(true, true);
+        assert_eq!(arbitration.primary(), Some(PointerSource::Mouse));
+
+        let mut arbitration = PointerArbitration::new(PointerSource::Touch);
+        arbitration.update(true, true);
+        assert_eq!(arbitration.primary(), Some(PointerSource::Touch));
+    }
+}
@@ -0,0 +1,34 @@
+use bevy::{math::prelude::*, window::Window};
+use serde::{Deserialize, Serialize};
+
+/// A resource that sets the units of mouse motion and touch drags before the input-mapping systems apply each controller's
+/// sensitivities. Raw deltas are in physical pixels, so the same sensitivity feels slower on a hiDPI monitor or in a larger
+/// window; normalizing them makes sensitivity resolution-independent.
+///
+/// `PanCamController` always works in physical pixels, since it keeps the grabbed point under the cursor.
+#[derive(Clone, Copy, Debug, Deserialize, Eq, PartialEq, Serialize)]
+pub enum PointerNormalization {
+    PhysicalPixels,
+    /// Divides by the window's scale factor.
+    LogicalPixels,
+    /// Divides by the window's height, so moving across the full height of the window is a delta of `1.0`. Sensitivities
+    /// need to be scaled up by roughly the window height to feel the same as with pixels.
+    WindowHeight,
+}
+
+impl Default for PointerNormalization {
+    fn default() -> Self {
+        Self::PhysicalPixels
+    }
+}
+
+impl PointerNormalization {
+    /// Converts a delta in physical pixels into these units. Without a window, the delta is left as-is.
+    pub fn normalize(self, delta: Vec2, window: Option<&Window>) -> Vec2 {
+        match (self, window) {
+            (Self::PhysicalPixels, _) | (_, None) => delta,
+            (Self::LogicalPixels, Some(window)) => delta / window.scale_factor() as f32,
+            (Self::WindowHeight, Some(window)) => delta / window.physical_height().max(1) as f32,
+        }
+    }
+}
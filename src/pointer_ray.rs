@@ -0,0 +1,153 @@
+use bevy::{
+    math::prelude::*,
+    render::camera::{Camera, CameraProjection},
+    transform::components::{GlobalTransform, Transform},
+};
+
+/// A world-space ray from a camera through a point on its viewport, e.g. for picking the object under the cursor.
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub struct PointerRay {
+    /// Where the ray crosses the camera's near plane.
+    pub origin: Vec3,
+    /// A unit vector pointing away from the camera.
+    pub direction: Vec3,
+}
+
+impl PointerRay {
+    /// The point `distance` units along the ray.
+    pub fn at(&self, distance: f32) -> Vec3 {
+        self.origin + distance * self.direction
+    }
+}
+
+/// Returns the world-space ray that passes through `cursor`, in viewport coordinates with the origin at the bottom-left,
+/// for a camera at `camera_transform` with `projection`. Works for both `PerspectiveProjection` and
+/// `OrthographicProjection`.
+///
+/// Pass the camera's `GlobalTransform`, which is where it's drawn from: unlike its `LookTransform`, it includes the lag of a
+/// `Smoother`, collision, `LookTransformOffset`s, and the camera's parents, so the ray hits what's under the cursor on
+/// screen. The projection must already be updated for a viewport of `viewport_size`, which Bevy does for cameras that
+/// render to a window.
+///
+/// ```rust
+/// fn pick(windows: Res<Windows>, cameras: Query<(&GlobalTransform, &PerspectiveProjection)>) {
+///     let window = windows.get_primary().unwrap();
+///     if let Some(cursor) = window.cursor_position() {
+///         for (camera_transform, projection) in cameras.iter() {
+///             let size = Vec2::new(window.width(), window.height());
+///             let ray = pointer_ray(camera_transform, projection, size, cursor);
+///         }
+///     }
+/// }
+/// ```
+pub fn pointer_ray<P: CameraProjection>(
+    camera_transform: &GlobalTransform,
+    projection: &P,
+    viewport_size: Vec2,
+    cursor: Vec2,
+) -> PointerRay {
+    unproject_ray(
+        camera_transform.compute_matrix(),
+        projection.get_projection_matrix(),
+        viewport_size,
+        cursor,
+    )
+}
+
+/// Returns the origin and unit direction of the world-space ray that passes through `cursor`, in window coordinates with the
+/// origin at the bottom-left. Works for any projection, since it only relies on the camera's projection matrix.
+pub(crate) fn cursor_ray(
+    camera: &Camera,
+    camera_transform: &Transform,
+    window_size: Vec2,
+    cursor: Vec2,
+) -> (Vec3, Vec3) {
+    let ray = unproject_ray(
+        camera_transform.compute_matrix(),
+        camera.projection_matrix,
+        window_size,
+        cursor,
+    );
+    (ray.origin, ray.direction)
+}
+
+fn unproject_ray(
+    camera_matrix: Mat4,
+    projection_matrix: Mat4,
+    viewport_size: Vec2,
+    cursor: Vec2,
+) -> PointerRay {
+    let ndc = 2.0 * cursor / viewport_size - Vec2::ONE;
+    let ndc_to_world = camera_matrix * projection_matrix.inverse();
+
+    let unproject = |depth: f32| {
+        let p = ndc_to_world * Vec4::new(ndc.x, ndc.y, depth, 1.0);
+        p.truncate() / p.w
+    };
+    let near = unproject(0.0);
+    let far = unproject(0.5);
+
+    PointerRay {
+        origin: near,
+        direction: (far - near).normalize(),
+    }
+}
+
+// ████████╗███████╗███████╗████████╗
+// ╚══██╔══╝██╔════╝██╔════╝╚══██╔══╝
+//    ██║   █████╗  ███████╗   ██║
+//    ██║   ██╔══╝  ╚════██║   ██║
+//    ██║   ███████╗███████║   ██║
+//    ╚═╝   ╚══════╝╚══════╝   ╚═╝
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::LookTransform;
+
+    use approx::assert_relative_eq;
+    use bevy::render::camera::{OrthographicProjection, PerspectiveProjection};
+
+    #[test]
+    fn test_perspective_rays_spread_out_to_the_frustum_edges() {
+        let viewport_size = Vec2::new(800.0, 600.0);
+        let mut projection = PerspectiveProjection::default();
+        projection.update(viewport_size.x, viewport_size.y);
+        let look_transform = LookTransform::new(Vec3::new(0.0, 0.0, 5.0), Vec3::ZERO);
+        let camera_transform = GlobalTransform::from(Transform::from(look_transform));
+
+        let center =
+            pointer_ray(&camera_transform, &projection, viewport_size, viewport_size / 2.0);
+        assert!(center.direction.abs_diff_eq(-Vec3::Z, 1e-5));
+        assert!(center.origin.abs_diff_eq(Vec3::new(0.0, 0.0, 5.0 - projection.near), 1e-4));
+
+        // The right edge is half the horizontal field of view away from the center.
+        let right = pointer_ray(
+            &camera_transform,
+            &projection,
+            viewport_size,
+            Vec2::new(viewport_size.x, viewport_size.y / 2.0),
+        );
+        let half_width = (projection.fov / 2.0).tan() * projection.aspect_ratio;
+        assert_relative_eq!(right.direction.x / -right.direction.z, half_width, epsilon = 1e-4);
+        assert_relative_eq!(right.direction.y, 0.0, epsilon = 1e-5);
+        assert_relative_eq!(right.direction.length(), 1.0, epsilon = 1e-5);
+    }
+
+    #[test]
+    fn test_orthographic_rays_are_parallel() {
+        let viewport_size = Vec2::new(800.0, 600.0);
+        let mut projection = OrthographicProjection::default();
+        projection.update(viewport_size.x, viewport_size.y);
+        let look_transform = LookTransform::new(Vec3::new(1.0, 2.0, 10.0), Vec3::new(1.0, 2.0, 0.0));
+        let camera_transform = GlobalTransform::from(Transform::from(look_transform));
+
+        // With the default scaling, one pixel is one world unit.
+        let corner = pointer_ray(&camera_transform, &projection, viewport_size, Vec2::ZERO);
+        assert!(corner.direction.abs_diff_eq(-Vec3::Z, 1e-5));
+        assert!(corner.origin.truncate().abs_diff_eq(Vec2::new(-399.0, -298.0), 1e-3));
+
+        let ground = corner.at(10.0 - projection.near);
+        assert_relative_eq!(ground.z, 0.0, epsilon = 1e-3);
+    }
+}
@@ -0,0 +1,135 @@
+use crate::SmoothingMethod;
+
+use serde::{Deserialize, Serialize};
+
+/// A resource that tones down camera motion for players who are sensitive to it, e.g. to honor the operating system's
+/// reduced-motion preference with one switch.
+///
+/// While `enabled`, every built-in controller and the `Smoother` respond more directly: smoothing is capped by
+/// `max_lag_weight`, the FPS head bob and the orbit momentum, idle rotation, and elastic pitch are turned off, and the chase
+/// camera's heading no longer swings past the back of the vehicle. Tweens and dolly zooms cut straight to their end, camera
+/// paths cut from keyframe to keyframe, and `LookTransformOffset`s, like camera shake, are scaled by `shake_scale`.
+///
+/// ```rust
+/// fn apply_accessibility(settings: Res<Settings>, mut reduced_motion: ResMut<ReducedMotion>) {
+///     reduced_motion.enabled = settings.reduce_motion;
+/// }
+/// ```
+#[derive(Clone, Copy, Debug, Deserialize, PartialEq, Serialize)]
+pub struct ReducedMotion {
+    pub enabled: bool,
+    /// The highest lag weight that smoothing uses while enabled, between `0.0` and `1.0`. `0.0` responds immediately.
+    /// Spring smoothing is capped at the half-life that closes as much distance in the same time.
+    pub max_lag_weight: f32,
+    /// Scales camera shake, and every other `LookTransformOffset`, while enabled. `0.0` turns it off.
+    pub shake_scale: f32,
+}
+
+impl Default for ReducedMotion {
+    fn default() -> Self {
+        Self {
+            enabled: false,
+            max_lag_weight: 0.5,
+            shake_scale: 0.0,
+        }
+    }
+}
+
+impl ReducedMotion {
+    pub fn new(enabled: bool) -> Self {
+        Self {
+            enabled,
+            ..Default::default()
+        }
+    }
+
+    /// The lag weight to smooth with in place of `lag_weight`.
+    pub fn lag_weight(&self, lag_weight: f32) -> f32 {
+        if self.enabled {
+            lag_weight.min(self.max_lag_weight)
+        } else {
+            lag_weight
+        }
+    }
+
+    /// The smoothing method to use in place of `method`.
+    pub fn smoothing_method(&self, method: SmoothingMethod) -> SmoothingMethod {
+        match method {
+            SmoothingMethod::Spring {
+                eye_half_life,
+                target_half_life,
+            } if self.enabled => {
+                // A lag weight leaves that fraction after a sixtieth of a second, so it halves the distance after this.
+                let max_half_life = if self.max_lag_weight > 0.0 {
+                    (0.5f32.ln() / self.max_lag_weight.ln()).max(0.0) / 60.0
+                } else {
+                    0.0
+                };
+                SmoothingMethod::Spring {
+                    eye_half_life: eye_half_life.min(max_half_life),
+                    target_half_life: target_half_life.min(max_half_life),
+                }
+            }
+            method => method,
+        }
+    }
+
+    /// Whether the camera may keep moving on its own, like momentum after a drag, a swinging spring, or an animated
+    /// transition.
+    pub fn allows_inertia(&self) -> bool {
+        !self.enabled
+    }
+
+    /// The amplitude to shake the camera by in place of `amplitude`.
+    pub fn shake(&self, amplitude: f32) -> f32 {
+        if self.enabled {
+            amplitude * self.shake_scale
+        } else {
+            amplitude
+        }
+    }
+}
+
+// ████████╗███████╗███████╗████████╗
+// ╚══██╔══╝██╔════╝██╔════╝╚══██╔══╝
+//    ██║   █████╗  ███████╗   ██║
+//    ██║   ██╔══╝  ╚════██║   ██║
+//    ██║   ███████╗███████║   ██║
+//    ╚═╝   ╚══════╝╚══════╝   ╚═╝
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    use approx::assert_relative_eq;
+
+    #[test]
+    fn test_reduced_motion_only_applies_while_enabled() {
+        let spring = SmoothingMethod::Spring {
+            eye_half_life: 0.5,
+            target_half_life: 0.001,
+        };
+
+        let off = ReducedMotion::default();
+        assert_eq!(off.lag_weight(0.9), 0.9);
+        assert_eq!(off.smoothing_method(spring), spring);
+        assert_eq!(off.shake(2.0), 2.0);
+        assert!(off.allows_inertia());
+
+        let on = ReducedMotion::new(true);
+        assert_eq!(on.lag_weight(0.9), 0.5);
+        assert_eq!(on.lag_weight(0.2), 0.2);
+        assert_eq!(on.shake(2.0), 0.0);
+        assert!(!on.allows_inertia());
+        match on.smoothing_method(spring) {
+            SmoothingMethod::Spring {
+                eye_half_life,
+                target_half_life,
+            } => {
+                assert_relative_eq!(eye_half_life, 1.0 / 60.0);
+                assert_eq!(target_half_life, 0.001);
+            }
+            method => panic!("expected a spring, got {:?}", method),
+        }
+    }
+}
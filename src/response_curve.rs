@@ -0,0 +1,66 @@
+use bevy::{math::prelude::*, reflect::Reflect};
+use serde::{Deserialize, Serialize};
+
+/// How mouse rotation responds to the speed of the mouse, like the acceleration option in an FPS settings menu.
+#[derive(Clone, Copy, Debug, Deserialize, PartialEq, Reflect, Serialize)]
+#[reflect_value(PartialEq, Serialize, Deserialize)]
+pub enum ResponseCurve {
+    /// Rotation is proportional to mouse motion.
+    Linear,
+    /// Scales mouse motion by `(speed / reference_speed)^(exponent - 1.0)`, where the speeds are in the units of the
+    /// `PointerNormalization` per second. With an exponent above `1.0`, fast flicks turn further while slow, small
+    /// adjustments stay precise. Motion at `reference_speed` is unchanged.
+    Power { exponent: f32, reference_speed: f32 },
+}
+
+impl Default for ResponseCurve {
+    fn default() -> Self {
+        Self::Linear
+    }
+}
+
+impl ResponseCurve {
+    /// Applies the curve to `delta`, the motion over the last `dt` seconds.
+    pub fn apply(self, delta: Vec2, dt: f32) -> Vec2 {
+        match self {
+            Self::Linear => delta,
+            Self::Power {
+                exponent,
+                reference_speed,
+            } => {
+                let speed = delta.length() / dt;
+                if !(speed > 0.0 && speed.is_finite() && reference_speed > 0.0) {
+                    return delta;
+                }
+
+                delta * (speed / reference_speed).powf(exponent - 1.0)
+            }
+        }
+    }
+}
+
+// ████████╗███████╗███████╗████████╗
+// ╚══██╔══╝██╔════╝██╔════╝╚══██╔══╝
+//    ██║   █████╗  ███████╗   ██║
+//    ██║   ██╔══╝  ╚════██║   ██║
+//    ██║   ███████╗███████║   ██║
+//    ╚═╝   ╚══════╝╚══════╝   ╚═╝
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_power_curve_amplifies_fast_motion() {
+        let curve = ResponseCurve::Power {
+            exponent: 2.0,
+            reference_speed: 100.0,
+        };
+
+        assert!(curve.apply(Vec2::new(1.0, 0.0), 0.01).abs_diff_eq(Vec2::new(1.0, 0.0), 1e-5));
+        assert!(curve.apply(Vec2::new(2.0, 0.0), 0.01).abs_diff_eq(Vec2::new(4.0, 0.0), 1e-5));
+        assert!(curve.apply(Vec2::new(0.5, 0.0), 0.01).abs_diff_eq(Vec2::new(0.25, 0.0), 1e-5));
+        assert_eq!(curve.apply(Vec2::ZERO, 0.01), Vec2::ZERO);
+        assert_eq!(ResponseCurve::Linear.apply(Vec2::new(2.0, 0.0), 0.01), Vec2::new(2.0, 0.0));
+    }
+}
@@ -0,0 +1,98 @@
+use bevy::ecs::{component::Component, prelude::*};
+use std::collections::VecDeque;
+
+/// A queue of control events to send on given steps of the control systems, standing in for input mapping in headless tests,
+/// CI, and replays. Add it with `AddCameraController::add_scripted_control_events`, usually along with a controller plugin
+/// whose `override_input_system` is set, so the cameras move only in response to the script.
+///
+/// Steps are counted from `0`, one for each time the control systems run, so with a `ControlTimestep::Stepped` timestep, a
+/// script moves the cameras the same way on every run, whatever the frame rate.
+///
+/// ```rust
+/// script.push(0, OrbitControlEvent::Orbit(camera, Vec2::new(0.5, 0.0)));
+/// script.push(30, OrbitControlEvent::Zoom(camera, 0.5));
+/// ```
+pub struct ScriptedControlEvents<E> {
+    queue: VecDeque<(u64, E)>,
+    step: u64,
+}
+
+impl<E> Default for ScriptedControlEvents<E> {
+    fn default() -> Self {
+        Self {
+            queue: VecDeque::new(),
+            step: 0,
+        }
+    }
+}
+
+impl<E> ScriptedControlEvents<E> {
+    /// A script of `(step, event)` pairs, which don't need to be in order.
+    pub fn new(events: impl IntoIterator<Item = (u64, E)>) -> Self {
+        let mut script = Self::default();
+        for (step, event) in events {
+            script.push(step, event);
+        }
+
+        script
+    }
+
+    /// Queues `event` to be sent on `step`, after any events already queued for that step. An event for a step that has
+    /// already passed is sent on the next one.
+    pub fn push(&mut self, step: u64, event: E) {
+        let index = self.queue.iter().take_while(|(s, _)| *s <= step).count();
+        self.queue.insert(index, (step, event));
+    }
+
+    /// The step that the control systems will run next.
+    pub fn step(&self) -> u64 {
+        self.step
+    }
+
+    /// Whether every queued event has been sent.
+    pub fn is_finished(&self) -> bool {
+        self.queue.is_empty()
+    }
+
+    /// Removes the events that are due on the current step, in order, and moves on to the next step.
+    fn advance(&mut self) -> Vec<E> {
+        let due = self.queue.iter().take_while(|(s, _)| *s <= self.step).count();
+        self.step += 1;
+
+        self.queue.drain(..due).map(|(_, event)| event).collect()
+    }
+}
+
+pub(crate) fn scripted_control_events_system<E: Component>(
+    mut script: ResMut<ScriptedControlEvents<E>>,
+    mut events: EventWriter<E>,
+) {
+    for event in script.advance() {
+        events.send(event);
+    }
+}
+
+// ████████╗███████╗███████╗████████╗
+// ╚══██╔══╝██╔════╝██╔════╝╚══██╔══╝
+//    ██║   █████╗  ███████╗   ██║
+//    ██║   ██╔══╝  ╚════██║   ██║
+//    ██║   ███████╗███████║   ██║
+//    ╚═╝   ╚══════╝╚══════╝   ╚═╝
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_script_sends_events_on_their_steps_in_order() {
+        let mut script = ScriptedControlEvents::new(vec![(2, 'c'), (0, 'a'), (2, 'd'), (0, 'b')]);
+        assert_eq!(script.advance(), vec!['a', 'b']);
+        assert!(script.advance().is_empty());
+
+        // An overdue event goes out on the next step.
+        script.push(1, 'e');
+        assert_eq!(script.advance(), vec!['e', 'c', 'd']);
+        assert_eq!(script.step(), 3);
+        assert!(script.is_finished());
+    }
+}
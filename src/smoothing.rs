@@ -0,0 +1,182 @@
+use crate::LookTransform;
+
+use bevy::math::prelude::*;
+use std::ops::{Add, Mul, Sub};
+
+/// The frame time that lag weights are given for.
+const LAG_WEIGHT_FRAME_SECONDS: f32 = 1.0 / 60.0;
+
+/// The highest lag weight that's used, so a weight of `1.0` or more still moves, however slowly.
+const MAX_LAG_WEIGHT: f32 = 1.0 - 1e-4;
+
+/// A value that `smooth` can ease toward a goal.
+pub trait Smoothable: Copy {
+    /// The value `t` of the way from `self` to `goal`.
+    fn lerp_toward(self, goal: Self, t: f32) -> Self;
+}
+
+impl Smoothable for f32 {
+    fn lerp_toward(self, goal: Self, t: f32) -> Self {
+        self + t * (goal - self)
+    }
+}
+
+impl Smoothable for Vec2 {
+    fn lerp_toward(self, goal: Self, t: f32) -> Self {
+        self.lerp(goal, t)
+    }
+}
+
+impl Smoothable for Vec3 {
+    fn lerp_toward(self, goal: Self, t: f32) -> Self {
+        self.lerp(goal, t)
+    }
+}
+
+impl Smoothable for LookTransform {
+    /// Eases the eye and target as positions, and turns the up vector along with them.
+    fn lerp_toward(self, goal: Self, t: f32) -> Self {
+        self.lerp(&goal, t)
+    }
+}
+
+/// Exponentially eases `current` toward `target` over `dt` seconds, like a `Smoother` with `SmoothingMethod::Exponential`,
+/// e.g. for a field of view or the scale of an `OrthographicProjection`.
+///
+/// The `lag` weight is between `0.0` and `1.0`, where higher is smoother and `0.0` jumps straight to `target`. It's the
+/// fraction of the distance that's left after a sixtieth of a second, so the easing is the same at any frame rate. Weights
+/// outside that range are clamped into it.
+///
+/// ```rust
+/// projection.fov = smooth(projection.fov, goal_fov, 0.8, time.delta_seconds());
+/// ```
+pub fn smooth<T: Smoothable>(current: T, target: T, lag: f32, dt: f32) -> T {
+    let lag = clamp_lag_weight(lag);
+    let remaining = if lag > 0.0 {
+        lag.powf(dt / LAG_WEIGHT_FRAME_SECONDS)
+    } else {
+        0.0
+    };
+    current.lerp_toward(target, 1.0 - remaining)
+}
+
+/// Clamps `lag` to the lag weights that ease toward a goal: from `0.0`, which jumps straight to it, to just below `1.0`,
+/// which never gets there. `NaN` jumps.
+pub(crate) fn clamp_lag_weight(lag: f32) -> f32 {
+    if lag.is_nan() {
+        0.0
+    } else {
+        lag.max(0.0).min(MAX_LAG_WEIGHT)
+    }
+}
+
+/// Steps a damped spring that pulls `offset` toward zero with `stiffness` and slows `velocity` with `damping`, by the exact
+/// solution over `dt` seconds, so it's stable for any frame time. Returns the new offset and velocity.
+pub(crate) fn damped_spring<T>(
+    offset: T,
+    velocity: T,
+    stiffness: f32,
+    damping: f32,
+    dt: f32,
+) -> (T, T)
+where
+    T: Copy + Add<Output = T> + Sub<Output = T> + Mul<f32, Output = T>,
+{
+    let half_damping = 0.5 * damping;
+    let discriminant = half_damping * half_damping - stiffness;
+    let decay = (-half_damping * dt).exp();
+
+    if discriminant.abs() <= 1e-4 * stiffness.max(1e-6) {
+        // Critically damped: the fastest settle without overshooting.
+        let j = velocity + offset * half_damping;
+        (
+            (offset + j * dt) * decay,
+            (velocity - j * (half_damping * dt)) * decay,
+        )
+    } else if discriminant < 0.0 {
+        // Underdamped: swings past zero before settling.
+        let frequency = (-discriminant).sqrt();
+        let (sin, cos) = (frequency * dt).sin_cos();
+        let j = velocity + offset * half_damping;
+        let k = velocity * half_damping + offset * stiffness;
+        (
+            (offset * cos + j * (sin / frequency)) * decay,
+            (velocity * cos - k * (sin / frequency)) * decay,
+        )
+    } else {
+        // Overdamped: creeps toward zero as the sum of two decays.
+        let root = discriminant.sqrt();
+        let (r1, r2) = (-half_damping + root, -half_damping - root);
+        let a = (velocity - offset * r2) * (1.0 / (r1 - r2));
+        let b = offset - a;
+        let (e1, e2) = ((r1 * dt).exp(), (r2 * dt).exp());
+        (a * e1 + b * e2, a * (r1 * e1) + b * (r2 * e2))
+    }
+}
+
+// ████████╗███████╗███████╗████████╗
+// ╚══██╔══╝██╔════╝██╔════╝╚══██╔══╝
+//    ██║   █████╗  ███████╗   ██║
+//    ██║   ██╔══╝  ╚════██║   ██║
+//    ██║   ███████╗███████║   ██║
+//    ╚═╝   ╚══════╝╚══════╝   ╚═╝
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    use approx::assert_relative_eq;
+
+    #[test]
+    fn test_smooth_is_independent_of_frame_rate() {
+        assert_relative_eq!(smooth(0.0, 1.0, 0.5, 1.0 / 60.0), 0.5);
+
+        let mut value = Vec3::ZERO;
+        for _ in 0..4 {
+            value = smooth(value, Vec3::X, 0.5, 1.0 / 240.0);
+        }
+        assert!(value.abs_diff_eq(Vec3::new(0.5, 0.0, 0.0), 1e-5));
+
+        assert_eq!(smooth(0.0, 1.0, 0.0, 0.0), 1.0);
+        assert_eq!(smooth(0.0, 1.0, 0.8, 0.0), 0.0);
+    }
+
+    #[test]
+    fn test_out_of_range_lag_weights_are_clamped() {
+        assert_eq!(smooth(0.0, 1.0, -0.5, 1.0 / 60.0), 1.0);
+        assert_eq!(smooth(0.0, 1.0, f32::NAN, 1.0 / 60.0), 1.0);
+
+        // A weight of one or more still creeps toward the goal rather than standing still or running away.
+        for &lag in [1.0, 2.0, f32::INFINITY].iter() {
+            let value = smooth(0.0, 1.0, lag, 1.0 / 60.0);
+            assert!(value > 0.0 && value < 1e-3);
+        }
+    }
+
+    #[test]
+    fn test_damped_spring_is_exact_for_long_frames() {
+        // However long the frame, one step lands where many short ones do.
+        let critical = 2.0 * 20.0f32.sqrt();
+        for &(stiffness, damping) in [(20.0f32, critical), (20.0, 2.0), (20.0, 20.0)].iter() {
+            let (mut offset, mut velocity) = (1.0f32, 0.5f32);
+            for _ in 0..500 {
+                let (o, v) = damped_spring(offset, velocity, stiffness, damping, 0.001);
+                offset = o;
+                velocity = v;
+            }
+            let (long_offset, long_velocity) = damped_spring(1.0f32, 0.5, stiffness, damping, 0.5);
+            assert_relative_eq!(long_offset, offset, epsilon = 1e-3);
+            assert_relative_eq!(long_velocity, velocity, epsilon = 1e-3);
+        }
+    }
+
+    #[test]
+    fn test_smoothed_look_transform_keeps_a_unit_up() {
+        let from = LookTransform::look_at(Vec3::ZERO, -Vec3::Z, Vec3::Y);
+        let to = LookTransform::look_at(Vec3::X, Vec3::X - Vec3::Z, Vec3::X);
+
+        let halfway = smooth(from, to, 0.5, 1.0 / 60.0);
+        assert!(halfway.eye.abs_diff_eq(Vec3::new(0.5, 0.0, 0.0), 1e-5));
+        assert_relative_eq!(halfway.up.length(), 1.0, epsilon = 1e-5);
+    }
+}
@@ -0,0 +1,117 @@
+use bevy::{input::touch::Touches, math::prelude::*};
+
+/// The motion made by the active touches over the last frame.
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub enum TouchGesture {
+    None,
+    /// A single finger moved by this delta.
+    Drag(Vec2),
+    /// Two fingers moved. `delta` is the motion of their midpoint, and `pinch_scale` is the ratio of the previous distance
+    /// between the fingers to the current one, so it's less than `1.0` when the fingers spread apart. `twist` is the angle in
+    /// radians that the line between the fingers turned, positive from the touch X axis toward the Y axis.
+    TwoFingerDrag {
+        delta: Vec2,
+        pinch_scale: f32,
+        twist: f32,
+    },
+}
+
+impl TouchGesture {
+    pub fn from_touches(touches: &Touches) -> Self {
+        let positions: Vec<(Vec2, Vec2)> = touches
+            .iter()
+            .map(|t| (t.previous_position(), t.position()))
+            .collect();
+
+        Self::from_positions(&positions)
+    }
+
+    /// Recognizes the gesture from `(previous, current)` positions of each touch. Gestures with more than two fingers are
+    /// ignored.
+    pub fn from_positions(positions: &[(Vec2, Vec2)]) -> Self {
+        match *positions {
+            [(prev, cur)] => Self::Drag(cur - prev),
+            [(prev_a, cur_a), (prev_b, cur_b)] => {
+                let delta = 0.5 * ((cur_a - prev_a) + (cur_b - prev_b));
+                let prev_distance = prev_a.distance(prev_b);
+                let cur_distance = cur_a.distance(cur_b);
+                let pinch_scale = if cur_distance > 0.0 && prev_distance > 0.0 {
+                    prev_distance / cur_distance
+                } else {
+                    1.0
+                };
+
+                let (prev_line, cur_line) = (prev_b - prev_a, cur_b - cur_a);
+                let twist = (prev_line.x * cur_line.y - prev_line.y * cur_line.x)
+                    .atan2(prev_line.dot(cur_line));
+
+                Self::TwoFingerDrag {
+                    delta,
+                    pinch_scale,
+                    twist,
+                }
+            }
+            _ => Self::None,
+        }
+    }
+}
+
+// ████████╗███████╗███████╗████████╗
+// ╚══██╔══╝██╔════╝██╔════╝╚══██╔══╝
+//    ██║   █████╗  ███████╗   ██║
+//    ██║   ██╔══╝  ╚════██║   ██║
+//    ██║   ███████╗███████║   ██║
+//    ╚═╝   ╚══════╝╚══════╝   ╚═╝
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    use approx::assert_relative_eq;
+
+    #[test]
+    fn test_one_finger_drag() {
+        let gesture = TouchGesture::from_positions(&[(Vec2::ZERO, Vec2::new(3.0, 4.0))]);
+
+        assert_eq!(gesture, TouchGesture::Drag(Vec2::new(3.0, 4.0)));
+    }
+
+    #[test]
+    fn test_pinch_apart_zooms_in() {
+        let gesture = TouchGesture::from_positions(&[
+            (Vec2::new(-1.0, 0.0), Vec2::new(-2.0, 0.0)),
+            (Vec2::new(1.0, 0.0), Vec2::new(2.0, 0.0)),
+        ]);
+
+        if let TouchGesture::TwoFingerDrag {
+            delta,
+            pinch_scale,
+            twist,
+        } = gesture
+        {
+            assert_eq!(delta, Vec2::ZERO);
+            assert_relative_eq!(pinch_scale, 0.5);
+            assert_relative_eq!(twist, 0.0);
+        } else {
+            panic!("Expected a two finger drag, got {:?}", gesture);
+        }
+    }
+
+    #[test]
+    fn test_two_finger_twist() {
+        let gesture = TouchGesture::from_positions(&[
+            (Vec2::new(-1.0, 0.0), Vec2::new(0.0, -1.0)),
+            (Vec2::new(1.0, 0.0), Vec2::new(0.0, 1.0)),
+        ]);
+
+        if let TouchGesture::TwoFingerDrag {
+            pinch_scale, twist, ..
+        } = gesture
+        {
+            assert_relative_eq!(pinch_scale, 1.0);
+            assert_relative_eq!(twist, std::f32::consts::FRAC_PI_2);
+        } else {
+            panic!("Expected a two finger drag, got {:?}", gesture);
+        }
+    }
+}
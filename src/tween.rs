@@ -0,0 +1,138 @@
+use crate::{ControlTimestep, LookTransform, ReducedMotion};
+
+use bevy::{core::Time, ecs::prelude::*};
+use serde::{Deserialize, Serialize};
+
+/// The shape of a tween's progress over time.
+#[derive(Clone, Copy, Debug, Deserialize, PartialEq, Serialize)]
+pub enum Easing {
+    Linear,
+    EaseIn,
+    EaseOut,
+    EaseInOut,
+}
+
+impl Default for Easing {
+    fn default() -> Self {
+        Easing::EaseInOut
+    }
+}
+
+impl Easing {
+    /// Maps linear progress `t` in `[0.0, 1.0]` to eased progress in `[0.0, 1.0]`.
+    pub fn apply(self, t: f32) -> f32 {
+        let t = t.min(1.0).max(0.0);
+        match self {
+            Easing::Linear => t,
+            Easing::EaseIn => t * t * t,
+            Easing::EaseOut => 1.0 - (1.0 - t).powi(3),
+            Easing::EaseInOut => t * t * (3.0 - 2.0 * t),
+        }
+    }
+}
+
+/// Flies a camera's `LookTransform` to `end` over `duration` seconds, like a cut-scene camera move. Controllers ignore input
+/// for the camera while this component is present, and it removes itself once the move is finished. While `ReducedMotion`
+/// is enabled, the camera cuts straight to `end`.
+///
+/// ```rust
+/// commands
+///     .entity(camera)
+///     .insert(LookTransformTween::ease_to(destination, 2.0, Easing::EaseInOut));
+/// ```
+#[derive(Clone, Copy, Debug)]
+pub struct LookTransformTween {
+    pub end: LookTransform,
+    pub duration: f32,
+    pub easing: Easing,
+    start: Option<LookTransform>,
+    elapsed: f32,
+}
+
+impl LookTransformTween {
+    pub fn ease_to(end: LookTransform, duration: f32, easing: Easing) -> Self {
+        Self {
+            end,
+            duration,
+            easing,
+            start: None,
+            elapsed: 0.0,
+        }
+    }
+
+    /// Progress through the tween in `[0.0, 1.0]`, before easing.
+    pub fn progress(&self) -> f32 {
+        if self.duration > 0.0 {
+            (self.elapsed / self.duration).min(1.0)
+        } else {
+            1.0
+        }
+    }
+
+    pub fn is_finished(&self) -> bool {
+        self.progress() >= 1.0
+    }
+}
+
+pub(crate) fn look_transform_tween_system(
+    mut commands: Commands,
+    time: Res<Time>,
+    timestep: Res<ControlTimestep>,
+    reduced_motion: Res<ReducedMotion>,
+    mut cameras: Query<(Entity, &mut LookTransformTween, &mut LookTransform)>,
+) {
+    for (entity, mut tween, mut transform) in cameras.iter_mut() {
+        // The tween starts from wherever the camera is on the first frame it's seen.
+        let start = *tween.start.get_or_insert(*transform);
+        tween.elapsed += timestep.frame_delta_seconds(&time);
+        if !reduced_motion.allows_inertia() {
+            tween.elapsed = tween.elapsed.max(tween.duration);
+        }
+
+        let s = tween.easing.apply(tween.progress());
+        *transform = start.lerp(&tween.end, s);
+
+        if tween.is_finished() {
+            commands.entity(entity).remove::<LookTransformTween>();
+        }
+    }
+}
+
+// ████████╗███████╗███████╗████████╗
+// ╚══██╔══╝██╔════╝██╔════╝╚══██╔══╝
+//    ██║   █████╗  ███████╗   ██║
+//    ██║   ██╔══╝  ╚════██║   ██║
+//    ██║   ███████╗███████║   ██║
+//    ╚═╝   ╚══════╝╚══════╝   ╚═╝
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    use approx::assert_relative_eq;
+
+    #[test]
+    fn test_easing_endpoints() {
+        for easing in [
+            Easing::Linear,
+            Easing::EaseIn,
+            Easing::EaseOut,
+            Easing::EaseInOut,
+        ]
+        .iter()
+        {
+            assert_relative_eq!(easing.apply(0.0), 0.0);
+            assert_relative_eq!(easing.apply(1.0), 1.0);
+            assert_relative_eq!(easing.apply(2.0), 1.0);
+        }
+    }
+
+    #[test]
+    fn test_ease_in_out_is_symmetric() {
+        assert_relative_eq!(Easing::EaseInOut.apply(0.5), 0.5);
+        assert_relative_eq!(
+            Easing::EaseInOut.apply(0.25),
+            1.0 - Easing::EaseInOut.apply(0.75)
+        );
+    }
+}
@@ -0,0 +1,248 @@
+use crate::{BindingInput, InputBinding};
+use bevy::{
+    ecs::prelude::*,
+    input::{mouse::MouseButton, Input},
+    math::prelude::*,
+    window::{Window, WindowId, Windows},
+};
+use std::collections::{HashMap, HashSet};
+
+/// Restricts a controller's pointer input to one window, and optionally to a rectangle within it, for apps with several
+/// windows or split-screen views. Mouse motion, buttons, scrolling, and touches only reach the controller while the cursor is
+/// inside. Keyboard input isn't affected.
+///
+/// Without this component, a controller takes pointer input from every window and uses the primary window's cursor.
+///
+/// ```rust
+/// commands
+///     .entity(camera)
+///     .insert(ControllerViewport::rect(WindowId::primary(), Vec2::ZERO, Vec2::new(640.0, 720.0)));
+/// ```
+#[derive(Clone, Copy, Debug)]
+pub struct ControllerViewport {
+    pub window: WindowId,
+    /// The minimum and maximum corners of the rectangle, in pixels with the origin at the bottom-left of the window. `None`
+    /// covers the whole window.
+    pub rect: Option<(Vec2, Vec2)>,
+}
+
+impl ControllerViewport {
+    pub fn window(window: WindowId) -> Self {
+        Self { window, rect: None }
+    }
+
+    pub fn rect(window: WindowId, min: Vec2, max: Vec2) -> Self {
+        Self {
+            window,
+            rect: Some((min, max)),
+        }
+    }
+
+    /// The minimum and maximum corners of the viewport in `window`.
+    pub fn bounds(&self, window: &Window) -> (Vec2, Vec2) {
+        self.rect
+            .unwrap_or_else(|| (Vec2::ZERO, Vec2::new(window.width(), window.height())))
+    }
+
+    pub fn contains(&self, window: &Window, point: Vec2) -> bool {
+        let (min, max) = self.bounds(window);
+
+        min.x <= point.x && point.x <= max.x && min.y <= point.y && point.y <= max.y
+    }
+}
+
+/// The window that a controller takes pointer input from.
+pub(crate) fn viewport_window<'a>(
+    windows: &'a Windows,
+    viewport: Option<&ControllerViewport>,
+) -> Option<&'a Window> {
+    match viewport {
+        Some(viewport) => windows.get(viewport.window),
+        None => windows.get_primary(),
+    }
+}
+
+/// The size in pixels of a controller's viewport in `window`, which is the whole window without a `ControllerViewport`.
+pub(crate) fn viewport_size(window: &Window, viewport: Option<&ControllerViewport>) -> Vec2 {
+    let (min, max) = viewport
+        .map(|v| v.bounds(window))
+        .unwrap_or_else(|| (Vec2::ZERO, Vec2::new(window.width(), window.height())));
+
+    max - min
+}
+
+/// The window that a controller takes pointer input from and the cursor position in it. The cursor is `None` when it's outside
+/// the controller's viewport.
+pub(crate) fn viewport_cursor<'a>(
+    windows: &'a Windows,
+    viewport: Option<&ControllerViewport>,
+) -> (Option<&'a Window>, Option<Vec2>) {
+    let window = viewport_window(windows, viewport);
+    let cursor = window.and_then(|w| {
+        w.cursor_position()
+            .filter(|&cursor| viewport.map_or(true, |v| v.contains(w, cursor)))
+    });
+
+    (window, cursor)
+}
+
+/// Whether pointer input should reach a controller with this viewport.
+pub(crate) fn pointer_in_viewport(windows: &Windows, viewport: Option<&ControllerViewport>) -> bool {
+    viewport.is_none() || viewport_cursor(windows, viewport).1.is_some()
+}
+
+/// The mouse drags in progress on each camera. A drag only starts when its button is pressed with the cursor over the
+/// camera's viewport, so a press on another window, the title bar, or a UI panel never turns into camera motion. Once
+/// started, a drag carries on outside the viewport, but pauses while the cursor is outside the window.
+///
+/// Presses are found by comparing with the last update rather than with `Input::just_pressed`, so they aren't missed by
+/// systems that don't run every frame.
+#[derive(Default)]
+pub(crate) struct DragState {
+    held: HashSet<(Entity, MouseButton)>,
+    dragging: HashSet<(Entity, MouseButton)>,
+    /// Where the cursor was when each button was pressed, until it moves far enough to start a drag.
+    pressed_at: HashMap<(Entity, MouseButton), Vec2>,
+    /// The cursor position at the last update of each active drag, and how far it moved since the one before.
+    cursors: HashMap<(Entity, MouseButton), (Vec2, Vec2)>,
+}
+
+impl DragState {
+    /// Drops the drags of every camera that `keep` returns false for, e.g. ones that were despawned.
+    pub(crate) fn retain(&mut self, keep: impl Fn(Entity) -> bool) {
+        self.held.retain(|&(camera, _)| keep(camera));
+        self.dragging.retain(|&(camera, _)| keep(camera));
+        self.pressed_at.retain(|&(camera, _), _| keep(camera));
+        self.cursors.retain(|&(camera, _), _| keep(camera));
+    }
+
+    /// Starts or ends the drag of `button` on `camera`, and returns whether motion should be applied to it this frame.
+    /// `can_start` should only be `true` while the cursor is over the camera's viewport and not captured, e.g. by a UI. The
+    /// drag only starts once the cursor has moved `threshold` logical pixels from where the button was pressed, so the small
+    /// twitches of a click don't move the camera.
+    pub fn update_with_threshold(
+        &mut self,
+        camera: Entity,
+        button: MouseButton,
+        mouse_buttons: &Input<MouseButton>,
+        window: Option<&Window>,
+        can_start: bool,
+        threshold: f32,
+    ) -> bool {
+        let key = (camera, button);
+        let cursor = window.and_then(|w| w.cursor_position());
+        if !mouse_buttons.pressed(button) {
+            self.held.remove(&key);
+            self.dragging.remove(&key);
+            self.pressed_at.remove(&key);
+        } else if self.held.insert(key) && can_start {
+            match cursor.filter(|_| threshold > 0.0) {
+                Some(cursor) => {
+                    self.pressed_at.insert(key, cursor);
+                }
+                None => {
+                    self.dragging.insert(key);
+                }
+            }
+        }
+
+        if let (Some(&origin), Some(cursor)) = (self.pressed_at.get(&key), cursor) {
+            if origin.distance(cursor) >= threshold {
+                self.pressed_at.remove(&key);
+                self.dragging.insert(key);
+            }
+        }
+
+        match cursor.filter(|_| self.dragging.contains(&key)) {
+            Some(cursor) => {
+                let previous = self.cursors.get(&key).map_or(cursor, |&(previous, _)| previous);
+                self.cursors.insert(key, (cursor, cursor - previous));
+                true
+            }
+            None => {
+                self.cursors.remove(&key);
+                false
+            }
+        }
+    }
+
+    /// Runs `update_with_threshold` for the button of every mouse binding in `bindings`. A binding with modifier keys only
+    /// starts its drag while exactly those are held. Returns the button that's dragging, if any.
+    pub(crate) fn update_bindings(
+        &mut self,
+        camera: Entity,
+        bindings: &[InputBinding],
+        input: &BindingInput,
+        window: Option<&Window>,
+        can_start: bool,
+        threshold: f32,
+    ) -> Option<MouseButton> {
+        let can_start = can_start && input.source.accepts_keyboard_mouse();
+        // Several bindings can share a button, which must only be updated once.
+        let mut buttons: Vec<(MouseButton, bool)> = Vec::new();
+        for (button, modifiers) in bindings.iter().filter_map(InputBinding::mouse_button) {
+            let modifiers_match =
+                modifiers.map_or(true, |modifiers| input.modifiers_match(modifiers));
+            match buttons.iter_mut().find(|(b, _)| *b == button) {
+                Some((_, can_start)) => *can_start |= modifiers_match,
+                None => buttons.push((button, modifiers_match)),
+            }
+        }
+
+        let mut dragging = None;
+        for (button, modifiers_match) in buttons {
+            let can_start = can_start && modifiers_match;
+            let held = self.update_with_threshold(
+                camera,
+                button,
+                input.mouse_buttons,
+                window,
+                can_start,
+                threshold,
+            );
+            if held {
+                dragging = dragging.or(Some(button));
+            }
+        }
+        dragging
+    }
+
+    /// How far the cursor moved in logical pixels during the drag of `button` on `camera`, between the last two updates.
+    /// Unlike mouse motion, this follows the cursor exactly, including any pointer acceleration. It's zero on the first
+    /// update of a drag, and again when the cursor comes back into the window.
+    pub fn cursor_delta(&self, camera: Entity, button: MouseButton) -> Vec2 {
+        self.cursors
+            .get(&(camera, button))
+            .map_or(Vec2::ZERO, |&(_, delta)| delta)
+    }
+}
+
+/// A resource that tells other systems, like object picking, whether a camera has turned the current or last press of each
+/// mouse button into a drag. Check it when the button is released to tell a click apart from a camera drag.
+///
+/// The orbit controller marks the buttons of its drags once they pass its `drag_threshold`. The marks are cleared when the
+/// button is pressed again.
+#[derive(Debug, Default)]
+pub struct ConsumedDrags {
+    buttons: HashSet<MouseButton>,
+}
+
+impl ConsumedDrags {
+    /// Whether a camera drag has used the latest press of `button`.
+    pub fn consumed(&self, button: MouseButton) -> bool {
+        self.buttons.contains(&button)
+    }
+
+    pub(crate) fn consume(&mut self, button: MouseButton) {
+        self.buttons.insert(button);
+    }
+}
+
+pub(crate) fn consumed_drags_system(
+    mut consumed_drags: ResMut<ConsumedDrags>,
+    mouse_buttons: Res<Input<MouseButton>>,
+) {
+    for button in mouse_buttons.get_just_pressed() {
+        consumed_drags.buttons.remove(button);
+    }
+}